@@ -0,0 +1,23 @@
+#include <stdio.h>
+#include <sys/wait.h>
+#include <unistd.h>
+
+void child_work(int n) { printf("child %d of %d working\n", n, getppid()); }
+
+int main() {
+  pid_t pid = fork();
+  if (pid == 0) {
+    child_work(1);
+    return 0;
+  }
+  waitpid(pid, NULL, 0);
+
+  pid = vfork();
+  if (pid == 0) {
+    execl("./target/debug/dummy", "dummy", NULL);
+    _exit(1);
+  }
+  waitpid(pid, NULL, 0);
+  printf("parent %d done\n", getpid());
+  return 0;
+}
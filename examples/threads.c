@@ -0,0 +1,26 @@
+#include <pthread.h>
+#include <stdio.h>
+#include <unistd.h>
+
+void worker_step(long id, int i) { printf("worker %ld: %i\n", id, i); }
+
+void *worker(void *arg) {
+  long id = (long)arg;
+  for (int i = 0; i < 5; i++) {
+    worker_step(id, i);
+    usleep(1000);
+  }
+  return NULL;
+}
+
+int main() {
+  pthread_t threads[3];
+  for (long i = 0; i < 3; i++) {
+    pthread_create(&threads[i], NULL, worker, (void *)i);
+  }
+  for (int i = 0; i < 3; i++) {
+    pthread_join(threads[i], NULL);
+  }
+  printf("all workers done\n");
+  return 0;
+}
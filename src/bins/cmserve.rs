@@ -5,7 +5,9 @@ use coreminer::addr::Addr;
 use coreminer::debugger::Debugger;
 use coreminer::errors::DebuggerError;
 use coreminer::feedback::Feedback;
+use coreminer::skiplist::{SkipList, SkipRule};
 use coreminer::ui::json::{Input, JsonUI};
+use coreminer::watchpoint::{WatchKind, WatchpointHit};
 
 use clap::Parser;
 use coreminer::feedback::Status;
@@ -86,14 +88,44 @@ fn main() -> Result<(), DebuggerError> {
 fn example_statuses() {
     let statuses: &[Status] = &[
         Status::StepOut,
+        Status::Finish,
+        Status::StepLine,
+        Status::NextLine,
+        Status::RunUntil(coreminer::location::Location::Line("main.c".into(), 12)),
+        Status::RunUntil(coreminer::location::Location::Caller),
         Status::DebuggerQuit,
         Status::Continue,
+        Status::ContinueAsync,
+        Status::Interrupt,
         Status::ProcMap,
         #[cfg(feature = "plugins")]
         Status::PluginSetEnable(PluginIDOwned::from("foobar"), true),
         #[cfg(feature = "plugins")]
         Status::PluginGetStatus(PluginIDOwned::from("foobar")),
         Status::SetBreakpoint(Addr::from(21958295usize)),
+        Status::SetBreakpointAtSymbol("main".to_string()),
+        Status::SetBreakpointAtLine("main.c".into(), 12),
+        Status::SetBreakpointCondition(
+            Addr::from(21958295usize),
+            Some("counter > 100".parse().unwrap()),
+        ),
+        Status::SetBreakpointIgnoreCount(Addr::from(21958295usize), 9),
+        Status::SetTemporaryBreakpoint(Addr::from(21958295usize)),
+        Status::SetHardwareBreakpoint(Addr::from(21958295usize)),
+        Status::SetWatchpoint(Addr::from(21958296usize), 4, WatchKind::Write),
+        Status::SetWatchpoint(Addr::from(21958296usize), 64, WatchKind::ReadWrite),
+        Status::SetWatchpointAtVariable("counter".to_string(), WatchKind::ReadWrite),
+        Status::DelWatchpoint(Addr::from(21958296usize)),
+        Status::ListWatchpoints,
+        Status::ListLibraries,
+        Status::SetBreakpointCommands(
+            Addr::from(21958295usize),
+            vec![
+                Status::ReadVariable("counter".to_string()),
+                Status::Backtrace,
+            ],
+        ),
+        Status::ListBreakpoints,
         Status::SetRegister(coreminer::Register::r9, 133719),
         Status::DumpRegisters,
         Status::Backtrace,
@@ -102,7 +134,39 @@ fn example_statuses() {
         Status::Run(
             Path::new("/bin/ls").into(),
             vec![c"/etc".into(), c"-la".into()],
+            coreminer::launch::LaunchOptions::default(),
         ),
+        Status::Run(Path::new("/bin/ls").into(), vec![c"-la".into()], {
+            let mut options = coreminer::launch::LaunchOptions::default();
+            options.set_env("LANG", "C");
+            options.cwd = Some("/etc".into());
+            options.stdout = Some("/tmp/ls.out".into());
+            options.disable_aslr = true;
+            options
+        }),
+        Status::Restart,
+        Status::Attach(1337),
+        Status::Detach,
+        Status::LoadCore("/tmp/core".into(), Some("/tmp/crashed".into())),
+        Status::GenerateCore("/tmp/gcore".into()),
+        Status::SaveSession("/tmp/session.json".into()),
+        Status::LoadSession("/tmp/session.json".into()),
+        Status::ListThreads,
+        Status::SelectThread(1338),
+        Status::SetFollowFork(coreminer::process::FollowForkMode::Child),
+        Status::ListProcesses,
+        Status::SelectProcess(1339),
+        Status::TraceSyscalls(true),
+        Status::CatchSyscall("write".to_string()),
+        Status::DelCatchSyscall("write".to_string()),
+        Status::SetSignalPolicy(
+            nix::sys::signal::Signal::SIGUSR1 as i32,
+            coreminer::signal::SignalPolicy::new(false, true, true),
+        ),
+        Status::ListSignalPolicies,
+        Status::AddSkip(SkipRule::Library("libc.so.6".into())),
+        Status::DelSkip(SkipRule::Function("std::*".to_string())),
+        Status::ListSkips,
         Status::GetSymbolsByName("main".to_string()),
         Status::DisassembleAt(Addr::from(1337139usize), 50, false),
     ];
@@ -118,12 +182,67 @@ fn example_statuses() {
 fn example_feedbacks() {
     let feedbacks: &[Feedback] = &[
         Feedback::Ok,
+        Feedback::Running,
         Feedback::Word(921589215 as Word),
         Feedback::Word(Word::MAX),
         Feedback::Word(Word::MIN),
+        Feedback::LineAddrs(12, vec![Addr::from(21958295usize)]),
+        Feedback::Breakpoints(vec![coreminer::breakpoint::BreakpointInfo {
+            addr: Addr::from(21958295usize),
+            kind: coreminer::breakpoint::BreakpointKind::Software,
+            enabled: true,
+            hits: 3,
+            settings: coreminer::breakpoint::BreakpointSettings {
+                condition: Some("counter > 100".parse().unwrap()),
+                ignore_count: 2,
+                temporary: false,
+                commands: vec![Status::DumpRegisters],
+            },
+        }]),
+        Feedback::Watchpoint(WatchpointHit {
+            addr: Addr::from(21958296usize),
+            len: 4,
+            kind: WatchKind::Write,
+            instruction: Some(Addr::from(21958295usize)),
+            accessed: None,
+            old: vec![41, 0, 0, 0],
+            new: vec![42, 0, 0, 0],
+        }),
+        Feedback::Logpoint(
+            Addr::from(21958295usize),
+            vec![Feedback::Variable(
+                coreminer::variable::VariableValue::Bytes(vec![101, 0, 0, 0]),
+            )],
+        ),
+        Feedback::PendingBreakpoint("plugin_init".to_string()),
+        Feedback::Libraries(vec![coreminer::sharedlib::LibraryInfo {
+            path: "/usr/lib/libc.so.6".into(),
+            base: Addr::from(0x7f3a_c2a00000usize),
+        }]),
+        Feedback::Library(coreminer::sharedlib::LibraryEvent {
+            loaded: vec![coreminer::sharedlib::LibraryInfo {
+                path: "/usr/lib/libplugin.so".into(),
+                base: Addr::from(0x7f3a_c2e00000usize),
+            }],
+            unloaded: vec![],
+            breakpoints: vec![("plugin_init".to_string(), Addr::from(0x7f3a_c2e01139usize))],
+        }),
         Feedback::Variable(coreminer::variable::VariableValue::Bytes(vec![
             19, 13, 13, 13, 17,
         ])),
+        Feedback::ReturnValue(coreminer::returnvalue::ReturnValue {
+            function: Some("square".to_string()),
+            value: Some(coreminer::variable::VariableValue::Bytes(vec![25, 0, 0, 0])),
+        }),
+        Feedback::RunUntil(coreminer::location::UntilStop {
+            location: coreminer::location::Location::Function("square".to_string()),
+            reason: coreminer::location::StopReason::Reached(Addr::from(0x5555_55555139usize)),
+        }),
+        Feedback::SkipList({
+            let mut skip_list = SkipList::default();
+            skip_list.add(SkipRule::SourcePrefix("/rustc".into()));
+            skip_list
+        }),
         Feedback::Error(DebuggerError::BreakpointIsAlreadyEnabled),
         Feedback::Error(DebuggerError::UnimplementedRegister(1337)),
         Feedback::Error(DebuggerError::Json(serde_json::Error::custom("test err"))),
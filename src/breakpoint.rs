@@ -9,12 +9,29 @@
 //! When a breakpoint is hit, the debugger can then restore the original instruction, single-step
 //! the process to execute that instruction, and then replace the breakpoint before continuing
 //! execution.
+//!
+//! A breakpoint can have a [`Condition`]. If it is false when the breakpoint is hit, the debugger
+//! continues without stopping. Every hit where the condition is true is counted. A breakpoint can
+//! also ignore a number of hits, and temporary breakpoints are removed after they are hit once.
+//!
+//! A breakpoint with commands is a logpoint: when it is hit, the debugger runs the commands,
+//! reports their feedback and continues, so that a program can be traced without stopping.
+//!
+//! These [settings](BreakpointSettings) are kept when the debuggee is restarted, the hit count
+//! is not.
+//!
+//! Breakpoints that use the debug registers of the CPU instead of `INT3` are in
+//! [`hwbreakpoint`](crate::hwbreakpoint).
+
+use std::fmt::Display;
 
 use nix::unistd::Pid;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tracing::{error, trace};
 
+use crate::condition::Condition;
 use crate::errors::{DebuggerError, Result};
+use crate::feedback::Status;
 use crate::{mem_read_word, mem_write_word, Addr, Word};
 
 /// Mask to set all bits to 1 (using two's complement)
@@ -42,7 +59,8 @@ pub const WORD_MASK_INV: Word = MASK_ALL ^ WORD_MASK;
 /// artificial `INT3` with the original byte.
 ///
 /// When a [Breakpoint] is dropped while still enabled, it is automatically disabled, see
-/// [`Breakpoint::drop`].
+/// [`Breakpoint::drop`]. Clones of a [Breakpoint] are only copies of its information and do not
+/// disable it when they are dropped.
 ///
 /// # Examples
 ///
@@ -65,12 +83,64 @@ pub const WORD_MASK_INV: Word = MASK_ALL ^ WORD_MASK;
 ///
 /// assert!(!bp.is_enabled());
 /// ```
-#[derive(Debug, Clone, Hash, Serialize)]
+#[derive(Debug, Hash, Serialize)]
 pub struct Breakpoint {
     addr: Addr,
     #[serde(serialize_with = "ser_pid")]
     pid: Pid,
     saved_data: Option<u8>,
+    hits: u64,
+    #[serde(flatten)]
+    settings: BreakpointSettings,
+    #[serde(skip)]
+    is_copy: bool,
+    #[serde(skip)]
+    internal: bool,
+}
+
+/// Settings of a [`Breakpoint`] that are chosen by the user
+///
+/// Unlike the rest of the [`Breakpoint`], these do not depend on the process, so they are kept
+/// when the debuggee is restarted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BreakpointSettings {
+    /// Only stop if this condition is true
+    pub condition: Option<Condition>,
+    /// How many of the next hits are ignored
+    pub ignore_count: u64,
+    /// Remove the breakpoint after it was hit once
+    pub temporary: bool,
+    /// Run these commands and continue instead of stopping
+    #[serde(default)]
+    pub commands: Vec<Status>,
+}
+
+/// The kinds of breakpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum BreakpointKind {
+    /// A [`Breakpoint`] that writes `INT3` into the code
+    #[default]
+    Software,
+    /// A [`HardwareBreakpoint`](crate::hwbreakpoint::HardwareBreakpoint) that uses a debug
+    /// register
+    Hardware,
+}
+
+/// Information on a breakpoint, as listed by
+/// [`Status::ListBreakpoints`](crate::feedback::Status::ListBreakpoints)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct BreakpointInfo {
+    /// Address of the breakpoint
+    pub addr: Addr,
+    /// Whether this is a software or a hardware breakpoint
+    pub kind: BreakpointKind,
+    /// Whether the breakpoint is in place
+    pub enabled: bool,
+    /// How often the breakpoint was hit while its condition was true
+    pub hits: u64,
+    /// The settings of the breakpoint
+    #[serde(flatten)]
+    pub settings: BreakpointSettings,
 }
 
 impl Breakpoint {
@@ -98,9 +168,37 @@ impl Breakpoint {
             pid,
             addr,
             saved_data: None,
+            hits: 0,
+            settings: BreakpointSettings::default(),
+            is_copy: false,
+            internal: false,
         }
     }
 
+    /// Creates a new, initially disabled breakpoint that the debugger sets for itself
+    ///
+    /// Internal breakpoints, like the one that tells the debugger about
+    /// [shared libraries](crate::sharedlib), are not listed to the user and cannot be removed
+    /// by them. The debuggee does not stop at them.
+    ///
+    /// # Parameters
+    ///
+    /// * `pid` - Process ID of the target process
+    /// * `addr` - Address where the breakpoint should be set
+    #[must_use]
+    pub(crate) fn new_internal(pid: Pid, addr: Addr) -> Self {
+        let mut bp = Self::new(pid, addr);
+        bp.internal = true;
+        bp
+    }
+
+    /// Checks if the debugger set this breakpoint for itself, see [`Self::new_internal`]
+    #[inline]
+    #[must_use]
+    pub fn is_internal(&self) -> bool {
+        self.internal
+    }
+
     /// Checks if the breakpoint is currently enabled
     ///
     /// # Returns
@@ -229,6 +327,212 @@ impl Breakpoint {
     pub fn saved_data(&self) -> Option<u8> {
         self.saved_data
     }
+
+    /// Returns the condition of the breakpoint, if it has one
+    ///
+    /// The debuggee only stops at a breakpoint with a condition if the condition is true.
+    #[must_use]
+    pub fn condition(&self) -> Option<&Condition> {
+        self.settings.condition.as_ref()
+    }
+
+    /// Sets or removes the condition of the breakpoint
+    ///
+    /// # Parameters
+    ///
+    /// * `condition` - The new condition, [`None`] to stop every time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coreminer::breakpoint::Breakpoint;
+    /// use coreminer::addr::Addr;
+    /// use nix::unistd::Pid;
+    ///
+    /// let mut bp = Breakpoint::new(Pid::from_raw(1234), Addr::from(0x1000usize));
+    /// bp.set_condition(Some("rdi == 0x10".parse().unwrap()));
+    /// assert_eq!(bp.condition().unwrap().to_string(), "rdi == 16");
+    /// ```
+    pub fn set_condition(&mut self, condition: Option<Condition>) {
+        self.settings.condition = condition;
+    }
+
+    /// Returns how often the breakpoint was hit while its condition was true
+    ///
+    /// Ignored hits are counted too.
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns how many of the next hits are ignored
+    #[must_use]
+    pub fn ignore_count(&self) -> u64 {
+        self.settings.ignore_count
+    }
+
+    /// Sets how many of the next hits are ignored
+    ///
+    /// Only hits where the condition is true count.
+    ///
+    /// # Parameters
+    ///
+    /// * `count` - The number of hits to ignore, 0 to stop at the next hit
+    pub fn set_ignore_count(&mut self, count: u64) {
+        self.settings.ignore_count = count;
+    }
+
+    /// Checks if the breakpoint is removed after it was hit once
+    #[must_use]
+    pub fn is_temporary(&self) -> bool {
+        self.settings.temporary
+    }
+
+    /// Sets if the breakpoint is removed after it was hit once
+    ///
+    /// Ignored hits do not remove a temporary breakpoint.
+    pub fn set_temporary(&mut self, temporary: bool) {
+        self.settings.temporary = temporary;
+    }
+
+    /// Returns the commands that run when the breakpoint is hit
+    ///
+    /// If there are commands, the debuggee does not stop at the breakpoint.
+    #[must_use]
+    pub fn commands(&self) -> &[Status] {
+        &self.settings.commands
+    }
+
+    /// Sets the commands that run when the breakpoint is hit, an empty list removes them
+    pub fn set_commands(&mut self, commands: Vec<Status>) {
+        self.settings.commands = commands;
+    }
+
+    /// Returns the settings of the breakpoint
+    #[must_use]
+    pub fn settings(&self) -> &BreakpointSettings {
+        &self.settings
+    }
+
+    /// Replaces the settings of the breakpoint, for example to restore them after a restart
+    pub fn set_settings(&mut self, settings: BreakpointSettings) {
+        self.settings = settings;
+    }
+
+    /// Returns the settings of the breakpoint to change them
+    pub(crate) fn settings_mut(&mut self) -> &mut BreakpointSettings {
+        &mut self.settings
+    }
+
+    /// Returns the information on this breakpoint that is listed to the user
+    #[must_use]
+    pub fn info(&self) -> BreakpointInfo {
+        BreakpointInfo {
+            addr: self.addr,
+            kind: BreakpointKind::Software,
+            enabled: self.is_enabled(),
+            hits: self.hits,
+            settings: self.settings.clone(),
+        }
+    }
+
+    /// Counts a hit of the breakpoint where its condition is true
+    ///
+    /// # Returns
+    ///
+    /// * `true` - If the debuggee should stop
+    /// * `false` - If the hit is ignored, the ignore count is decreased then
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coreminer::breakpoint::Breakpoint;
+    /// use coreminer::addr::Addr;
+    /// use nix::unistd::Pid;
+    ///
+    /// let mut bp = Breakpoint::new(Pid::from_raw(1234), Addr::from(0x1000usize));
+    /// bp.set_ignore_count(1);
+    /// assert!(!bp.hit());
+    /// assert!(bp.hit());
+    /// assert_eq!(bp.hits(), 2);
+    /// ```
+    pub fn hit(&mut self) -> bool {
+        count_hit(&mut self.hits, &mut self.settings)
+    }
+
+    /// Creates the breakpoint that a forked child process inherited from this one
+    ///
+    /// The memory of a forked process is a copy of the memory of its parent, so the `INT3` is
+    /// in place in the child too. Unlike a [clone](Breakpoint::clone), the new breakpoint
+    /// belongs to the child and is disabled when it is dropped.
+    ///
+    /// # Parameters
+    ///
+    /// * `pid` - Process ID of the child process
+    #[must_use]
+    pub(crate) fn inherit(&self, pid: Pid) -> Self {
+        Self {
+            addr: self.addr,
+            pid,
+            saved_data: self.saved_data,
+            hits: self.hits,
+            settings: self.settings.clone(),
+            is_copy: false,
+            internal: self.internal,
+        }
+    }
+
+    /// Forgets that the breakpoint is enabled, without touching the memory of the process
+    ///
+    /// This is needed when the memory of the process was replaced, for example by `execve`,
+    /// or when it is shared with another process that still needs the `INT3`.
+    pub(crate) fn invalidate(&mut self) {
+        self.saved_data = None;
+    }
+}
+
+impl Display for BreakpointInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:<8} hits: {:<6}",
+            self.addr,
+            if self.enabled { "enabled" } else { "disabled" },
+            self.hits
+        )?;
+        if self.settings.ignore_count > 0 {
+            write!(f, " ignore next {}", self.settings.ignore_count)?;
+        }
+        if self.settings.temporary {
+            write!(f, " temporary")?;
+        }
+        if self.kind == BreakpointKind::Hardware {
+            write!(f, " hardware")?;
+        }
+        if let Some(condition) = &self.settings.condition {
+            write!(f, " if {condition}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Clone for Breakpoint {
+    /// Copies the information of the breakpoint
+    ///
+    /// The copy does not disable the breakpoint when it is dropped, so that for example returning
+    /// a copy with [`Feedback::Breakpoint`](crate::feedback::Feedback::Breakpoint) does not
+    /// remove the `INT3` from the debuggee.
+    fn clone(&self) -> Self {
+        Self {
+            addr: self.addr,
+            pid: self.pid,
+            saved_data: self.saved_data,
+            hits: self.hits,
+            settings: self.settings.clone(),
+            is_copy: true,
+            internal: self.internal,
+        }
+    }
 }
 
 impl Drop for Breakpoint {
@@ -242,7 +546,7 @@ impl Drop for Breakpoint {
     /// Panics if the breakpoint cannot be disabled. This should only happen if
     /// the target process is no longer accessible.
     fn drop(&mut self) {
-        if self.is_enabled() {
+        if self.is_enabled() && !self.is_copy {
             if let Err(e) = self.disable() {
                 if matches!(e, DebuggerError::Os(nix::errno::Errno::ESRCH)) {
                     // if the process does not exist anymore, that is not an error
@@ -254,15 +558,45 @@ impl Drop for Breakpoint {
     }
 }
 
-// serde always passes a reference. If we pass by value, the serializing
-// will not compile
+/// Counts a hit of a breakpoint, see [`Breakpoint::hit`]
+pub(crate) fn count_hit(hits: &mut u64, settings: &mut BreakpointSettings) -> bool {
+    *hits += 1;
+    if settings.ignore_count > 0 {
+        settings.ignore_count -= 1;
+        return false;
+    }
+    true
+}
+
+/// Serializes a [`Pid`] as its number, for `#[serde(serialize_with)]`
+///
+/// serde always passes a reference. If we pass by value, the serializing will not compile.
 #[allow(clippy::trivially_copy_pass_by_ref)]
-fn ser_pid<S: serde::Serializer>(pid: &Pid, s: S) -> std::result::Result<S::Ok, S::Error> {
+pub(crate) fn ser_pid<S: serde::Serializer>(
+    pid: &Pid,
+    s: S,
+) -> std::result::Result<S::Ok, S::Error> {
     s.serialize_i32(pid.as_raw())
 }
 
 #[cfg(test)]
 mod test {
+    use super::*;
+
+    #[test]
+    fn test_count_hit_ignores_first_hits() {
+        let mut hits = 0;
+        let mut settings = BreakpointSettings {
+            ignore_count: 2,
+            ..Default::default()
+        };
+        assert!(!count_hit(&mut hits, &mut settings));
+        assert!(!count_hit(&mut hits, &mut settings));
+        assert!(count_hit(&mut hits, &mut settings));
+        assert_eq!(hits, 3);
+        assert_eq!(settings.ignore_count, 0);
+    }
+
     #[test]
     fn test_minus_one_has_this_representaiton() {
         assert_eq!(
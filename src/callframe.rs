@@ -0,0 +1,319 @@
+//! # Call Frame Module
+//!
+//! Finds the canonical frame address (CFA) and the return address of a stack frame with the call
+//! frame information (CFI) in the `.eh_frame` and `.debug_frame` sections.
+//!
+//! The CFA is the value of the stack pointer right before the `call` that created a frame, on
+//! `x86_64` the return address is the word right below it. For every instruction of a function,
+//! the compiler describes how the CFA is computed from the registers, usually as an offset from
+//! `rsp` or `rbp`, and where the registers of the caller were saved. Unlike following the saved
+//! frame pointers, this also works in function prologues and for code that was compiled with
+//! `-fomit-frame-pointer`.
+//!
+//! `.eh_frame` is in almost every executable and shared library, as it is needed to unwind for
+//! exceptions. `.debug_frame` is only there with debug information, it is used for the code that
+//! `.eh_frame` has nothing for. Code without any call frame information is assumed to use `rbp`
+//! as a frame pointer.
+
+use std::rc::Rc;
+
+use gimli::{
+    BaseAddresses, DebugFrame, EhFrame, Expression, RegisterRule, UnwindContext, UnwindSection,
+    UnwindTableRow,
+};
+use object::{Object, ObjectSection};
+use tracing::{debug, trace};
+
+use crate::dbginfo::CMDebugInfo;
+use crate::debuggee::Debuggee;
+use crate::dwarf_parse::{FrameInfo, GimliReaderThing};
+use crate::errors::{DebuggerError, Result};
+use crate::{Addr, Register, WORD_BYTES};
+
+/// Encoding of the DWARF expressions in the call frame information, they are always evaluated
+/// with 64 bit addresses
+const CFI_ENCODING: gimli::Encoding = gimli::Encoding {
+    format: gimli::Format::Dwarf32,
+    version: 4,
+    address_size: WORD_BYTES as u8,
+};
+
+/// The location of a stack frame
+///
+/// # Examples
+///
+/// ```no_run
+/// # use coreminer::debuggee::Debuggee;
+/// # use coreminer::addr::Addr;
+/// # fn example(debuggee: &Debuggee, rip: Addr) -> coreminer::errors::Result<()> {
+/// let frame = debuggee.call_frame(rip)?;
+/// println!("the frame ends at {}", frame.cfa);
+/// if let Some(return_addr) = frame.return_addr {
+///     println!("the function returns to {return_addr}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallFrame {
+    /// The canonical frame address: the stack pointer before the `call` of the function
+    pub cfa: Addr,
+    /// Where the function returns to, [`None`] for the outermost frame
+    pub return_addr: Option<Addr>,
+}
+
+/// The call frame information of an executable or shared library
+#[derive(Debug, Clone)]
+pub struct CallFrameTable {
+    eh_frame: Option<EhFrame<GimliReaderThing>>,
+    debug_frame: Option<DebugFrame<GimliReaderThing>>,
+    bases: BaseAddresses,
+    load_bias: Addr,
+}
+
+/// The rules to find the CFA and the return address at an instruction
+#[derive(Debug, Clone)]
+struct FrameRules {
+    cfa: CfaRule,
+    return_addr: RegisterRule<usize>,
+}
+
+/// How the CFA is computed
+#[derive(Debug, Clone)]
+enum CfaRule {
+    /// The value of a register plus an offset
+    RegisterAndOffset {
+        register: gimli::Register,
+        offset: i64,
+    },
+    /// The result of a DWARF expression
+    Expression(Expression<GimliReaderThing>),
+}
+
+impl CallFrameTable {
+    /// Reads the call frame information from the sections of an executable or shared library
+    ///
+    /// # Parameters
+    ///
+    /// * `dbginfo` - Debug information extracted from the file
+    /// * `load_bias` - Difference between the load address and the link address of the file,
+    ///   see [`CMDebugInfo::load_bias`]
+    ///
+    /// # Errors
+    ///
+    /// This function fails if a section cannot be decompressed.
+    pub fn build(dbginfo: &CMDebugInfo<'_>, load_bias: Addr) -> Result<Self> {
+        let object_info = &dbginfo.object_info;
+        let mut bases = BaseAddresses::default();
+        let mut eh_frame = None;
+        let mut debug_frame = None;
+        for section in object_info.sections() {
+            match section.name()? {
+                ".eh_frame" => {
+                    bases = bases.set_eh_frame(section.address());
+                    eh_frame = Some(EhFrame::from(reader(&section)?));
+                }
+                ".debug_frame" => debug_frame = Some(DebugFrame::from(reader(&section)?)),
+                ".eh_frame_hdr" => bases = bases.set_eh_frame_hdr(section.address()),
+                ".text" => bases = bases.set_text(section.address()),
+                ".got" => bases = bases.set_got(section.address()),
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            eh_frame,
+            debug_frame,
+            bases,
+            load_bias,
+        })
+    }
+
+    /// Finds the rules for the frame at an address
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(None)` - If there is no call frame information for the address
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the call frame information cannot be parsed.
+    fn rules(&self, addr: Addr) -> Result<Option<FrameRules>> {
+        let link_addr = (addr - self.load_bias).u64();
+        let mut ctx = UnwindContext::new();
+
+        if let Some(eh_frame) = &self.eh_frame {
+            let row = eh_frame.unwind_info_for_address(
+                &self.bases,
+                &mut ctx,
+                link_addr,
+                EhFrame::cie_from_offset,
+            );
+            if let Some(rules) = FrameRules::from_row(eh_frame, row)? {
+                return Ok(Some(rules));
+            }
+        }
+        if let Some(debug_frame) = &self.debug_frame {
+            let row = debug_frame.unwind_info_for_address(
+                &self.bases,
+                &mut ctx,
+                link_addr,
+                DebugFrame::cie_from_offset,
+            );
+            return FrameRules::from_row(debug_frame, row);
+        }
+        Ok(None)
+    }
+}
+
+impl Default for CallFrameTable {
+    fn default() -> Self {
+        Self {
+            eh_frame: None,
+            debug_frame: None,
+            bases: BaseAddresses::default(),
+            load_bias: Addr::NULL,
+        }
+    }
+}
+
+impl FrameRules {
+    /// Takes the rules out of a row of the unwind table, if one was found
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the section could not be parsed, or the expression of the CFA
+    /// is not in the section.
+    fn from_row<S: UnwindSection<GimliReaderThing>>(
+        section: &S,
+        row: gimli::Result<&UnwindTableRow<usize>>,
+    ) -> Result<Option<Self>> {
+        let row = match row {
+            Ok(row) => row,
+            Err(gimli::Error::NoUnwindInfoForAddress) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let cfa = match row.cfa() {
+            gimli::CfaRule::RegisterAndOffset { register, offset } => CfaRule::RegisterAndOffset {
+                register: *register,
+                offset: *offset,
+            },
+            gimli::CfaRule::Expression(expression) => CfaRule::Expression(expression.get(section)?),
+        };
+        Ok(Some(Self {
+            cfa,
+            return_addr: row.register(gimli::X86_64::RA),
+        }))
+    }
+}
+
+/// Makes a reader for the data of a section
+fn reader(section: &object::Section<'_, '_>) -> Result<GimliReaderThing> {
+    Ok(gimli::EndianReader::new(
+        Rc::from(section.uncompressed_data()?.as_ref()),
+        gimli::LittleEndian,
+    ))
+}
+
+impl Debuggee {
+    /// Finds the frame of the selected thread, as it is at an address in its current function
+    ///
+    /// The call frame information of the executable or the shared library that contains the
+    /// address is used. Without call frame information, `rbp` is assumed to be the frame
+    /// pointer.
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The address of the instruction that the thread executes next, usually `rip`
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the call frame information cannot be parsed, uses a rule that is
+    /// not supported, or the registers or stack of the thread cannot be read.
+    pub fn call_frame(&self, addr: Addr) -> Result<CallFrame> {
+        let tid = self.selected_thread();
+        let table = self
+            .libraries()
+            .iter()
+            .find(|library| library.contains(addr))
+            .map_or(&self.frames, |library| library.frames());
+
+        let Some(rules) = table.rules(addr)? else {
+            debug!("no call frame information for {addr}, using rbp as frame pointer");
+            let cfa = Addr::from(self.get_reg(tid, Register::rbp)?) + 2 * WORD_BYTES;
+            return Ok(CallFrame {
+                cfa,
+                return_addr: Some(self.read_word(cfa - WORD_BYTES)?.into()),
+            });
+        };
+        trace!("call frame rules at {addr}: {rules:?}");
+
+        let cfa: Addr = match rules.cfa {
+            CfaRule::RegisterAndOffset { register, offset } => {
+                let value = self.get_reg(tid, Register::try_from(register)?)?;
+                value.wrapping_add_signed(offset).into()
+            }
+            CfaRule::Expression(expression) => {
+                match self.eval_expression(expression, &FrameInfo::new(None, None), CFI_ENCODING)? {
+                    gimli::Location::Address { address } => address.into(),
+                    other => {
+                        return Err(DebuggerError::UnsupportedUnwindRule(format!(
+                            "the CFA expression resulted in {other:?}"
+                        )))
+                    }
+                }
+            }
+        };
+
+        let return_addr = match rules.return_addr {
+            RegisterRule::Undefined => None,
+            RegisterRule::Offset(offset) => Some(
+                self.read_word(cfa.u64().wrapping_add_signed(offset).into())?
+                    .into(),
+            ),
+            RegisterRule::ValOffset(offset) => Some(cfa.u64().wrapping_add_signed(offset).into()),
+            RegisterRule::Register(register) => {
+                Some(self.get_reg(tid, Register::try_from(register)?)?.into())
+            }
+            other => {
+                return Err(DebuggerError::UnsupportedUnwindRule(format!(
+                    "the return address uses {other:?}"
+                )))
+            }
+        };
+
+        Ok(CallFrame { cfa, return_addr })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nix::unistd::getpid;
+
+    use super::*;
+
+    #[test]
+    fn test_rules_at_function_entry() {
+        let data = std::fs::read(std::env::current_exe().unwrap()).unwrap();
+        let dbginfo = CMDebugInfo::build(object::File::parse(data.as_slice()).unwrap()).unwrap();
+        let load_bias = dbginfo.load_bias(Debuggee::get_base_addr_by_pid(getpid()).unwrap());
+        let table = CallFrameTable::build(&dbginfo, load_bias).unwrap();
+
+        // right after the call, the CFA is above the return address on top of the stack
+        let entry = Addr::from(test_rules_at_function_entry as *const () as usize);
+        let rules = table
+            .rules(entry)
+            .unwrap()
+            .expect("no rules for our own code");
+        assert!(matches!(
+            rules.cfa,
+            CfaRule::RegisterAndOffset {
+                register: gimli::X86_64::RSP,
+                offset: 8
+            }
+        ));
+        assert_eq!(rules.return_addr, RegisterRule::Offset(-8));
+
+        assert!(CallFrameTable::default().rules(entry).unwrap().is_none());
+    }
+}
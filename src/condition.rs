@@ -0,0 +1,319 @@
+//! # Condition Module
+//!
+//! Provides the [`Condition`] of a conditional [`Breakpoint`](crate::breakpoint::Breakpoint).
+//!
+//! A condition compares two operands, like `rdi == 0x10` or `counter > 100`. An operand is one
+//! of:
+//!
+//! - A register of the selected thread, like `rdi` or `$rdi`
+//! - A word of memory, like `*0x7ffc1234`
+//! - A variable, like `counter`
+//! - A number, decimal or hexadecimal with `0x`, like `-1` or `0x10`
+//!
+//! All values are compared as signed 64 bit integers. Variables that are smaller than that are
+//! extended by their type: unsigned ones with zeros, all others with their sign. Floating point
+//! variables cannot be compared.
+//!
+//! When a breakpoint with a condition is hit and the condition is false, the debugger steps
+//! over the breakpoint and continues, without returning to the
+//! [`DebuggerUI`](crate::ui::DebuggerUI).
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{DebuggerError, Result};
+use crate::variable::VariableValue;
+use crate::{Addr, Register};
+
+/// An operand of a [`Condition`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Operand {
+    /// A register of the selected thread
+    Register(Register),
+    /// The word of memory at an address
+    Memory(Addr),
+    /// A variable, by name
+    Variable(String),
+    /// A number
+    Value(i64),
+}
+
+/// How the operands of a [`Condition`] are compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(missing_docs)] // just comparison operators, self explanatory
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A condition of a breakpoint, comparing two [operands](Operand)
+///
+/// Conditions are serialized as strings, like `"rdi == 0x10"`.
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::condition::{Condition, Operand};
+///
+/// let condition: Condition = "counter > 100".parse().unwrap();
+/// assert_eq!(condition.lhs, Operand::Variable("counter".to_string()));
+/// assert_eq!(condition.to_string(), "counter > 100");
+///
+/// let holds = condition
+///     .evaluate(|operand| match operand {
+///         Operand::Value(v) => Ok(*v),
+///         _ => Ok(101), // the value of counter
+///     })
+///     .unwrap();
+/// assert!(holds);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Condition {
+    /// The left side of the comparison
+    pub lhs: Operand,
+    /// How the sides are compared
+    pub op: Comparison,
+    /// The right side of the comparison
+    pub rhs: Operand,
+}
+
+impl Condition {
+    /// Evaluates the condition
+    ///
+    /// # Parameters
+    ///
+    /// * `value` - Gets the value of an operand from the debuggee
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the value of an operand cannot be read.
+    pub fn evaluate(&self, mut value: impl FnMut(&Operand) -> Result<i64>) -> Result<bool> {
+        let lhs = value(&self.lhs)?;
+        let rhs = value(&self.rhs)?;
+        Ok(match self.op {
+            Comparison::Eq => lhs == rhs,
+            Comparison::Ne => lhs != rhs,
+            Comparison::Lt => lhs < rhs,
+            Comparison::Le => lhs <= rhs,
+            Comparison::Gt => lhs > rhs,
+            Comparison::Ge => lhs >= rhs,
+        })
+    }
+}
+
+/// Converts the value of a variable to the signed integer that conditions compare
+///
+/// Values that are smaller than 8 bytes are zero extended if their base type is unsigned, and
+/// sign extended otherwise, also if the type is not a base type, like for an `enum`.
+///
+/// # Parameters
+///
+/// * `value` - The value of the variable
+/// * `encoding` - The `DW_AT_encoding` of the base type of the variable, if it has one
+///
+/// # Errors
+///
+/// This function fails if the variable is a floating point number.
+pub fn variable_to_i64(value: &VariableValue, encoding: Option<gimli::DwAte>) -> Result<i64> {
+    let bits = value.byte_size().min(8) * 8;
+    let raw = value.to_u64();
+    let signed = match encoding {
+        Some(gimli::DW_ATE_float | gimli::DW_ATE_complex_float | gimli::DW_ATE_decimal_float) => {
+            return Err(DebuggerError::FloatInCondition)
+        }
+        Some(
+            gimli::DW_ATE_unsigned
+            | gimli::DW_ATE_unsigned_char
+            | gimli::DW_ATE_boolean
+            | gimli::DW_ATE_address
+            | gimli::DW_ATE_UTF,
+        ) => false,
+        _ => true,
+    };
+    if bits == 0 || bits == 64 {
+        return Ok(raw as i64);
+    }
+    if !signed {
+        return Ok((raw & ((1 << bits) - 1)) as i64);
+    }
+    // move the sign bit of the value to the top and back, filling with it
+    Ok(((raw << (64 - bits)) as i64) >> (64 - bits))
+}
+
+impl Comparison {
+    /// All comparisons with their operators, two character operators first
+    const OPERATORS: [(&'static str, Self); 6] = [
+        ("==", Self::Eq),
+        ("!=", Self::Ne),
+        ("<=", Self::Le),
+        (">=", Self::Ge),
+        ("<", Self::Lt),
+        (">", Self::Gt),
+    ];
+
+    fn as_str(self) -> &'static str {
+        Self::OPERATORS
+            .iter()
+            .find(|(_, c)| *c == self)
+            .map_or("??", |(s, _)| s)
+    }
+}
+
+impl FromStr for Operand {
+    type Err = DebuggerError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(addr) = s.strip_prefix('*') {
+            return match parse_number(addr.trim()) {
+                Some(addr) => Ok(Self::Memory(Addr::from(addr as usize))),
+                None => Err(DebuggerError::ParseStr(s.to_string())),
+            };
+        }
+        if let Some(number) = parse_number(s) {
+            return Ok(Self::Value(number));
+        }
+        if let Ok(register) = Register::from_str(s.strip_prefix('$').unwrap_or(s)) {
+            return Ok(Self::Register(register));
+        }
+        if !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Ok(Self::Variable(s.to_string()));
+        }
+        Err(DebuggerError::ParseStr(s.to_string()))
+    }
+}
+
+impl FromStr for Condition {
+    type Err = DebuggerError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        for (operator, op) in Comparison::OPERATORS {
+            if let Some((lhs, rhs)) = s.split_once(operator) {
+                return Ok(Self {
+                    lhs: lhs.parse()?,
+                    op,
+                    rhs: rhs.parse()?,
+                });
+            }
+        }
+        Err(DebuggerError::ParseStr(s.to_string()))
+    }
+}
+
+/// Parses a decimal or hexadecimal (with `0x`) number, which may be negative
+fn parse_number(s: &str) -> Option<i64> {
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s),
+    };
+    let value = match digits.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok()? as i64,
+        None => digits.parse::<i64>().ok()?,
+    };
+    Some(if negative {
+        value.wrapping_neg()
+    } else {
+        value
+    })
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Register(r) => write!(f, "{r:?}"),
+            Self::Memory(addr) => write!(f, "*{addr}"),
+            Self::Variable(name) => write!(f, "{name}"),
+            Self::Value(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.lhs, self.op.as_str(), self.rhs)
+    }
+}
+
+impl TryFrom<String> for Condition {
+    type Error = DebuggerError;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Condition> for String {
+    fn from(value: Condition) -> Self {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_condition() {
+        let c: Condition = "$rdi==0x10".parse().unwrap();
+        assert_eq!(c.lhs, Operand::Register(Register::rdi));
+        assert_eq!(c.op, Comparison::Eq);
+        assert_eq!(c.rhs, Operand::Value(0x10));
+
+        let c: Condition = "*0x1000 <= -1".parse().unwrap();
+        assert_eq!(c.lhs, Operand::Memory(Addr::from(0x1000usize)));
+        assert_eq!(c.op, Comparison::Le);
+        assert_eq!(c.rhs, Operand::Value(-1));
+
+        assert!("counter".parse::<Condition>().is_err());
+        assert!("a b > 1".parse::<Condition>().is_err());
+    }
+
+    #[test]
+    fn test_variable_sign_extension() {
+        let int = Some(gimli::DW_ATE_signed);
+        assert_eq!(
+            variable_to_i64(&VariableValue::Bytes(vec![0xff; 4]), int).unwrap(),
+            -1
+        );
+        assert_eq!(
+            variable_to_i64(&VariableValue::Bytes(vec![0x7f, 0, 0, 0]), int).unwrap(),
+            0x7f
+        );
+        assert_eq!(
+            variable_to_i64(&VariableValue::Bytes(vec![0xfe]), None).unwrap(),
+            -2
+        );
+    }
+
+    #[test]
+    fn test_variable_zero_extension() {
+        // unsigned char x = 200
+        let x = VariableValue::Bytes(vec![200]);
+        let x = variable_to_i64(&x, Some(gimli::DW_ATE_unsigned_char)).unwrap();
+        assert_eq!(x, 200);
+        let c: Condition = "x > 100".parse().unwrap();
+        assert!(c
+            .evaluate(|o| Ok(if *o == c.lhs { x } else { 100 }))
+            .unwrap());
+
+        // uint32_t counter = 0xffff_fff0
+        let counter = VariableValue::Bytes(0xffff_fff0u32.to_ne_bytes().to_vec());
+        assert_eq!(
+            variable_to_i64(&counter, Some(gimli::DW_ATE_unsigned)).unwrap(),
+            0xffff_fff0
+        );
+
+        let float = VariableValue::Bytes(1.5f32.to_ne_bytes().to_vec());
+        assert!(matches!(
+            variable_to_i64(&float, Some(gimli::DW_ATE_float)),
+            Err(DebuggerError::FloatInCondition)
+        ));
+    }
+}
@@ -0,0 +1,1070 @@
+//! # Core Dump Module
+//!
+//! Provides the [`CoreDump`] target, which makes an ELF core file readable like a stopped
+//! process.
+//!
+//! A core file is written by the kernel when a process crashes (or by tools like `gcore`). It
+//! contains:
+//!
+//! - A `PT_LOAD` segment for every memory mapping of the process. The contents of writable and
+//!   anonymous mappings are stored in the core file, the contents of mapped files usually are
+//!   not, so they are read from the files on disk instead, like the code of the executable.
+//! - A `NT_PRSTATUS` note for every thread, containing its registers and the signal that
+//!   killed the process.
+//! - A `NT_PRPSINFO` note with the process ID and name.
+//! - A `NT_FILE` note listing which file is mapped where.
+//!
+//! The [`Debuggee`] can be built from a [`CoreDump`] with
+//! [`Status::LoadCore`](crate::feedback::Status::LoadCore). The debug symbols still come from
+//! the executable, so that registers, memory, disassembly, backtraces and variables can be
+//! inspected without a live process. Nothing can be executed or changed.
+//!
+//! A core file of a live debuggee can be written with [`Debuggee::generate_core`], like `gcore`
+//! does. It also contains a `NT_AUXV` note and can be read by other tools like gdb.
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+use nix::libc::user_regs_struct;
+use nix::sys::signal::Signal;
+use nix::unistd::Pid;
+use object::elf;
+use object::read::elf::{FileHeader, ProgramHeader};
+use tracing::{debug, trace, warn};
+
+use crate::debuggee::Debuggee;
+use crate::errors::{DebuggerError, Result};
+use crate::memorymap::{MemoryPermissions, MemoryRegion, ProcessMemoryMap};
+use crate::{mem_read, Addr, Word, PAGE_SIZE, WORD_BYTES};
+
+/// Size of the `elf_prstatus` struct of `x86_64`
+const PRSTATUS_SIZE: usize = 336;
+/// Offset of `pr_cursig` in the `elf_prstatus` struct of `x86_64`
+const PRSTATUS_CURSIG: usize = 12;
+/// Offset of `pr_pid` in the `elf_prstatus` struct of `x86_64`, followed by `pr_ppid`,
+/// `pr_pgrp` and `pr_sid`
+const PRSTATUS_PID: usize = 32;
+/// Offset of `pr_reg` (a `user_regs_struct`) in the `elf_prstatus` struct of `x86_64`
+const PRSTATUS_REGS: usize = 112;
+/// Number of registers in a `user_regs_struct` of `x86_64`
+const USER_REGS_COUNT: usize = 27;
+/// Size of the `elf_prpsinfo` struct of `x86_64`
+const PRPSINFO_SIZE: usize = 136;
+/// Offset of `pr_flag` in the `elf_prpsinfo` struct of `x86_64`
+const PRPSINFO_FLAG: usize = 8;
+/// Offset of `pr_uid` in the `elf_prpsinfo` struct of `x86_64`, followed by `pr_gid`
+const PRPSINFO_UID: usize = 16;
+/// Offset of `pr_pid` in the `elf_prpsinfo` struct of `x86_64`, followed by `pr_ppid`,
+/// `pr_pgrp` and `pr_sid`
+const PRPSINFO_PID: usize = 24;
+/// Offset of `pr_fname` in the `elf_prpsinfo` struct of `x86_64`
+const PRPSINFO_FNAME: usize = 40;
+/// Length of `pr_fname` in the `elf_prpsinfo` struct
+const PRPSINFO_FNAME_LEN: usize = 16;
+/// Offset of `pr_psargs` in the `elf_prpsinfo` struct of `x86_64`
+const PRPSINFO_PSARGS: usize = 56;
+/// Length of `pr_psargs` in the `elf_prpsinfo` struct
+const PRPSINFO_PSARGS_LEN: usize = 80;
+/// Size of an entry of the `NT_FILE` note: start address, end address and file offset
+const NT_FILE_ENTRY_SIZE: usize = 3 * 8;
+/// Size of the header of a 64 bit ELF file
+const ELF_HEADER_SIZE: usize = 64;
+/// Size of a program header of a 64 bit ELF file
+const PROGRAM_HEADER_SIZE: usize = 56;
+/// Size of a section header of a 64 bit ELF file
+const SECTION_HEADER_SIZE: usize = 64;
+/// Memory is copied into a generated core file in chunks of this size
+const COPY_CHUNK_SIZE: usize = 0x10000;
+
+/// A thread of a crashed process, as recorded in a `NT_PRSTATUS` note
+#[derive(Clone, Copy)]
+struct CoreThread {
+    tid: Pid,
+    regs: user_regs_struct,
+}
+
+/// A `PT_LOAD` segment, one memory mapping of the crashed process
+#[derive(Debug, Clone, Copy)]
+struct CoreSegment {
+    vaddr: usize,
+    memsz: usize,
+    /// Offset of the contents in the core file
+    offset: usize,
+    /// Number of bytes of the contents stored in the core file, may be less than `memsz`
+    filesz: usize,
+    flags: u32,
+}
+
+/// A mapped file, as recorded in the `NT_FILE` note
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MappedFile {
+    start: usize,
+    end: usize,
+    /// Offset into the file in bytes
+    offset: usize,
+    path: PathBuf,
+}
+
+/// An ELF core file of a crashed process
+///
+/// Registers and memory of the process are read from the core file as if the process was
+/// stopped at the moment it crashed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use coreminer::coredump::CoreDump;
+///
+/// let core = CoreDump::open("./core").unwrap();
+/// println!("process {} was killed by {:?}", core.pid(), core.signal());
+///
+/// let rip = core.registers(core.crashed_thread()).unwrap().rip;
+/// let mut code = [0u8; 16];
+/// core.read_memory(&mut code, rip.into()).unwrap();
+/// ```
+pub struct CoreDump {
+    path: PathBuf,
+    data: Vec<u8>,
+    pid: Pid,
+    name: Option<String>,
+    signal: Option<Signal>,
+    threads: Vec<CoreThread>,
+    segments: Vec<CoreSegment>,
+    files: Vec<MappedFile>,
+}
+
+impl CoreDump {
+    /// Reads and parses an ELF core file
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - Path to the core file
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CoreDump)` - The parsed core file
+    /// * `Err(DebuggerError)` - If the core file could not be read
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The file cannot be read
+    /// - The file is not a 64 bit ELF core file
+    /// - The file does not contain any thread
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let data = std::fs::read(path)?;
+
+        let header = elf::FileHeader64::<object::Endianness>::parse(&*data)?;
+        let endian = header.endian()?;
+        if header.e_type(endian) != elf::ET_CORE {
+            return Err(DebuggerError::InvalidCoreDump(
+                "the file is not an ELF core file".to_string(),
+            ));
+        }
+        if header.e_machine(endian) != elf::EM_X86_64 {
+            return Err(DebuggerError::InvalidCoreDump(
+                "only core files of x86_64 processes are supported".to_string(),
+            ));
+        }
+
+        let mut threads = Vec::new();
+        let mut segments = Vec::new();
+        let mut files = Vec::new();
+        let mut signal = None;
+        let mut pid = None;
+        let mut name = None;
+
+        for phdr in header.program_headers(endian, &*data)? {
+            match phdr.p_type(endian) {
+                elf::PT_LOAD => {
+                    let seg = CoreSegment {
+                        vaddr: phdr.p_vaddr(endian) as usize,
+                        memsz: phdr.p_memsz(endian) as usize,
+                        offset: phdr.p_offset(endian) as usize,
+                        filesz: phdr.p_filesz(endian) as usize,
+                        flags: phdr.p_flags(endian),
+                    };
+                    if seg.vaddr.checked_add(seg.memsz.max(seg.filesz)).is_none()
+                        || seg.offset.checked_add(seg.filesz).is_none()
+                    {
+                        return Err(DebuggerError::InvalidCoreDump(format!(
+                            "the segment at {:#x} is out of range",
+                            seg.vaddr
+                        )));
+                    }
+                    segments.push(seg);
+                }
+                elf::PT_NOTE => {
+                    let Some(mut notes) = phdr.notes(endian, &*data)? else {
+                        continue;
+                    };
+                    while let Some(note) = notes.next()? {
+                        if note.name() != elf::ELF_NOTE_CORE {
+                            continue;
+                        }
+                        let desc = note.desc();
+                        match note.n_type(endian) {
+                            elf::NT_PRSTATUS => {
+                                let thread = parse_prstatus(desc)?;
+                                // the thread that crashed comes first
+                                if threads.is_empty() {
+                                    let signum = i32::from(read_u16(desc, PRSTATUS_CURSIG)?);
+                                    signal = Signal::try_from(signum).ok();
+                                }
+                                threads.push(thread);
+                            }
+                            elf::NT_PRPSINFO => {
+                                pid = Some(Pid::from_raw(read_u32(desc, PRPSINFO_PID)? as i32));
+                                name = parse_fname(desc);
+                            }
+                            elf::NT_FILE => files = parse_nt_file(desc)?,
+                            other => debug!("skipping core note of type {other:#x}"),
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let Some(first) = threads.first() else {
+            return Err(DebuggerError::InvalidCoreDump(
+                "the core file has no NT_PRSTATUS note".to_string(),
+            ));
+        };
+        let pid = pid.unwrap_or(first.tid);
+        segments.sort_by_key(|s| s.vaddr);
+        debug!(
+            "core of process {pid} with {} threads, {} segments and {} mapped files",
+            threads.len(),
+            segments.len(),
+            files.len()
+        );
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            data,
+            pid,
+            name,
+            signal,
+            threads,
+            segments,
+            files,
+        })
+    }
+
+    /// Gets the path of the core file
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the process ID of the crashed process
+    #[must_use]
+    pub fn pid(&self) -> Pid {
+        self.pid
+    }
+
+    /// Gets the name of the crashed process, as shown by `ps`
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the signal that killed the process, if it is known
+    #[must_use]
+    pub fn signal(&self) -> Option<Signal> {
+        self.signal
+    }
+
+    /// Gets the thread ID of the thread that crashed
+    #[must_use]
+    pub fn crashed_thread(&self) -> Pid {
+        self.threads[0].tid
+    }
+
+    /// Gets the thread IDs of all threads of the crashed process
+    pub fn threads(&self) -> impl Iterator<Item = Pid> + '_ {
+        self.threads.iter().map(|t| t.tid)
+    }
+
+    /// Gets the path of the executable of the crashed process
+    ///
+    /// This is the file mapped at the lowest address, like the base address of a live process.
+    #[must_use]
+    pub fn executable(&self) -> Option<&Path> {
+        self.files
+            .iter()
+            .min_by_key(|f| f.start)
+            .map(|f| f.path.as_path())
+    }
+
+    /// Reads the executable from a different file than the one recorded in the core file
+    ///
+    /// This is needed when the core file was made on another machine, or the executable was
+    /// moved since.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - Path to the executable
+    pub fn set_executable(&mut self, path: impl Into<PathBuf>) {
+        let Some(old) = self.executable().map(Path::to_path_buf) else {
+            return;
+        };
+        let path = path.into();
+        for file in self.files.iter_mut().filter(|f| f.path == old) {
+            file.path.clone_from(&path);
+        }
+    }
+
+    /// Gets the address the executable is loaded at
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the core file lists no mapped files.
+    pub fn base_addr(&self) -> Result<Addr> {
+        self.files
+            .iter()
+            .map(|f| Addr::from(f.start))
+            .min()
+            .ok_or_else(|| {
+                DebuggerError::InvalidCoreDump("the core file has no NT_FILE note".to_string())
+            })
+    }
+
+    /// Gets the registers of a thread at the moment of the crash
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the process had no thread with that thread ID.
+    pub fn registers(&self, tid: Pid) -> Result<user_regs_struct> {
+        self.threads
+            .iter()
+            .find(|t| t.tid == tid)
+            .map(|t| t.regs)
+            .ok_or(DebuggerError::NoSuchThread(tid.as_raw()))
+    }
+
+    /// Reads memory of the crashed process
+    ///
+    /// Memory that is not stored in the core file is read from the mapped file on disk, if
+    /// there is one.
+    ///
+    /// # Parameters
+    ///
+    /// * `buf` - Buffer to read into
+    /// * `addr` - Address to start reading at
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - How many bytes were read, less than the length of `buf` if the memory
+    ///   after them is not available
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the memory at `addr` is not available.
+    pub fn read_memory(&self, buf: &mut [u8], addr: Addr) -> Result<usize> {
+        let mut done = 0;
+        while done < buf.len() {
+            let len = self.read_chunk(&mut buf[done..], addr.usize() + done);
+            if len == 0 {
+                break;
+            }
+            done += len;
+        }
+        if done == 0 && !buf.is_empty() {
+            return Err(DebuggerError::NotInCoreDump(addr));
+        }
+        Ok(done)
+    }
+
+    /// Reads a [Word] of memory of the crashed process
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the memory at `addr` is not available.
+    pub fn read_word(&self, addr: Addr) -> Result<Word> {
+        let mut buf = [0; WORD_BYTES];
+        if self.read_memory(&mut buf, addr)? < WORD_BYTES {
+            return Err(DebuggerError::NotInCoreDump(addr + WORD_BYTES));
+        }
+        Ok(Word::from_ne_bytes(buf))
+    }
+
+    /// Reads as much as possible from the single mapping that contains `addr`
+    fn read_chunk(&self, buf: &mut [u8], addr: usize) -> usize {
+        if let Some(seg) = self
+            .segments
+            .iter()
+            .find(|s| s.vaddr <= addr && addr - s.vaddr < s.filesz)
+        {
+            let skip = addr - seg.vaddr;
+            let len = buf.len().min(seg.filesz - skip);
+            let src = seg
+                .offset
+                .checked_add(skip)
+                .and_then(|start| self.data.get(start..start.checked_add(len)?));
+            let Some(src) = src else {
+                warn!("segment at {:#x} is cut off in the core file", seg.vaddr);
+                return 0;
+            };
+            buf[..len].copy_from_slice(src);
+            return len;
+        }
+
+        let Some(file) = self.files.iter().find(|f| f.start <= addr && addr < f.end) else {
+            return 0;
+        };
+        let len = buf.len().min(file.end - addr);
+        let Some(offset) = file.offset.checked_add(addr - file.start) else {
+            return 0;
+        };
+        match read_file_at(&file.path, offset, &mut buf[..len]) {
+            Ok(len) => len,
+            Err(e) => {
+                warn!("could not read '{}': {e}", file.path.to_string_lossy());
+                0
+            }
+        }
+    }
+
+    /// Builds the memory map of the crashed process from the `PT_LOAD` segments
+    #[must_use]
+    pub fn memory_map(&self) -> ProcessMemoryMap {
+        let regions: Vec<MemoryRegion> = self
+            .segments
+            .iter()
+            .map(|seg| {
+                let file = self
+                    .files
+                    .iter()
+                    .find(|f| f.start <= seg.vaddr && seg.vaddr < f.end);
+                MemoryRegion {
+                    start_address: Addr::from(seg.vaddr),
+                    end_address: Addr::from(seg.vaddr + seg.memsz),
+                    size: seg.memsz,
+                    permissions: MemoryPermissions {
+                        read: seg.flags & elf::PF_R != 0,
+                        write: seg.flags & elf::PF_W != 0,
+                        execute: seg.flags & elf::PF_X != 0,
+                        shared: false,
+                        private: true,
+                    },
+                    offset: file.map_or(0, |f| f.offset.saturating_add(seg.vaddr - f.start)),
+                    device: "00:00".to_string(),
+                    inode: 0,
+                    path: file.map(|f| f.path.to_string_lossy().to_string()),
+                }
+            })
+            .collect();
+        regions.into()
+    }
+}
+
+/// Status of a live process, as found in `/proc/<pid>/stat`
+#[derive(Debug, Clone, Default)]
+struct ProcStat {
+    comm: String,
+    state: u8,
+    ppid: i32,
+    pgrp: i32,
+    sid: i32,
+    flags: u64,
+    nice: i8,
+}
+
+impl ProcStat {
+    /// Reads the status of a process
+    fn read(pid: Pid) -> Result<Self> {
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat"))?;
+        // the name is in parentheses and can contain anything, even parentheses
+        let (Some(open), Some(close)) = (stat.find('('), stat.rfind(')')) else {
+            return Err(DebuggerError::ParseStr(stat));
+        };
+        let fields: Vec<&str> = stat[close + 1..].split_whitespace().collect();
+        let field = |i: usize| fields.get(i).copied().unwrap_or("0");
+        Ok(Self {
+            comm: stat[open + 1..close].to_string(),
+            state: field(0).bytes().next().unwrap_or(b'R'),
+            ppid: field(1).parse()?,
+            pgrp: field(2).parse()?,
+            sid: field(3).parse()?,
+            flags: field(6).parse()?,
+            nice: field(16).parse()?,
+        })
+    }
+}
+
+impl Debuggee {
+    /// Writes a core file of the debuggee, like `gcore` does
+    ///
+    /// Every readable region of the [`ProcessMemoryMap`] is copied into a `PT_LOAD` segment,
+    /// with the original code in place of enabled [breakpoints](crate::breakpoint::Breakpoint).
+    /// The notes contain the registers of every thread (`NT_PRSTATUS`), information about the
+    /// process (`NT_PRPSINFO`), the auxiliary vector (`NT_AUXV`) and the mapped files
+    /// (`NT_FILE`). The selected thread comes first, so that it is treated as the thread that
+    /// crashed.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - Where to write the core file
+    /// * `sig` - The signal the selected thread stopped with, if any
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The size of the written core file in bytes
+    /// * `Err(DebuggerError)` - If the core file could not be written
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not a live process
+    /// - The memory map, the registers or the information in `/proc` cannot be read
+    /// - The core file cannot be written
+    pub fn generate_core(&self, path: &Path, sig: Option<Signal>) -> Result<usize> {
+        if self.is_core() {
+            return Err(DebuggerError::CoreDumpNotLive);
+        }
+        let stat = ProcStat::read(self.pid)?;
+        let map = self.get_process_map()?;
+
+        let mut notes = Vec::new();
+        let mut tids: Vec<Pid> = vec![self.selected_thread];
+        tids.extend(self.threads.keys().filter(|t| **t != self.selected_thread));
+        for (i, tid) in tids.iter().enumerate() {
+            let sig = if i == 0 {
+                sig
+            } else {
+                self.threads.get(tid).and_then(|t| t.pending_signal)
+            };
+            let prstatus = build_prstatus(*tid, &stat, sig, &self.get_regs(*tid)?);
+            push_note(&mut notes, elf::NT_PRSTATUS, &prstatus);
+            if i == 0 {
+                push_note(
+                    &mut notes,
+                    elf::NT_PRPSINFO,
+                    &build_prpsinfo(self.pid, &stat)?,
+                );
+                let auxv = std::fs::read(format!("/proc/{}/auxv", self.pid))?;
+                push_note(&mut notes, elf::NT_AUXV, &auxv);
+                push_note(&mut notes, elf::NT_FILE, &build_nt_file(&map));
+            }
+        }
+
+        let phnum = 1 + map.regions.len();
+        let headers_size = ELF_HEADER_SIZE + phnum * PROGRAM_HEADER_SIZE;
+        let notes_offset = headers_size;
+        let memory_offset = (notes_offset + notes.len()).next_multiple_of(PAGE_SIZE);
+        let mut load_headers = Vec::with_capacity(map.regions.len() * PROGRAM_HEADER_SIZE);
+        let mut offset = write_load_headers(&mut load_headers, &map, memory_offset);
+
+        let mut out = Vec::with_capacity(headers_size);
+        // the section header for too many program headers goes after the memory
+        write_elf_header(&mut out, phnum, offset);
+        write_program_header(
+            &mut out,
+            elf::PT_NOTE,
+            0,
+            notes_offset,
+            0,
+            notes.len(),
+            0,
+            4,
+        );
+        out.extend_from_slice(&load_headers);
+
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(&out)?;
+        file.write_all(&notes)?;
+        file.write_all(&vec![0; memory_offset - (notes_offset + notes.len())])?;
+
+        let mut chunk = vec![0; COPY_CHUNK_SIZE];
+        for region in map.regions.iter().filter(|r| r.permissions.read) {
+            let mut addr = region.start_address;
+            while addr < region.end_address {
+                let len = COPY_CHUNK_SIZE.min((region.end_address - addr).usize());
+                let buf = &mut chunk[..len];
+                self.read_for_core(buf, addr);
+                file.write_all(buf)?;
+                addr += len;
+            }
+        }
+        if phnum >= elf::PN_XNUM as usize {
+            let mut section_header = Vec::with_capacity(SECTION_HEADER_SIZE);
+            write_xnum_section_header(&mut section_header, phnum);
+            file.write_all(&section_header)?;
+            offset += SECTION_HEADER_SIZE;
+        }
+        file.flush()?;
+
+        debug!(
+            "wrote core of process {} with {} threads and {} regions",
+            self.pid,
+            tids.len(),
+            map.regions.len()
+        );
+        Ok(offset)
+    }
+
+    /// Reads memory for a core file, with the original code in place of breakpoints
+    ///
+    /// Memory that cannot be read, like `[vvar]`, is filled with zeros.
+    fn read_for_core(&self, buf: &mut [u8], addr: Addr) {
+        let mut done = 0;
+        while done < buf.len() {
+            match mem_read(&mut buf[done..], self.pid, addr + done) {
+                Ok(0) => break,
+                Ok(len) => done += len,
+                Err(e) => {
+                    trace!("could not read {} for the core file: {e}", addr + done);
+                    break;
+                }
+            }
+        }
+        buf[done..].fill(0);
+        self.hide_breakpoints(buf, addr);
+    }
+}
+
+/// Appends a note with the name `CORE` to the notes of a core file
+fn push_note(notes: &mut Vec<u8>, n_type: u32, desc: &[u8]) {
+    let name = b"CORE\0";
+    notes.extend_from_slice(&(name.len() as u32).to_ne_bytes());
+    notes.extend_from_slice(&(desc.len() as u32).to_ne_bytes());
+    notes.extend_from_slice(&n_type.to_ne_bytes());
+    notes.extend_from_slice(name);
+    notes.resize(notes.len().next_multiple_of(4), 0);
+    notes.extend_from_slice(desc);
+    notes.resize(notes.len().next_multiple_of(4), 0);
+}
+
+/// Writes the ELF header of a core file
+///
+/// With [`elf::PN_XNUM`] or more program headers, their number does not fit into `e_phnum`.
+/// It is stored in the section header at `shoff` instead, see [`write_xnum_section_header`].
+fn write_elf_header(out: &mut Vec<u8>, phnum: usize, shoff: usize) {
+    let extended = phnum >= elf::PN_XNUM as usize;
+    let mut ident = [0u8; 16];
+    ident[..4].copy_from_slice(&elf::ELFMAG);
+    ident[4] = elf::ELFCLASS64;
+    ident[5] = elf::ELFDATA2LSB;
+    ident[6] = elf::EV_CURRENT;
+    ident[7] = elf::ELFOSABI_SYSV;
+    out.extend_from_slice(&ident);
+    out.extend_from_slice(&elf::ET_CORE.to_le_bytes());
+    out.extend_from_slice(&elf::EM_X86_64.to_le_bytes());
+    out.extend_from_slice(&u32::from(elf::EV_CURRENT).to_le_bytes());
+    out.extend_from_slice(&0u64.to_le_bytes()); // e_entry
+    out.extend_from_slice(&(ELF_HEADER_SIZE as u64).to_le_bytes()); // e_phoff
+    let (shoff, shentsize, shnum, phnum) = if extended {
+        (shoff, SECTION_HEADER_SIZE as u16, 1u16, elf::PN_XNUM)
+    } else {
+        (0, 0, 0, phnum as u16)
+    };
+    out.extend_from_slice(&(shoff as u64).to_le_bytes()); // e_shoff
+    out.extend_from_slice(&0u32.to_le_bytes()); // e_flags
+    out.extend_from_slice(&(ELF_HEADER_SIZE as u16).to_le_bytes());
+    out.extend_from_slice(&(PROGRAM_HEADER_SIZE as u16).to_le_bytes());
+    out.extend_from_slice(&phnum.to_le_bytes());
+    out.extend_from_slice(&shentsize.to_le_bytes());
+    out.extend_from_slice(&shnum.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // e_shstrndx
+}
+
+/// Writes the only section header of a core file with too many program headers for `e_phnum`
+///
+/// Like the kernel does, the real number of program headers is stored in `sh_info`.
+fn write_xnum_section_header(out: &mut Vec<u8>, phnum: usize) {
+    out.extend_from_slice(&0u32.to_le_bytes()); // sh_name
+    out.extend_from_slice(&elf::SHT_NULL.to_le_bytes());
+    out.extend_from_slice(&0u64.to_le_bytes()); // sh_flags
+    out.extend_from_slice(&0u64.to_le_bytes()); // sh_addr
+    out.extend_from_slice(&0u64.to_le_bytes()); // sh_offset
+    out.extend_from_slice(&0u64.to_le_bytes()); // sh_size
+    out.extend_from_slice(&0u32.to_le_bytes()); // sh_link
+    out.extend_from_slice(&(phnum as u32).to_le_bytes()); // sh_info
+    out.extend_from_slice(&0u64.to_le_bytes()); // sh_addralign
+    out.extend_from_slice(&0u64.to_le_bytes()); // sh_entsize
+}
+
+/// Writes the `PT_LOAD` program headers for the regions of a memory map
+///
+/// The contents of the readable regions follow each other in the core file, starting at
+/// `offset`.
+///
+/// # Returns
+///
+/// The offset in the core file after the contents of the last region
+fn write_load_headers(out: &mut Vec<u8>, map: &ProcessMemoryMap, mut offset: usize) -> usize {
+    for region in &map.regions {
+        let filesz = if region.permissions.read {
+            region.size
+        } else {
+            0
+        };
+        let mut flags = 0;
+        if region.permissions.read {
+            flags |= elf::PF_R;
+        }
+        if region.permissions.write {
+            flags |= elf::PF_W;
+        }
+        if region.permissions.execute {
+            flags |= elf::PF_X;
+        }
+        write_program_header(
+            out,
+            elf::PT_LOAD,
+            flags,
+            offset,
+            region.start_address.usize(),
+            filesz,
+            region.size,
+            PAGE_SIZE,
+        );
+        offset += filesz;
+    }
+    offset
+}
+
+/// Writes a program header of a core file
+#[allow(clippy::too_many_arguments)] // these are just the fields of the header
+fn write_program_header(
+    out: &mut Vec<u8>,
+    p_type: u32,
+    flags: u32,
+    offset: usize,
+    vaddr: usize,
+    filesz: usize,
+    memsz: usize,
+    align: usize,
+) {
+    out.extend_from_slice(&p_type.to_le_bytes());
+    out.extend_from_slice(&flags.to_le_bytes());
+    for v in [offset, vaddr, 0, filesz, memsz, align] {
+        out.extend_from_slice(&(v as u64).to_le_bytes());
+    }
+}
+
+/// Builds the `NT_PRSTATUS` note of a thread
+fn build_prstatus(
+    tid: Pid,
+    stat: &ProcStat,
+    sig: Option<Signal>,
+    regs: &user_regs_struct,
+) -> Vec<u8> {
+    let mut desc = vec![0; PRSTATUS_SIZE];
+    let signum = sig.map_or(0, |s| s as i32);
+    desc[..4].copy_from_slice(&signum.to_ne_bytes());
+    desc[PRSTATUS_CURSIG..PRSTATUS_CURSIG + 2].copy_from_slice(&(signum as u16).to_ne_bytes());
+    for (i, id) in [tid.as_raw(), stat.ppid, stat.pgrp, stat.sid]
+        .iter()
+        .enumerate()
+    {
+        let at = PRSTATUS_PID + i * 4;
+        desc[at..at + 4].copy_from_slice(&id.to_ne_bytes());
+    }
+    for (i, reg) in regs_to_array(regs).iter().enumerate() {
+        let at = PRSTATUS_REGS + i * 8;
+        desc[at..at + 8].copy_from_slice(&reg.to_ne_bytes());
+    }
+    desc
+}
+
+/// Builds the `NT_PRPSINFO` note of a process
+fn build_prpsinfo(pid: Pid, stat: &ProcStat) -> Result<Vec<u8>> {
+    let mut desc = vec![0; PRPSINFO_SIZE];
+    desc[0] = b"RSDTtZX"
+        .iter()
+        .position(|s| *s == stat.state)
+        .unwrap_or(0) as u8;
+    desc[1] = stat.state;
+    desc[2] = u8::from(stat.state == b'Z');
+    desc[3] = stat.nice as u8;
+    desc[PRPSINFO_FLAG..PRPSINFO_FLAG + 8].copy_from_slice(&stat.flags.to_ne_bytes());
+    let owner = std::fs::metadata(format!("/proc/{pid}"))?;
+    desc[PRPSINFO_UID..PRPSINFO_UID + 4].copy_from_slice(&owner.uid().to_ne_bytes());
+    desc[PRPSINFO_UID + 4..PRPSINFO_UID + 8].copy_from_slice(&owner.gid().to_ne_bytes());
+    for (i, id) in [pid.as_raw(), stat.ppid, stat.pgrp, stat.sid]
+        .iter()
+        .enumerate()
+    {
+        let at = PRPSINFO_PID + i * 4;
+        desc[at..at + 4].copy_from_slice(&id.to_ne_bytes());
+    }
+
+    // both are cut off and need to end with a NUL byte
+    let fname = stat.comm.as_bytes();
+    let len = fname.len().min(PRPSINFO_FNAME_LEN - 1);
+    desc[PRPSINFO_FNAME..PRPSINFO_FNAME + len].copy_from_slice(&fname[..len]);
+    let mut psargs = std::fs::read(format!("/proc/{pid}/cmdline"))?;
+    psargs.truncate(PRPSINFO_PSARGS_LEN - 1);
+    for b in &mut psargs {
+        if *b == 0 {
+            *b = b' ';
+        }
+    }
+    while psargs.last() == Some(&b' ') {
+        psargs.pop();
+    }
+    desc[PRPSINFO_PSARGS..PRPSINFO_PSARGS + psargs.len()].copy_from_slice(&psargs);
+    Ok(desc)
+}
+
+/// Builds the `NT_FILE` note from the mapped files of a memory map, see [`parse_nt_file`]
+fn build_nt_file(map: &ProcessMemoryMap) -> Vec<u8> {
+    let files: Vec<&MemoryRegion> = map
+        .regions
+        .iter()
+        .filter(|r| r.inode != 0 && r.path.as_ref().is_some_and(|p| p.starts_with('/')))
+        .collect();
+
+    let mut desc = Vec::new();
+    for v in [files.len(), PAGE_SIZE] {
+        desc.extend_from_slice(&(v as u64).to_ne_bytes());
+    }
+    for region in &files {
+        for v in [
+            region.start_address.usize(),
+            region.end_address.usize(),
+            region.offset / PAGE_SIZE,
+        ] {
+            desc.extend_from_slice(&(v as u64).to_ne_bytes());
+        }
+    }
+    for region in &files {
+        desc.extend_from_slice(region.path.as_deref().unwrap_or_default().as_bytes());
+        desc.push(0);
+    }
+    desc
+}
+
+/// Reads from a file at the given offset
+fn read_file_at(path: &Path, offset: usize, buf: &mut [u8]) -> Result<usize> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(offset as u64))?;
+    Ok(file.read(buf)?)
+}
+
+/// Lists the registers in the order of the `user_regs_struct`
+fn regs_to_array(regs: &user_regs_struct) -> [u64; USER_REGS_COUNT] {
+    [
+        regs.r15,
+        regs.r14,
+        regs.r13,
+        regs.r12,
+        regs.rbp,
+        regs.rbx,
+        regs.r11,
+        regs.r10,
+        regs.r9,
+        regs.r8,
+        regs.rax,
+        regs.rcx,
+        regs.rdx,
+        regs.rsi,
+        regs.rdi,
+        regs.orig_rax,
+        regs.rip,
+        regs.cs,
+        regs.eflags,
+        regs.rsp,
+        regs.ss,
+        regs.fs_base,
+        regs.gs_base,
+        regs.ds,
+        regs.es,
+        regs.fs,
+        regs.gs,
+    ]
+}
+
+/// Parses the thread ID and registers of a `NT_PRSTATUS` note
+fn parse_prstatus(desc: &[u8]) -> Result<CoreThread> {
+    let tid = Pid::from_raw(read_u32(desc, PRSTATUS_PID)? as i32);
+    let mut r = [0u64; USER_REGS_COUNT];
+    for (i, reg) in r.iter_mut().enumerate() {
+        *reg = read_u64(desc, PRSTATUS_REGS + i * 8)?;
+    }
+    // same order as in the struct
+    let regs = user_regs_struct {
+        r15: r[0],
+        r14: r[1],
+        r13: r[2],
+        r12: r[3],
+        rbp: r[4],
+        rbx: r[5],
+        r11: r[6],
+        r10: r[7],
+        r9: r[8],
+        r8: r[9],
+        rax: r[10],
+        rcx: r[11],
+        rdx: r[12],
+        rsi: r[13],
+        rdi: r[14],
+        orig_rax: r[15],
+        rip: r[16],
+        cs: r[17],
+        eflags: r[18],
+        rsp: r[19],
+        ss: r[20],
+        fs_base: r[21],
+        gs_base: r[22],
+        ds: r[23],
+        es: r[24],
+        fs: r[25],
+        gs: r[26],
+    };
+    Ok(CoreThread { tid, regs })
+}
+
+/// Parses the process name of a `NT_PRPSINFO` note
+fn parse_fname(desc: &[u8]) -> Option<String> {
+    let raw = desc.get(PRPSINFO_FNAME..PRPSINFO_FNAME + PRPSINFO_FNAME_LEN)?;
+    let raw = raw.split(|b| *b == 0).next()?;
+    Some(String::from_utf8_lossy(raw).to_string())
+}
+
+/// Parses the `NT_FILE` note
+///
+/// It starts with the number of entries and the page size, followed by a start address, end
+/// address and file offset (in pages) for every entry, followed by the paths of the entries.
+fn parse_nt_file(desc: &[u8]) -> Result<Vec<MappedFile>> {
+    let cut_off = || DebuggerError::InvalidCoreDump("the NT_FILE note is cut off".to_string());
+    let count = read_u64(desc, 0)? as usize;
+    let page_size = read_u64(desc, 8)? as usize;
+    // the count comes from the file, it must not be trusted before it is checked
+    if count > desc.len() / NT_FILE_ENTRY_SIZE {
+        return Err(cut_off());
+    }
+    let names = desc
+        .get(16 + count * NT_FILE_ENTRY_SIZE..)
+        .ok_or_else(cut_off)?;
+
+    let mut files = Vec::with_capacity(count);
+    for (i, name) in names.split(|b| *b == 0).take(count).enumerate() {
+        let entry = 16 + i * NT_FILE_ENTRY_SIZE;
+        let offset = (read_u64(desc, entry + 16)? as usize)
+            .checked_mul(page_size)
+            .ok_or_else(|| {
+                DebuggerError::InvalidCoreDump("a file offset in NT_FILE is too large".to_string())
+            })?;
+        files.push(MappedFile {
+            start: read_u64(desc, entry)? as usize,
+            end: read_u64(desc, entry + 8)? as usize,
+            offset,
+            path: PathBuf::from(String::from_utf8_lossy(name).to_string()),
+        });
+    }
+    Ok(files)
+}
+
+/// Reads a native endian integer out of a note
+fn read_bytes<const N: usize>(desc: &[u8], offset: usize) -> Result<[u8; N]> {
+    offset
+        .checked_add(N)
+        .and_then(|end| desc.get(offset..end))
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| DebuggerError::InvalidCoreDump("a note is cut off".to_string()))
+}
+
+fn read_u16(desc: &[u8], offset: usize) -> Result<u16> {
+    Ok(u16::from_ne_bytes(read_bytes(desc, offset)?))
+}
+
+fn read_u32(desc: &[u8], offset: usize) -> Result<u32> {
+    Ok(u32::from_ne_bytes(read_bytes(desc, offset)?))
+}
+
+fn read_u64(desc: &[u8], offset: usize) -> Result<u64> {
+    Ok(u64::from_ne_bytes(read_bytes(desc, offset)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_nt_file() {
+        let mut desc = Vec::new();
+        for v in [2u64, 0x1000, 0x5000, 0x6000, 0, 0x7000, 0x9000, 3] {
+            desc.extend_from_slice(&v.to_ne_bytes());
+        }
+        desc.extend_from_slice(b"/bin/exe\0/lib/libc.so.6\0");
+
+        let files = parse_nt_file(&desc).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, PathBuf::from("/bin/exe"));
+        assert_eq!(files[1].start, 0x7000);
+        assert_eq!(files[1].offset, 0x3000);
+    }
+
+    #[test]
+    fn test_parse_nt_file_rejects_huge_count() {
+        let mut desc = Vec::new();
+        for v in [u64::MAX / 8, 0x1000, 0x5000, 0x6000, 0] {
+            desc.extend_from_slice(&v.to_ne_bytes());
+        }
+        desc.extend_from_slice(b"/bin/exe\0");
+        assert!(matches!(
+            parse_nt_file(&desc),
+            Err(DebuggerError::InvalidCoreDump(_))
+        ));
+
+        // a page offset that overflows when it is turned into bytes
+        let mut desc = Vec::new();
+        for v in [1, 0x1000, 0x5000, 0x6000, u64::MAX] {
+            desc.extend_from_slice(&v.to_ne_bytes());
+        }
+        desc.extend_from_slice(b"/bin/exe\0");
+        assert!(parse_nt_file(&desc).is_err());
+        assert!(read_u64(&desc, usize::MAX - 2).is_err());
+    }
+
+    #[test]
+    fn test_elf_header_with_many_program_headers() {
+        let endian = object::LittleEndian;
+        for phnum in [3, elf::PN_XNUM as usize, 70_000] {
+            let shoff = ELF_HEADER_SIZE;
+            let mut out = Vec::new();
+            write_elf_header(&mut out, phnum, shoff);
+            assert_eq!(out.len(), ELF_HEADER_SIZE);
+            write_xnum_section_header(&mut out, phnum);
+
+            let header = elf::FileHeader64::<object::LittleEndian>::parse(out.as_slice()).unwrap();
+            assert_eq!(header.phnum(endian, out.as_slice()).unwrap(), phnum);
+        }
+    }
+
+    #[test]
+    fn test_prstatus_roundtrip() {
+        // SAFETY: user_regs_struct only contains integers
+        let mut regs: user_regs_struct = unsafe { std::mem::zeroed() };
+        regs.rip = 0x5555_5555_1234;
+        regs.rbp = 0x7fff_ffff_e000;
+        regs.gs = 7;
+
+        let desc = build_prstatus(
+            Pid::from_raw(42),
+            &ProcStat::default(),
+            Some(Signal::SIGSEGV),
+            &regs,
+        );
+        assert_eq!(desc.len(), PRSTATUS_SIZE);
+        assert_eq!(
+            read_u16(&desc, PRSTATUS_CURSIG).unwrap(),
+            Signal::SIGSEGV as u16
+        );
+
+        let thread = parse_prstatus(&desc).unwrap();
+        assert_eq!(thread.tid, Pid::from_raw(42));
+        assert_eq!(regs_to_array(&thread.regs), regs_to_array(&regs));
+    }
+}
@@ -12,12 +12,12 @@ use std::fmt::Debug;
 use std::rc::Rc;
 
 use gimli::{Attribute, Encoding, EndianRcSlice, NativeEndian, Reader};
-use object::{Object, ObjectSection};
+use object::{Object, ObjectSection, ObjectSegment};
 use serde::Serialize;
 
 use crate::dwarf_parse::GimliReaderThing;
 use crate::errors::{DebuggerError, Result};
-use crate::Addr;
+use crate::{Addr, PAGE_SIZE};
 
 /// Type alias for the Gimli reader used for DWARF information parsing
 type GimliRd = EndianRcSlice<NativeEndian>;
@@ -69,6 +69,24 @@ pub enum SymbolKind {
 
     /// A lexical scope block
     Block,
+
+    /// A member of a structure, class or union
+    Member,
+
+    /// A pointer or a reference
+    Pointer,
+
+    /// A structure, class or union
+    Struct,
+
+    /// An array
+    Array,
+
+    /// An enumeration
+    Enum,
+
+    /// A typedef or a qualified type, like a `const` one, which is stored like its datatype
+    Typedef,
 }
 
 /// Represents a parsed symbol from the debug information
@@ -77,8 +95,8 @@ pub enum SymbolKind {
 /// information, including its name, address range, type information, and children
 /// symbols that are in its scope.
 ///
-/// Note that `encoding`, `frame_base` and `location` are skipped when serializing this with
-/// [`serde`], as they are [`gimli`] datatypes.
+/// Note that `encoding`, `frame_base`, `location` and `base_encoding` are skipped when serializing
+/// this with [`serde`], as they are [`gimli`] datatypes.
 #[derive(Clone, Serialize, PartialEq, Eq)]
 pub struct OwnedSymbol {
     offset: usize,
@@ -93,6 +111,9 @@ pub struct OwnedSymbol {
     #[serde(skip)]
     frame_base: Option<Attribute<GimliReaderThing>>,
     byte_size: Option<usize>,
+    member_offset: Option<usize>,
+    #[serde(skip)]
+    base_encoding: Option<gimli::DwAte>,
     #[serde(skip)]
     encoding: gimli::Encoding,
 }
@@ -134,6 +155,8 @@ impl OwnedSymbol {
             frame_base: None,
             children: children.to_vec(),
             byte_size: None,
+            member_offset: None,
+            base_encoding: None,
             encoding,
         }
     }
@@ -188,6 +211,16 @@ impl OwnedSymbol {
         self.byte_size = byte_size;
     }
 
+    /// Sets the member offset of this [`OwnedSymbol`].
+    pub fn set_member_offset(&mut self, member_offset: Option<usize>) {
+        self.member_offset = member_offset;
+    }
+
+    /// Sets the base type encoding of this [`OwnedSymbol`].
+    pub fn set_base_encoding(&mut self, base_encoding: Option<gimli::DwAte>) {
+        self.base_encoding = base_encoding;
+    }
+
     /// Sets the encoding of this [`OwnedSymbol`].
     pub fn set_encoding(&mut self, encoding: gimli::Encoding) {
         self.encoding = encoding;
@@ -253,6 +286,18 @@ impl OwnedSymbol {
         self.byte_size
     }
 
+    /// Returns the offset of this member in its structure, class or union.
+    #[must_use]
+    pub fn member_offset(&self) -> Option<usize> {
+        self.member_offset
+    }
+
+    /// Returns how the values of this base type are encoded, like signed, unsigned or float.
+    #[must_use]
+    pub fn base_encoding(&self) -> Option<gimli::DwAte> {
+        self.base_encoding
+    }
+
     /// Returns the encoding of this [`OwnedSymbol`].
     #[must_use]
     pub fn encoding(&self) -> Encoding {
@@ -302,6 +347,36 @@ impl<'executable> CMDebugInfo<'executable> {
 
         Ok(CMDebugInfo { object_info, dwarf })
     }
+
+    /// Calculates the load bias: how far the executable was moved from where it was linked
+    ///
+    /// Addresses in the debug information are link addresses. A position independent executable
+    /// is linked at 0, so that its load bias is its load address. Other executables are loaded
+    /// where they were linked, their load bias is 0.
+    ///
+    /// # Parameters
+    ///
+    /// * `base_addr` - The address the first segment of the executable was loaded at
+    #[must_use]
+    pub fn load_bias(&self, base_addr: Addr) -> Addr {
+        load_bias(&self.object_info, base_addr)
+    }
+}
+
+/// Calculates the load bias of any object file, see [`CMDebugInfo::load_bias`]
+///
+/// # Parameters
+///
+/// * `object_info` - The object file
+/// * `base_addr` - The address the first segment of the object file was loaded at
+#[must_use]
+pub(crate) fn load_bias(object_info: &object::File<'_>, base_addr: Addr) -> Addr {
+    let link_addr = object_info
+        .segments()
+        .map(|s| s.address())
+        .min()
+        .unwrap_or(0);
+    base_addr - (link_addr as usize & !(PAGE_SIZE - 1))
 }
 
 impl TryFrom<gimli::DwTag> for SymbolKind {
@@ -318,6 +393,20 @@ impl TryFrom<gimli::DwTag> for SymbolKind {
             | gimli::DW_TAG_catch_block
             | gimli::DW_TAG_lexical_block
             | gimli::DW_TAG_common_block => SymbolKind::Block,
+            gimli::DW_TAG_member => SymbolKind::Member,
+            gimli::DW_TAG_pointer_type
+            | gimli::DW_TAG_reference_type
+            | gimli::DW_TAG_rvalue_reference_type => SymbolKind::Pointer,
+            gimli::DW_TAG_structure_type | gimli::DW_TAG_class_type | gimli::DW_TAG_union_type => {
+                SymbolKind::Struct
+            }
+            gimli::DW_TAG_array_type => SymbolKind::Array,
+            gimli::DW_TAG_enumeration_type => SymbolKind::Enum,
+            gimli::DW_TAG_typedef
+            | gimli::DW_TAG_const_type
+            | gimli::DW_TAG_volatile_type
+            | gimli::DW_TAG_restrict_type
+            | gimli::DW_TAG_atomic_type => SymbolKind::Typedef,
             _ => SymbolKind::Other,
         })
     }
@@ -341,6 +430,8 @@ impl Debug for OwnedSymbol {
                 &format_args!("{}", &dbg_large_option(self.frame_base())),
             )
             .field("byte_size", &self.byte_size)
+            .field("member_offset", &self.member_offset)
+            .field("base_encoding", &self.base_encoding)
             .field("children", &self.children)
             .field("encoding", &self.encoding)
             .finish()
@@ -481,9 +572,18 @@ mod tests {
             SymbolKind::try_from(gimli::DW_TAG_lexical_block).unwrap(),
             SymbolKind::Block
         );
-        // Test unknown tag becomes Other
         assert_eq!(
             SymbolKind::try_from(gimli::DW_TAG_array_type).unwrap(),
+            SymbolKind::Array
+        );
+        // Test types with qualifiers are grouped with typedefs
+        assert_eq!(
+            SymbolKind::try_from(gimli::DW_TAG_const_type).unwrap(),
+            SymbolKind::Typedef
+        );
+        // Test unknown tag becomes Other
+        assert_eq!(
+            SymbolKind::try_from(gimli::DW_TAG_subrange_type).unwrap(),
             SymbolKind::Other
         );
     }
@@ -500,13 +600,16 @@ mod tests {
         let parent = OwnedSymbol::new(0, SymbolKind::Function, &[child1, child2], encoding);
 
         // Search for symbol by name
-        let results = search_through_symbols(&[parent.clone()], |s| s.name() == Some("target"));
+        let results = search_through_symbols(std::slice::from_ref(&parent), |s| {
+            s.name() == Some("target")
+        });
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].name(), Some("target"));
 
         // Search by kind
-        let results =
-            search_through_symbols(&[parent.clone()], |s| s.kind() == SymbolKind::Variable);
+        let results = search_through_symbols(std::slice::from_ref(&parent), |s| {
+            s.kind() == SymbolKind::Variable
+        });
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].offset(), 1);
     }
@@ -533,7 +636,7 @@ mod tests {
             r#"{"offset":0,"name":"parent","low_addr":null,"high_addr":null,"datatype":null,"#,
             r#""kind":"Function","children":[{"offset":1,"name":"child","low_addr":null,"#,
             r#""high_addr":null,"datatype":null,"kind":"Variable","children":[],"#,
-            r#""byte_size":null}],"byte_size":null}"#
+            r#""byte_size":null,"member_offset":null}],"byte_size":null,"member_offset":null}"#
         );
 
         let encoding = test_encoding();
@@ -6,27 +6,48 @@
 //! debugged, and provides methods for interacting with that process. The debuggee
 //! is controlled through the [ptrace] API and manages debug symbols, breakpoints,
 //! memory access, and other low-level debugging operations.
+//!
+//! A [`Debuggee`] can also be built from a [`CoreDump`] of a crashed process. Registers and
+//! memory are then read from the core file, and everything that would change or run the process
+//! is refused by the [`Debugger`](crate::debugger::Debugger).
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Display;
+use std::path::Path;
 
 use gimli::{
-    Attribute, DW_AT_frame_base, DW_AT_high_pc, DW_AT_location, DW_AT_low_pc, DW_AT_name,
-    DW_AT_type, Unit,
+    Attribute, DW_AT_byte_size, DW_AT_data_member_location, DW_AT_encoding, DW_AT_frame_base,
+    DW_AT_high_pc, DW_AT_location, DW_AT_low_pc, DW_AT_name, DW_AT_type, DwAte, Unit,
 };
+use nix::libc::{user_fpregs_struct, user_regs_struct};
 use nix::sys::ptrace;
+use nix::sys::signal::Signal;
 use nix::unistd::Pid;
 use tracing::{debug, warn};
 
 use crate::breakpoint::{Breakpoint, INT3_BYTE};
+use crate::callframe::CallFrameTable;
+use crate::coredump::CoreDump;
 use crate::dbginfo::{search_through_symbols, CMDebugInfo, OwnedSymbol, SymbolKind};
-use crate::disassemble::Disassembly;
+use crate::disassemble::{instruction_flow, prologue_end, Disassembly, InstructionFlow};
 use crate::dwarf_parse::GimliReaderThing;
 use crate::errors::DebuggerError;
+use crate::hwbreakpoint::{DebugRegisters, HardwareBreakpoint};
+use crate::linetable::LineTable;
 use crate::memorymap::ProcessMemoryMap;
+use crate::sharedlib::{Rendezvous, SharedLibrary};
 use crate::stack::Stack;
-use crate::{get_reg, mem_read_word, Result};
+use crate::thread::{thread_name, Thread, ThreadInfo, ThreadState};
+use crate::unwind::{self, Backtrace};
+use crate::watchpoint::Watchpoint;
 use crate::{mem_read, Addr};
+use crate::{mem_read_word, reg_value, Register, Result, Word};
+
+/// At most this many bytes at the start of a function are searched for the end of its prologue
+const MAX_PROLOGUE_LEN: usize = 64;
+
+/// Maximum length of an `x86_64` instruction
+const MAX_INSTRUCTION_LEN: usize = 15;
 
 /// Represents a process being debugged
 ///
@@ -42,11 +63,57 @@ pub struct Debuggee {
 
     /// Debug symbols extracted from the executable
     pub(crate) symbols: Vec<OwnedSymbol>,
+
+    /// Line table extracted from the executable, mapping addresses to source lines
+    pub(crate) lines: LineTable,
+
+    /// Call frame information of the executable, describing the stack frames of its functions
+    pub(crate) frames: CallFrameTable,
+
+    /// Whether the process was attached to instead of being launched by the debugger
+    pub(crate) attached: bool,
+
+    /// Table of all known threads of the process by thread ID
+    pub(crate) threads: BTreeMap<Pid, Thread>,
+
+    /// Thread ID of the thread that register, backtrace and stepping operations act on
+    pub(crate) selected_thread: Pid,
+
+    /// Addresses of breakpoints that are disabled while a `vfork` child runs in the memory of
+    /// this process
+    pub(crate) suspended_breakpoints: Vec<Addr>,
+
+    /// Hardware breakpoints by address
+    pub(crate) hw_breakpoints: BTreeMap<Addr, HardwareBreakpoint>,
+
+    /// Watchpoints by the address of the watched region
+    pub(crate) watchpoints: BTreeMap<Addr, Watchpoint>,
+
+    /// Pages that are protected for software watchpoints, with their original protection as
+    /// `PROT_*` flags
+    pub(crate) guarded_pages: BTreeMap<Addr, i32>,
+
+    /// The debug registers that all threads should have, for the hardware breakpoints and
+    /// watchpoints
+    pub(crate) debug_registers: DebugRegisters,
+
+    /// The core file that registers and memory are read from, if this is not a live process
+    pub(crate) core: Option<CoreDump>,
+
+    /// Where the debugger learns about loaded shared libraries, if the executable is
+    /// dynamically linked
+    pub(crate) rendezvous: Option<Rendezvous>,
+
+    /// Shared libraries that are loaded into the process
+    pub(crate) libraries: Vec<SharedLibrary>,
 }
 
 impl Debuggee {
     /// Creates a new debuggee instance from a process ID, debug info, and breakpoints
     ///
+    /// If the executable is dynamically linked, the debuggee keeps track of its
+    /// [shared libraries](crate::sharedlib) from now on. If that fails, the problem is logged.
+    ///
     /// # Parameters
     ///
     /// * `pid` - The process ID of the debugged process
@@ -67,6 +134,97 @@ impl Debuggee {
         dbginfo: &CMDebugInfo<'_>,
         breakpoints: HashMap<Addr, Breakpoint>,
     ) -> Result<Self> {
+        let load_bias = dbginfo.load_bias(Self::get_base_addr_by_pid(pid)?);
+        let symbols = Self::parse_symbols(load_bias, dbginfo)?;
+        let lines = LineTable::build(dbginfo, load_bias)?;
+        let frames = CallFrameTable::build(dbginfo, load_bias)?;
+
+        let mut threads = BTreeMap::new();
+        threads.insert(pid, Thread::new(pid, ThreadState::Stopped));
+
+        let mut dbge = Self {
+            pid,
+            breakpoints,
+            symbols,
+            lines,
+            frames,
+            attached: false,
+            threads,
+            selected_thread: pid,
+            suspended_breakpoints: Vec::new(),
+            hw_breakpoints: BTreeMap::new(),
+            watchpoints: BTreeMap::new(),
+            guarded_pages: BTreeMap::new(),
+            debug_registers: DebugRegisters::default(),
+            core: None,
+            rendezvous: None,
+            libraries: Vec::new(),
+        };
+        if let Err(e) = dbge.watch_loader(dbginfo, load_bias) {
+            warn!("could not keep track of the shared libraries: {e}");
+        }
+        Ok(dbge)
+    }
+
+    /// Creates a debuggee from the core file of a crashed process
+    ///
+    /// All threads of the crashed process are in the thread table, the thread that crashed is
+    /// selected.
+    ///
+    /// # Parameters
+    ///
+    /// * `core` - The core file
+    /// * `dbginfo` - Debug information extracted from the executable of the crashed process
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if there are issues parsing the debug information or if the core
+    /// file does not tell where the executable was loaded.
+    pub(crate) fn build_core(core: CoreDump, dbginfo: &CMDebugInfo<'_>) -> Result<Self> {
+        let load_bias = dbginfo.load_bias(core.base_addr()?);
+        let symbols = Self::parse_symbols(load_bias, dbginfo)?;
+        let lines = LineTable::build(dbginfo, load_bias)?;
+        let frames = CallFrameTable::build(dbginfo, load_bias)?;
+        let threads = core
+            .threads()
+            .map(|tid| (tid, Thread::new(tid, ThreadState::Stopped)))
+            .collect();
+
+        Ok(Self {
+            pid: core.pid(),
+            breakpoints: HashMap::new(),
+            symbols,
+            lines,
+            frames,
+            attached: false,
+            threads,
+            selected_thread: core.crashed_thread(),
+            suspended_breakpoints: Vec::new(),
+            hw_breakpoints: BTreeMap::new(),
+            watchpoints: BTreeMap::new(),
+            guarded_pages: BTreeMap::new(),
+            debug_registers: DebugRegisters::default(),
+            core: Some(core),
+            rendezvous: None,
+            libraries: Vec::new(),
+        })
+    }
+
+    /// Parses the debug symbols of all compilation units
+    ///
+    /// # Parameters
+    ///
+    /// * `load_bias` - How far the executable was moved from its link address, see
+    ///   [`CMDebugInfo::load_bias`]
+    /// * `dbginfo` - Debug information extracted from the executable
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if there are issues parsing the debug information.
+    pub(crate) fn parse_symbols(
+        load_bias: Addr,
+        dbginfo: &CMDebugInfo<'_>,
+    ) -> Result<Vec<OwnedSymbol>> {
         let mut symbols = Vec::new();
         let dwarf = &dbginfo.dwarf;
         let mut iter = dwarf.units();
@@ -74,14 +232,264 @@ impl Debuggee {
         while let Some(header) = iter.next()? {
             let unit = dwarf.unit(header)?;
             let mut tree = unit.entries_tree(None)?;
-            symbols.push(Self::process_tree(pid, dwarf, &unit, tree.root()?)?);
+            symbols.push(Self::process_tree(load_bias, dwarf, &unit, tree.root()?)?);
         }
 
-        Ok(Self {
-            pid,
-            breakpoints,
-            symbols,
-        })
+        Ok(symbols)
+    }
+
+    /// Creates the debuggee for a child process that was just forked from this one
+    ///
+    /// The child has the same executable and libraries, so it gets a copy of the symbols. The
+    /// breakpoints
+    /// are inherited, see [`Breakpoint::inherit`]. Hardware breakpoints are not, as the kernel
+    /// does not copy the debug registers. Neither are watchpoints, but the child knows which
+    /// pages were protected for them, see [`Self::unguard_pages`]. The child has a single
+    /// thread, which is stopped.
+    ///
+    /// # Parameters
+    ///
+    /// * `child` - The process ID of the child process
+    #[must_use]
+    pub(crate) fn fork_child(&self, child: Pid) -> Self {
+        let mut threads = BTreeMap::new();
+        threads.insert(child, Thread::new(child, ThreadState::Stopped));
+
+        Self {
+            pid: child,
+            breakpoints: self
+                .breakpoints
+                .iter()
+                .map(|(addr, bp)| (*addr, bp.inherit(child)))
+                .collect(),
+            symbols: self.symbols.clone(),
+            lines: self.lines.clone(),
+            frames: self.frames.clone(),
+            attached: self.attached,
+            threads,
+            selected_thread: child,
+            suspended_breakpoints: Vec::new(),
+            hw_breakpoints: BTreeMap::new(),
+            watchpoints: BTreeMap::new(),
+            // the child inherits the protection of the pages, but not the watchpoints
+            guarded_pages: self.guarded_pages.clone(),
+            debug_registers: DebugRegisters::default(),
+            core: None,
+            rendezvous: self.rendezvous.clone(),
+            libraries: self.libraries.clone(),
+        }
+    }
+
+    /// Checks if the debugged process was attached to instead of being launched
+    ///
+    /// Attached processes are detached instead of killed when the debugging session ends.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the process was attached to with [`ptrace::attach`]
+    /// * `false` if the process was launched by the debugger
+    #[inline]
+    #[must_use]
+    pub fn is_attached(&self) -> bool {
+        self.attached
+    }
+
+    /// Checks if the debuggee is the core file of a crashed process instead of a live process
+    #[inline]
+    #[must_use]
+    pub fn is_core(&self) -> bool {
+        self.core.is_some()
+    }
+
+    /// Gets the core file the debuggee was built from, if it is not a live process
+    #[inline]
+    #[must_use]
+    pub fn core(&self) -> Option<&CoreDump> {
+        self.core.as_ref()
+    }
+
+    /// Reads memory of the debuggee, from the process or from the core file
+    ///
+    /// # Parameters
+    ///
+    /// * `buf` - Buffer to read into
+    /// * `addr` - Address to start reading at
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - How many bytes were read
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the memory cannot be read.
+    pub fn read_memory(&self, buf: &mut [u8], addr: Addr) -> Result<usize> {
+        match &self.core {
+            Some(core) => core.read_memory(buf, addr),
+            None => mem_read(buf, self.pid, addr),
+        }
+    }
+
+    /// Reads a [Word] of memory of the debuggee, from the process or from the core file
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the memory cannot be read.
+    pub fn read_word(&self, addr: Addr) -> Result<Word> {
+        match &self.core {
+            Some(core) => core.read_word(addr),
+            None => mem_read_word(self.pid, addr),
+        }
+    }
+
+    /// Gets all registers of a thread, from the process or from the core file
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the registers cannot be read.
+    pub fn get_regs(&self, tid: Pid) -> Result<user_regs_struct> {
+        match &self.core {
+            Some(core) => core.registers(tid),
+            None => Ok(ptrace::getregs(tid)?),
+        }
+    }
+
+    /// Gets the floating point and SSE registers of a thread
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the registers cannot be read, or the debuggee is a core file.
+    pub fn get_fpregs(&self, tid: Pid) -> Result<user_fpregs_struct> {
+        if self.is_core() {
+            return Err(DebuggerError::CoreDumpNotLive);
+        }
+        Ok(ptrace::getregset::<ptrace::regset::NT_PRFPREG>(tid)?)
+    }
+
+    /// Gets the value of a register of a thread, from the process or from the core file
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread
+    /// * `r` - The register to get
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the registers cannot be read.
+    pub fn get_reg(&self, tid: Pid, r: Register) -> Result<u64> {
+        Ok(reg_value(&self.get_regs(tid)?, r))
+    }
+
+    /// Gets the thread ID of the selected thread
+    ///
+    /// Register, backtrace and stepping operations act on this thread.
+    #[inline]
+    #[must_use]
+    pub fn selected_thread(&self) -> Pid {
+        self.selected_thread
+    }
+
+    /// Selects the thread that register, backtrace and stepping operations act on
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread to select
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the debuggee has no thread with that thread ID.
+    pub fn select_thread(&mut self, tid: Pid) -> Result<()> {
+        if !self.threads.contains_key(&tid) {
+            return Err(DebuggerError::NoSuchThread(tid.as_raw()));
+        }
+        self.selected_thread = tid;
+        Ok(())
+    }
+
+    /// Gets a thread from the thread table
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&Thread)` - If the thread is known
+    /// * `None` - If the debuggee has no thread with that thread ID
+    #[must_use]
+    pub fn thread(&self, tid: Pid) -> Option<&Thread> {
+        self.threads.get(&tid)
+    }
+
+    /// Gets all threads of the debuggee, ordered by their thread ID
+    pub fn threads(&self) -> impl Iterator<Item = &Thread> {
+        self.threads.values()
+    }
+
+    /// Collects information about all threads of the debuggee
+    ///
+    /// # Returns
+    ///
+    /// A list of [`ThreadInfo`], one for each thread, ordered by their thread ID
+    #[must_use]
+    pub fn list_threads(&self) -> Vec<ThreadInfo> {
+        self.threads
+            .keys()
+            .map(|tid| ThreadInfo {
+                tid: *tid,
+                name: match &self.core {
+                    Some(core) if *tid == self.pid => core.name().map(str::to_string),
+                    Some(_) => None,
+                    None => thread_name(self.pid, *tid),
+                },
+                rip: self.get_reg(*tid, Register::rip).ok().map(Addr::from),
+                selected: *tid == self.selected_thread,
+            })
+            .collect()
+    }
+
+    /// Resumes all stopped threads of the debugged process
+    ///
+    /// Threads that received a signal while all threads were being stopped get that signal
+    /// delivered now.
+    ///
+    /// # Parameters
+    ///
+    /// * `sig` - A signal to deliver to the selected thread, if any
+    /// * `syscalls` - Whether the threads should stop at syscalls, see [`Thread::resume`]
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the selected thread cannot be resumed. Other threads that cannot be
+    /// resumed are logged.
+    pub(crate) fn resume_all(&mut self, sig: Option<Signal>, syscalls: bool) -> Result<()> {
+        for thread in self.threads.values_mut() {
+            if thread.is_running() {
+                continue;
+            }
+            if let Err(e) = self.debug_registers.apply(thread) {
+                warn!(
+                    "could not write the debug registers of thread {}: {e}",
+                    thread.tid
+                );
+            }
+            if thread.tid == self.selected_thread {
+                thread.resume(sig, syscalls)?;
+            } else {
+                let sig = thread.pending_signal.take();
+                if let Err(e) = thread.resume(sig, syscalls) {
+                    warn!("could not resume thread {}: {e}", thread.tid);
+                }
+            }
+        }
+        Ok(())
     }
 
     /// Terminates the debugged process
@@ -97,10 +505,119 @@ impl Debuggee {
     ///
     /// This function can fail if the ptrace kill operation fails.
     pub fn kill(&self) -> Result<()> {
+        if self.is_core() {
+            return Ok(());
+        }
         ptrace::kill(self.pid)?;
         Ok(())
     }
 
+    /// Detaches from the debugged process, leaving it running untouched
+    ///
+    /// All [breakpoints](Breakpoint) are removed first, so that the original code of the
+    /// process is restored before it continues without the debugger. The debug registers of the
+    /// hardware breakpoints are cleared too, and the pages protected for software watchpoints
+    /// get their original protection back. Every thread of the process is detached.
+    ///
+    /// # Parameters
+    ///
+    /// * `sig` - A signal to deliver to the selected thread when it resumes, if any
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the process was successfully detached
+    /// * `Err(DebuggerError)` - If the process could not be detached
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if the debug registers or the protection of the pages cannot be
+    /// restored. Threads that cannot be detached from are logged.
+    pub fn detach(&mut self, sig: Option<Signal>) -> Result<()> {
+        // breakpoints get disabled on dropping
+        self.breakpoints.clear();
+        self.unguard_pages(self.selected_thread)?;
+        self.clear_hw_breakpoints()?;
+        for thread in self.threads.values_mut() {
+            let sig = if thread.tid == self.selected_thread {
+                sig
+            } else {
+                thread.pending_signal.take()
+            };
+            if let Err(e) = ptrace::detach(thread.tid, sig) {
+                warn!("could not detach from thread {}: {e}", thread.tid);
+            }
+        }
+        self.threads.clear();
+        Ok(())
+    }
+
+    /// Removes all breakpoints without touching the memory of the process
+    ///
+    /// This is used when the memory of the process was replaced by `execve`, or when it is
+    /// shared with a process that still needs the breakpoints after a `vfork`. Hardware
+    /// breakpoints are forgotten too, the kernel clears the debug registers on `execve`. So are
+    /// watchpoints and the protected pages. Without its breakpoint, the debugger does not learn
+    /// about [shared libraries](crate::sharedlib) anymore.
+    pub(crate) fn forget_breakpoints(&mut self) {
+        for bp in self.breakpoints.values_mut() {
+            bp.invalidate();
+        }
+        self.breakpoints.clear();
+        self.suspended_breakpoints.clear();
+        self.hw_breakpoints.clear();
+        self.watchpoints.clear();
+        self.guarded_pages.clear();
+        self.debug_registers.clear();
+        self.rendezvous = None;
+    }
+
+    /// Disables all enabled breakpoints until [`Self::resume_breakpoints`] is called
+    ///
+    /// A child created with `vfork` runs in the memory of its parent until it calls `execve` or
+    /// exits. The breakpoints of the parent are suspended meanwhile, so that the child does not
+    /// hit them. The pages protected for software watchpoints get their original protection
+    /// meanwhile.
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - A stopped thread of this process, which restores the protection of the pages
+    ///
+    /// # Errors
+    ///
+    /// This function fails if a breakpoint cannot be disabled or the protection of a page cannot
+    /// be restored.
+    pub(crate) fn suspend_breakpoints(&mut self, tid: Pid) -> Result<()> {
+        self.lift_page_protection(tid)?;
+        for (addr, bp) in &mut self.breakpoints {
+            if bp.is_enabled() {
+                bp.disable()?;
+                self.suspended_breakpoints.push(*addr);
+            }
+        }
+        Ok(())
+    }
+
+    /// Enables the breakpoints that were disabled by [`Self::suspend_breakpoints`] again
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - A stopped thread of this process, which protects the pages again
+    ///
+    /// # Errors
+    ///
+    /// This function fails if a breakpoint cannot be enabled or a page cannot be protected.
+    pub(crate) fn resume_breakpoints(&mut self, tid: Pid) -> Result<()> {
+        self.guard_pages(tid)?;
+        for addr in std::mem::take(&mut self.suspended_breakpoints) {
+            if let Some(bp) = self.breakpoints.get_mut(&addr) {
+                if !bp.is_enabled() {
+                    bp.enable()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Gets the memory map of a process by its PID
     ///
     /// # Parameters
@@ -158,7 +675,10 @@ impl Debuggee {
     /// This function can fail if the process's memory map cannot be accessed.
     #[inline]
     pub fn get_process_map(&self) -> Result<ProcessMemoryMap> {
-        Self::get_process_map_by_pid(self.pid)
+        match &self.core {
+            Some(core) => Ok(core.memory_map()),
+            None => Self::get_process_map_by_pid(self.pid),
+        }
     }
 
     /// Gets the base address of the debugged process
@@ -172,7 +692,10 @@ impl Debuggee {
     ///
     /// This function can fail if the process's memory map cannot be accessed.
     pub fn get_base_addr(&self) -> Result<Addr> {
-        Self::get_base_addr_by_pid(self.pid)
+        match &self.core {
+            Some(core) => core.base_addr(),
+            None => Self::get_base_addr_by_pid(self.pid),
+        }
     }
 
     /// Disassembles a section of memory in the debugged process
@@ -200,21 +723,18 @@ impl Debuggee {
     /// not exist after the [Disassembly] was created, this will also panic.
     pub fn disassemble(&self, addr: Addr, len: usize, literal: bool) -> Result<Disassembly> {
         let mut data_raw: Vec<u8> = vec![0; len];
-        mem_read(&mut data_raw, self.pid, addr)?;
+        self.read_memory(&mut data_raw, addr)?;
 
         let mut bp_indexes = Vec::new();
 
         for (idx, byte) in data_raw.iter_mut().enumerate() {
             if *byte == INT3_BYTE {
-                let bp = match self.breakpoints.get(&(addr + idx)) {
-                    None => {
-                        warn!(
-                            "found an int3 without breakpoint at {}, ignoring",
-                            addr + idx
-                        );
-                        continue;
-                    }
-                    Some(b) => b,
+                let Some(bp) = self.breakpoints.get(&(addr + idx)) else {
+                    warn!(
+                        "found an int3 without breakpoint at {}, ignoring",
+                        addr + idx
+                    );
+                    continue;
                 };
                 bp_indexes.push(idx);
 
@@ -242,7 +762,7 @@ impl Debuggee {
     ///
     /// # Parameters
     ///
-    /// * `pid` - The process ID (used to get the base address)
+    /// * `base_addr` - The address the executable is loaded at
     /// * `dwarf` - The DWARF debug information
     /// * `unit` - The compilation unit containing the entry
     /// * `entry` - The debugging information entry
@@ -257,18 +777,20 @@ impl Debuggee {
     /// This function can fail if there are issues parsing the debug information
     /// or if required attributes are missing.
     fn entry_from_gimli(
-        pid: Pid,
+        base_addr: Addr,
         dwarf: &gimli::Dwarf<GimliReaderThing>,
         unit: &Unit<GimliReaderThing>,
         entry: &gimli::DebuggingInformationEntry<'_, '_, GimliReaderThing>,
     ) -> Result<OwnedSymbol> {
-        let base_addr = Self::get_base_addr_by_pid(pid)?;
-
         let name = Self::parse_string(dwarf, unit, entry.attr(DW_AT_name)?)?;
         let kind = SymbolKind::try_from(entry.tag())?;
         let low = Self::parse_addr_low(dwarf, unit, entry.attr(DW_AT_low_pc)?, base_addr)?;
         let high = Self::parse_addr_high(entry.attr(DW_AT_high_pc)?, low)?;
         let datatype: Option<usize> = Self::parse_datatype(entry.attr(DW_AT_type)?);
+        let byte_size: Option<usize> = Self::parse_byte_size(entry.attr(DW_AT_byte_size)?);
+        let member_offset: Option<usize> =
+            Self::parse_member_offset(entry.attr(DW_AT_data_member_location)?);
+        let base_encoding: Option<DwAte> = Self::parse_base_encoding(entry.attr(DW_AT_encoding)?);
         let location: Option<Attribute<GimliReaderThing>> = entry.attr(DW_AT_location)?;
         let frame_base: Option<Attribute<GimliReaderThing>> = entry.attr(DW_AT_frame_base)?;
 
@@ -276,6 +798,9 @@ impl Debuggee {
         sym.set_name(name);
         sym.set_location(location);
         sym.set_datatype(datatype);
+        sym.set_byte_size(byte_size);
+        sym.set_member_offset(member_offset);
+        sym.set_base_encoding(base_encoding);
         sym.set_low_addr(low);
         sym.set_high_addr(high);
         sym.set_frame_base(frame_base);
@@ -286,7 +811,7 @@ impl Debuggee {
     ///
     /// # Parameters
     ///
-    /// * `pid` - The process ID
+    /// * `base_addr` - The address the executable is loaded at
     /// * `dwarf` - The DWARF debug information
     /// * `unit` - The compilation unit containing the tree
     /// * `node` - The tree node to process
@@ -300,19 +825,19 @@ impl Debuggee {
     ///
     /// This function can fail if there are issues parsing the debug information.
     fn process_tree(
-        pid: Pid,
+        base_addr: Addr,
         dwarf: &gimli::Dwarf<GimliReaderThing>,
         unit: &Unit<GimliReaderThing>,
         node: gimli::EntriesTreeNode<GimliReaderThing>,
     ) -> Result<OwnedSymbol> {
         let mut children: Vec<OwnedSymbol> = Vec::new();
-        let mut parent = Self::entry_from_gimli(pid, dwarf, unit, node.entry())?;
+        let mut parent = Self::entry_from_gimli(base_addr, dwarf, unit, node.entry())?;
 
         // then process it's children
         let mut children_tree = node.children();
         while let Some(child) = children_tree.next()? {
             // Recursively process a child.
-            children.push(match Self::process_tree(pid, dwarf, unit, child) {
+            children.push(match Self::process_tree(base_addr, dwarf, unit, child) {
                 Err(e) => {
                     debug!("could not parse a leaf of the debug symbol tree: {e}");
                     continue;
@@ -379,6 +904,159 @@ impl Debuggee {
         Ok(None)
     }
 
+    /// Resolves the address to break at for a function
+    ///
+    /// The prologue of the function is skipped with [`prologue_end`], so that a breakpoint at
+    /// the returned address stops after the stack frame was set up and the parameters were
+    /// stored, like a breakpoint on a function in gdb. The address is relocated by the load
+    /// address of the executable.
+    ///
+    /// If the executable has no such function, the loaded [shared libraries](crate::sharedlib)
+    /// are searched, see [`SharedLibrary`].
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the function
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Addr)` - The address after the prologue of the function
+    /// * `Err(DebuggerError)` - If the function could not be resolved
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - No function with this name and an address exists, in the executable or a library
+    /// - Multiple functions with this name exist at different addresses
+    /// - The code of the function cannot be read
+    pub fn resolve_function(&self, name: &str) -> Result<Addr> {
+        let mut functions: Vec<(Addr, Option<Addr>)> = self
+            .get_symbol_by_name(name)?
+            .iter()
+            .filter(|s| s.kind() == SymbolKind::Function)
+            .filter_map(|s| Some((s.low_addr()?, s.high_addr())))
+            .collect();
+        functions.sort_unstable();
+        functions.dedup();
+
+        let (low, high) = match functions[..] {
+            [] => self
+                .library_function(name)?
+                .ok_or_else(|| DebuggerError::FunctionNotFound(name.to_string()))?,
+            [function] => function,
+            _ => return Err(DebuggerError::MultipleDwarfEntries),
+        };
+        let addr = self.skip_prologue(low, high)?;
+        debug!("function {name} starts at {low}, its prologue ends at {addr}");
+        Ok(addr)
+    }
+
+    /// Finds the end of the prologue of a function, see [`prologue_end`]
+    ///
+    /// # Parameters
+    ///
+    /// * `low` - The address of the function
+    /// * `high` - The address after the end of the function, if it is known
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Addr)` - The address of the first instruction after the prologue
+    /// * `Err(DebuggerError)` - If the code of the function could not be read
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the code of the function cannot be read.
+    pub fn skip_prologue(&self, low: Addr, high: Option<Addr>) -> Result<Addr> {
+        let len = high.map_or(MAX_PROLOGUE_LEN, |high| {
+            (high - low).usize().min(MAX_PROLOGUE_LEN)
+        });
+
+        let mut code = vec![0; len];
+        self.read_memory(&mut code, low)?;
+        self.hide_breakpoints(&mut code, low);
+        Ok(prologue_end(&code, low))
+    }
+
+    /// Decodes the instruction at an address to see how it changes the control flow
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The address of the instruction
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the code at `addr` cannot be read.
+    pub fn instruction_flow(&self, addr: Addr) -> Result<InstructionFlow> {
+        let mut code = [0; MAX_INSTRUCTION_LEN];
+        self.read_memory(&mut code, addr)?;
+        self.hide_breakpoints(&mut code, addr);
+        Ok(instruction_flow(&code, addr))
+    }
+
+    /// Resolves the addresses to break at for a source line
+    ///
+    /// See [`LineTable::addrs_for_line`] for how the addresses are found. Of the addresses in
+    /// the same function, only the lowest is kept, so that a line with a loop condition stops
+    /// once when it is entered.
+    ///
+    /// # Parameters
+    ///
+    /// * `file` - The source file, or the end of its path
+    /// * `line` - The line in the source file
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((line, addrs))` - The line that has code, at or after `line`, and its addresses
+    /// * `Err(DebuggerError)` - If the line could not be resolved
+    ///
+    /// # Errors
+    ///
+    /// This function fails if no line at or after `line` in `file` has code.
+    pub fn resolve_line(&self, file: &Path, line: u64) -> Result<(u64, Vec<Addr>)> {
+        let (found, addrs) = self
+            .lines
+            .addrs_for_line(file, line)
+            .ok_or_else(|| DebuggerError::LineNotFound(file.to_path_buf(), line))?;
+
+        let mut functions = Vec::new();
+        let mut resolved = Vec::new();
+        for addr in addrs {
+            let function = self.get_function_by_addr(addr)?.and_then(|f| f.low_addr());
+            if function.is_none() || !functions.contains(&function) {
+                functions.push(function);
+                resolved.push(addr);
+            }
+        }
+        debug!(
+            "line {found} of {} is at {resolved:?}",
+            file.to_string_lossy()
+        );
+        Ok((found, resolved))
+    }
+
+    /// Gets the line table of the executable
+    #[inline]
+    #[must_use]
+    pub fn lines(&self) -> &LineTable {
+        &self.lines
+    }
+
+    /// Replaces the `int3` instructions of enabled breakpoints with the original code
+    ///
+    /// # Parameters
+    ///
+    /// * `buf` - Memory that was read from the debuggee
+    /// * `addr` - The address `buf` was read from
+    pub(crate) fn hide_breakpoints(&self, buf: &mut [u8], addr: Addr) {
+        for (bp_addr, bp) in &self.breakpoints {
+            if *bp_addr >= addr && *bp_addr < addr + buf.len() {
+                if let Some(original) = bp.saved_data() {
+                    buf[(*bp_addr - addr).usize()] = original;
+                }
+            }
+        }
+    }
+
     /// Gets local variables in scope at the specified address
     ///
     /// # Parameters
@@ -489,7 +1167,7 @@ impl Debuggee {
         search_through_symbols(self.symbols(), fil)
     }
 
-    /// Gets the current stack of the debugged process
+    /// Gets the current stack of the selected thread
     ///
     /// # Returns
     ///
@@ -502,16 +1180,48 @@ impl Debuggee {
     /// register values are not accessible.
     #[allow(clippy::similar_names)] // not my fault they named the registers that
     pub fn get_stack(&self) -> Result<Stack> {
-        let rbp: Addr = get_reg(self.pid, crate::Register::rbp)?.into();
-        let rsp: Addr = get_reg(self.pid, crate::Register::rsp)?.into();
+        let rbp: Addr = self
+            .get_reg(self.selected_thread, crate::Register::rbp)?
+            .into();
+        let rsp: Addr = self
+            .get_reg(self.selected_thread, crate::Register::rsp)?
+            .into();
 
         let mut next: Addr = rbp;
         let mut stack = Stack::new(rbp);
         while next >= rsp {
-            stack.push(mem_read_word(self.pid, next)?);
+            stack.push(self.read_word(next)?);
             next -= 8usize;
         }
 
         Ok(stack)
     }
+
+    /// Generates a [Backtrace] of the selected thread
+    ///
+    /// A live process is unwound with [`unwind::unwind`]. A core file is unwound by following
+    /// the frame pointers with [`unwind::unwind_frame_pointers`], the function names come from
+    /// the debug symbols.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if the registers cannot be read or the stack cannot be unwound.
+    pub fn backtrace(&self) -> Result<Backtrace> {
+        if !self.is_core() {
+            return unwind::unwind(self.selected_thread);
+        }
+
+        let regs = self.get_regs(self.selected_thread)?;
+        Ok(unwind::unwind_frame_pointers(
+            regs.rip.into(),
+            regs.rbp.into(),
+            |addr| self.read_word(addr),
+            |addr| {
+                self.get_function_by_addr(addr)
+                    .ok()
+                    .flatten()
+                    .and_then(|f| Some((f.low_addr()?, f.name()?.to_string())))
+            },
+        ))
+    }
 }
@@ -23,7 +23,7 @@
 //! - **[Debuggee]** - Various methods of the [Debuggee] struct.
 //!
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::CString;
 use std::fmt::Display;
 use std::path::{Path, PathBuf};
@@ -31,24 +31,44 @@ use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use iced_x86::FormatterTextKind;
+use nix::errno::Errno;
 use nix::sys::ptrace;
 use nix::sys::signal::Signal;
 use nix::sys::wait::{waitpid, WaitPidFlag, WaitStatus};
-use nix::unistd::execv;
+use nix::unistd::{execve, Pid};
 use tracing::{debug, error, info, trace, warn};
 use which::which;
 
-use crate::breakpoint::Breakpoint;
-use crate::consts::{SI_KERNEL, TRAP_BRKPT, TRAP_TRACE};
+use crate::breakpoint::{Breakpoint, BreakpointKind, BreakpointSettings};
+use crate::callframe::CallFrame;
+use crate::condition::{variable_to_i64, Condition, Operand};
+use crate::consts::{SEGV_ACCERR, SI_KERNEL, TRAP_BRKPT, TRAP_HWBKPT, TRAP_TRACE};
+use crate::coredump::CoreDump;
 use crate::dbginfo::{CMDebugInfo, OwnedSymbol};
 use crate::debuggee::Debuggee;
-use crate::disassemble::Disassembly;
+use crate::disassemble::{Disassembly, InstructionFlow};
 use crate::dwarf_parse::FrameInfo;
 use crate::errors::{DebuggerError, Result};
 use crate::feedback::{Feedback, InternalFeedback, Status};
+use crate::hwbreakpoint::HardwareBreakpoint;
+use crate::launch::{LaunchConfig, LaunchOptions};
+use crate::location::{Location, StopReason, UntilStop};
+use crate::memorymap::ModuleOffset;
+use crate::process::{FollowForkMode, ForkEvent, ProcessInfo};
+use crate::returnvalue::ReturnValue;
+use crate::session::{Session, SessionBreakpoint, SessionWatchpoint};
+use crate::sharedlib::{LibraryEvent, SharedLibrary};
+use crate::signal::{SignalPolicy, SignalPolicyTable};
+use crate::skiplist::{CodeOrigin, SkipList, SkipRule};
+use crate::syscall::{is_syscall_entry, parse_syscall, SyscallCatcher, SyscallEvent};
+use crate::thread::{is_thread_of, list_tids, Thread, ThreadState};
 use crate::ui::DebuggerUI;
 use crate::variable::{VariableExpression, VariableValue};
-use crate::{mem_read_word, mem_write_word, unwind, Addr, Register, Word};
+use crate::watchpoint::{WatchKind, WatchpointHit};
+use crate::{mem_write_word, Addr, Register, Word};
+
+/// How long to sleep between checks while the debuggee runs in the background
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
 
 // plugin stuff
 use crate::for_hooks; // does nothing without the feature
@@ -110,6 +130,7 @@ use steckrs::{PluginIDOwned, PluginManager};
 /// use coreminer::ui::cli::CliUi;
 /// use coreminer::errors::Result;
 /// use coreminer::feedback::Feedback;
+/// use coreminer::launch::LaunchOptions;
 /// use std::path::Path;
 /// use std::ffi::CString;
 ///
@@ -125,7 +146,7 @@ use steckrs::{PluginIDOwned, PluginManager};
 ///     let program_path = Path::new("./target/debug/my_program");
 ///     let args = vec![CString::new("my_program").unwrap(), CString::new("my_program").unwrap()];
 ///     // returns control shortly after forking off the debuggee as child process
-///     debugger.run(program_path, &args)?;
+///     debugger.run(program_path, &args, &LaunchOptions::default())?;
 ///
 ///     if let Feedback::Registers(regs) = debugger.dump_regs()? {
 ///         println!("rip is here: {}", regs.rip)
@@ -152,6 +173,18 @@ pub struct Debugger<'executable, UI: DebuggerUI> {
     stored_obj_data: Option<object::File<'executable>>,
     stored_obj_data_raw: Vec<u8>,
     last_signal: Option<Signal>,
+    follow_fork: FollowForkMode,
+    held_processes: Vec<Debuggee>,
+    vfork_parent: Option<Debuggee>,
+    early_fork_children: Vec<Pid>,
+    syscall_catcher: SyscallCatcher,
+    signal_policies: SignalPolicyTable,
+    skip_list: SkipList,
+    last_launch: Option<LaunchConfig>,
+    breakpoint_locations: BTreeMap<ModuleOffset, (BreakpointKind, BreakpointSettings)>,
+    watchpoint_locations: BTreeMap<ModuleOffset, (usize, WatchKind)>,
+    pending_breakpoints: Vec<String>,
+    interrupted_thread: Option<Pid>,
     #[cfg(feature = "plugins")]
     plugins: Arc<Mutex<PluginManager>>,
 }
@@ -193,6 +226,18 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
             stored_obj_data: None,
             stored_obj_data_raw: Vec::new(),
             last_signal: None,
+            follow_fork: FollowForkMode::default(),
+            held_processes: Vec::new(),
+            vfork_parent: None,
+            early_fork_children: Vec::new(),
+            syscall_catcher: SyscallCatcher::default(),
+            signal_policies: SignalPolicyTable::default(),
+            skip_list: SkipList::default(),
+            last_launch: None,
+            breakpoint_locations: BTreeMap::new(),
+            watchpoint_locations: BTreeMap::new(),
+            pending_breakpoints: Vec::new(),
+            interrupted_thread: None,
             #[cfg(feature = "plugins")]
             plugins: Arc::new(crate::plugins::default_plugin_manager().into()),
         })
@@ -201,12 +246,14 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     /// Launches a new debuggee process
     ///
     /// This function loads an executable, parses its debug information, forks a new process,
-    /// and sets up ptrace for debugging.
+    /// and sets up ptrace for debugging. It returns once the new process has executed the
+    /// executable and is stopped.
     ///
     /// # Parameters
     ///
     /// * `path` - Path to the executable
     /// * `arguments` - Command-line arguments for the executable
+    /// * `options` - Environment, working directory, redirections and ASLR of the debuggee
     ///
     /// # Returns
     ///
@@ -221,12 +268,19 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     /// - Debug information cannot be parsed
     /// - The process cannot be forked
     /// - ptrace cannot be initialized
+    /// - The [`LaunchOptions`] cannot be applied, or the executable cannot be executed, so that
+    ///   the new process exits right away
     ///
     /// # Panics
     ///
     /// This function will panic if the the argument vector cannot be built from the path and the
     /// arguments. This can happen if the path has unicode.
-    fn launch_debuggee(&mut self, path: impl AsRef<Path>, arguments: &[CString]) -> Result<()> {
+    fn launch_debuggee(
+        &mut self,
+        path: impl AsRef<Path>,
+        arguments: &[CString],
+        options: &LaunchOptions,
+    ) -> Result<()> {
         let path = path.as_ref();
         let path_as_cstring = CString::new(path.to_string_lossy().as_bytes())
             .expect("could not make argv from given path and args");
@@ -247,6 +301,11 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
 
         let dbginfo: CMDebugInfo = CMDebugInfo::build(executable_obj_data)?;
 
+        // the working directory might change, so a relative path would not work
+        let path = std::env::current_dir()?.join(path);
+        let cpath = CString::new(path.to_string_lossy().as_bytes())?;
+        let env = options.environment();
+
         let fork_res = unsafe { nix::unistd::fork() };
         match fork_res {
             Err(e) => {
@@ -255,17 +314,36 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
             }
             Ok(fr) => match fr {
                 nix::unistd::ForkResult::Parent { child: pid } => {
+                    // wait until the child has executed the executable, else we would read the
+                    // memory map of our own forked process
+                    let status = waitpid(pid, None)?;
+                    debug!("wait status after launch: {status:?}");
+                    if let WaitStatus::Exited(_, code) = status {
+                        let err = DebuggerError::LaunchFailed(code);
+                        error!("{err}");
+                        return Err(err);
+                    }
+                    ptrace::setoptions(pid, Self::ptrace_options())?;
+
                     let dbge = Debuggee::build(pid, &dbginfo, HashMap::new())?;
                     self.debuggee = Some(dbge);
                     Ok(())
                 }
                 nix::unistd::ForkResult::Child => {
-                    let cpath = CString::new(path.to_string_lossy().to_string().as_str())?;
+                    // never return from here, the caller would go on as a second debugger
                     trace!("CHILD: requested run with executable={cpath:?} and argv={argv:?}");
-                    ptrace::traceme()
-                        .inspect_err(|e| eprintln!("error while doing traceme: {e}"))?;
-                    execv(&cpath, &argv)?; // NOTE: unsure if args[0] is set to the executable
-                    unreachable!()
+                    if let Err(e) = options.apply() {
+                        eprintln!("could not apply the launch options: {e}");
+                        std::process::exit(127);
+                    }
+                    if let Err(e) = ptrace::traceme() {
+                        eprintln!("error while doing traceme: {e}");
+                        std::process::exit(127);
+                    }
+                    // NOTE: unsure if args[0] is set to the executable
+                    let e = execve(&cpath, &argv, &env).unwrap_err();
+                    eprintln!("could not execute '{}': {e}", path.to_string_lossy());
+                    std::process::exit(127);
                 }
             },
         }
@@ -276,6 +354,9 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     /// This function waits for signals from the debuggee, such as breakpoints, signals, or exits,
     /// and processes them appropriately.
     ///
+    /// If waiting is interrupted by a signal to the debugger, like `SIGINT` from Ctrl-C in the
+    /// terminal, the debuggee is stopped.
+    ///
     /// # Returns
     ///
     /// * `Ok(Feedback)` - The result of the wait operation
@@ -311,228 +392,1235 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     ///
     /// # }}
     /// ```
-    // BUG: this seems to eat signals, the debuggee does not get them. This is especially bad for
-    // SIGTERM #43
     pub fn wait_signal(&mut self) -> Result<Feedback> {
-        trace!("new wait signal iteration");
-        match self.wait(&[])? {
-            WaitStatus::Exited(_, exit_code) => Ok(Feedback::Exit(exit_code)),
-            WaitStatus::Signaled(_, signal, _) => {
-                info!("Debuggee terminated by signal: {}", signal);
-                Ok(Feedback::Exit(-1))
-            }
-            wait_status => {
-                // Get and handle other signals as before
-                let siginfo = ptrace::getsiginfo(
-                    self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?.pid,
-                )?;
-                let sig = Signal::try_from(siginfo.si_signo)?;
-                debug!("wait status: {wait_status:?}");
-
-                for_hooks!(
-                    for hook[EPreSignalHandler] in self {
-                        self.hook_feedback_loop(hook.name(), |f| {
-                            hook.inner_mut().pre_handle_signal(f, &siginfo, &sig, &wait_status)
-                        })?;
-                    }
-                );
-
-                match sig {
-                    Signal::SIGTRAP => {
-                        self.handle_sigtrap(sig, siginfo)?;
-                        Ok(Feedback::Ok)
-                    }
-                    Signal::SIGSEGV
-                    | Signal::SIGINT
-                    | Signal::SIGPIPE
-                    | Signal::SIGSTOP
-                    | Signal::SIGWINCH
-                    | Signal::SIGTERM
-                    | Signal::SIGILL => {
-                        self.handle_important_signal(sig, siginfo)?;
-                        Ok(Feedback::Ok)
-                    }
-                    _ => {
-                        self.handle_other_signal(sig, siginfo)?;
-                        Ok(Feedback::Ok)
+        let mut interrupted = false;
+        loop {
+            trace!("new wait signal iteration");
+            let wait_status = match self.wait(&[]) {
+                Err(DebuggerError::Os(Errno::EINTR)) => {
+                    // the debugger got a signal, like SIGINT from Ctrl-C in the terminal. If the
+                    // debuggee is in the foreground, it got the SIGINT too and will stop for it.
+                    // If it does not, the next interrupt stops it for sure.
+                    if !interrupted && self.debuggee_in_foreground() {
+                        info!("interrupted, waiting for the debuggee to stop");
+                    } else {
+                        info!("interrupted, stopping the debuggee");
+                        self.request_interrupt()?;
                     }
+                    interrupted = true;
+                    continue;
                 }
+                other => other?,
+            };
+            if let Some(feedback) = self.handle_wait_status(wait_status)? {
+                self.interrupted_thread = None;
+                return Ok(feedback);
+            }
+        }
+    }
+
+    /// Checks if the debuggee stopped, without blocking
+    ///
+    /// This is used while the debuggee runs in the background after [`Self::cont_async`]. All
+    /// wait statuses that are already there are processed like in [`Self::wait_signal`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Feedback))` - If the debuggee stopped or exited
+    /// * `Ok(None)` - If the debuggee is still running
+    /// * `Err(DebuggerError)` - If there was an error during waiting
+    ///
+    /// # Errors
+    ///
+    /// This function can fail for the same reasons as [`Self::wait_signal`].
+    pub fn poll_signal(&mut self) -> Result<Option<Feedback>> {
+        loop {
+            let wait_status = self.wait(&[WaitPidFlag::WNOHANG])?;
+            if wait_status == WaitStatus::StillAlive {
+                return Ok(None);
+            }
+            if let Some(feedback) = self.handle_wait_status(wait_status)? {
+                self.interrupted_thread = None;
+                return Ok(Some(feedback));
             }
         }
     }
 
-    /// Low-level wait for a change in the debuggee's state
+    /// Processes a single wait status of the debuggee
     ///
     /// # Parameters
     ///
-    /// * `options` - Options to pass to waitpid, usually `&[]`
+    /// * `wait_status` - The wait status to process
     ///
     /// # Returns
     ///
-    /// * `Ok(WaitStatus)` - The status of the wait operation
-    /// * `Err(DebuggerError)` - If there was an error during waiting
+    /// * `Ok(Some(Feedback))` - If the debuggee stopped or exited
+    /// * `Ok(None)` - If the debuggee keeps running
+    /// * `Err(DebuggerError)` - If the wait status could not be processed
     ///
     /// # Errors
     ///
     /// This function can fail if:
     /// - The debuggee is not running
-    /// - waitpid fails
+    /// - Signal information cannot be retrieved
+    /// - Threads cannot be stopped or resumed
+    fn handle_wait_status(&mut self, wait_status: WaitStatus) -> Result<Option<Feedback>> {
+        let pid = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?.pid;
+        if self.foreign_status(pid, wait_status) {
+            return Ok(None);
+        }
+        match wait_status {
+            WaitStatus::Exited(tid, exit_code) if tid == pid => {
+                self.release_vfork_parent()?;
+                return Ok(Some(Feedback::Exit(exit_code)));
+            }
+            WaitStatus::Signaled(tid, signal, _) if tid == pid => {
+                info!("Debuggee terminated by signal: {}", signal);
+                self.release_vfork_parent()?;
+                return Ok(Some(Feedback::Exit(-1)));
+            }
+            WaitStatus::Exited(tid, _) | WaitStatus::Signaled(tid, _, _) => {
+                if self.thread_exited(tid)? {
+                    return Ok(Some(Feedback::Ok));
+                }
+            }
+            WaitStatus::PtraceEvent(tid, _, nix::libc::PTRACE_EVENT_CLONE) => {
+                self.handle_clone(tid)?;
+            }
+            WaitStatus::PtraceEvent(
+                tid,
+                _,
+                event @ (nix::libc::PTRACE_EVENT_FORK | nix::libc::PTRACE_EVENT_VFORK),
+            ) => {
+                if let Some(exit) = self.stop_all_at(tid)? {
+                    return Ok(Some(exit));
+                }
+                return self
+                    .handle_fork(tid, event == nix::libc::PTRACE_EVENT_VFORK)
+                    .map(Some);
+            }
+            WaitStatus::PtraceEvent(tid, _, nix::libc::PTRACE_EVENT_VFORK_DONE) => {
+                self.handle_vfork_done(tid)?;
+            }
+            WaitStatus::PtraceEvent(tid, _, nix::libc::PTRACE_EVENT_EXEC) => {
+                return self.handle_exec(tid).map(Some);
+            }
+            WaitStatus::PtraceSyscall(tid) => {
+                return self.handle_syscall_stop(tid);
+            }
+            WaitStatus::Stopped(tid, Signal::SIGSTOP) if self.interrupted_thread == Some(tid) => {
+                return self.handle_interrupt(tid).map(Some);
+            }
+            WaitStatus::Stopped(tid, Signal::SIGSTOP) if self.swallow_sigstop(tid)? => (),
+            WaitStatus::Stopped(tid, sig)
+                if sig != Signal::SIGTRAP
+                    && !self.signal_policies.get(sig).stop
+                    && self.guarded_fault(&ptrace::getsiginfo(tid)?).is_none() =>
+            {
+                self.pass_signal(tid, sig)?;
+            }
+            wait_status => {
+                let Some(tid) = wait_status.pid() else {
+                    warn!("wait status without a thread: {wait_status:?}");
+                    return Ok(None);
+                };
+                return self.handle_stop(tid, wait_status);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Checks if the debuggee is in the foreground process group of the terminal
     ///
-    /// # Examples
+    /// If it is, it receives the `SIGINT` when Ctrl-C is pressed in the terminal, just like the
+    /// debugger.
+    fn debuggee_in_foreground(&self) -> bool {
+        let Some(dbge) = self.debuggee.as_ref() else {
+            return false;
+        };
+        // SAFETY: tcgetpgrp does not touch any memory, it fails if stdin is not a terminal
+        let foreground = unsafe { nix::libc::tcgetpgrp(nix::libc::STDIN_FILENO) };
+        nix::unistd::getpgid(Some(dbge.pid)).is_ok_and(|pgid| pgid.as_raw() == foreground)
+    }
+
+    /// Sends a `SIGSTOP` to a running thread of the debuggee, so that the debuggee stops
     ///
+    /// `PTRACE_INTERRUPT` only works for threads that were attached with `PTRACE_SEIZE`, which
+    /// the debugger does not use, so a `SIGSTOP` is sent instead. The `SIGSTOP` is reported by
+    /// [`Self::wait_signal`] or [`Self::poll_signal`] as a stop of the debuggee. If the debuggee
+    /// stops for another reason first, the `SIGSTOP` is consumed later, like when stopping all
+    /// threads.
     ///
-    /// ```no_run
-    /// #[cfg(feature = "cli")]
-    /// # mod featguard { fn _do_thing() {
-    /// # use coreminer::debugger::Debugger;
-    /// # use coreminer::ui::cli::CliUi;
-    /// # use nix::sys::wait::{WaitPidFlag, WaitStatus};
-    /// #
-    /// # let ui = CliUi::build(None).unwrap();
-    /// # let debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
-    /// #
-    /// // Wait for any status change without options
-    /// let status = debugger.wait(&[]).unwrap();
+    /// # Errors
     ///
-    /// // Wait with the WNOHANG option (non-blocking)
-    /// let status = debugger.wait(&[WaitPidFlag::WNOHANG]).unwrap();
+    /// This function fails if the debuggee is not running or no thread could be stopped.
+    fn request_interrupt(&mut self) -> Result<()> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        let pid = dbge.pid;
+        let selected = dbge.selected_thread;
+        // prefer the selected thread, any other running thread will do too
+        let mut candidates: Vec<&mut Thread> = dbge
+            .threads
+            .values_mut()
+            .filter(|t| t.is_running())
+            .collect();
+        candidates.sort_by_key(|t| t.tid != selected);
+        for thread in candidates {
+            match thread.request_stop(pid) {
+                Ok(()) => {
+                    debug!("interrupting thread {}", thread.tid);
+                    self.interrupted_thread = Some(thread.tid);
+                    return Ok(());
+                }
+                Err(e) => warn!("could not interrupt thread {}: {e}", thread.tid),
+            }
+        }
+        Err(DebuggerError::DebuggeeNotRunning)
+    }
+
+    /// Handles the `SIGSTOP` of an interrupt, stopping all threads of the debuggee
     ///
-    /// match status {
-    ///     WaitStatus::Exited(_, code) => println!("Process exited with code {}", code),
-    ///     WaitStatus::Stopped(_, signal) => println!("Process stopped by signal {:?}", signal),
-    ///     _ => println!("Other status change"),
-    /// }
+    /// # Parameters
     ///
-    /// # }}
-    /// ```
-    pub fn wait(&self, options: &[WaitPidFlag]) -> Result<WaitStatus> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-        let mut flags = WaitPidFlag::empty();
-        trace!("wait flags: {flags:?}");
-        for f in options {
-            flags |= *f;
+    /// * `tid` - The thread ID of the interrupted thread
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the other threads cannot be stopped.
+    fn handle_interrupt(&mut self, tid: Pid) -> Result<Feedback> {
+        info!("thread {tid} was interrupted");
+        self.mark_thread_stopped(tid)?.expect_sigstop = false;
+        self.interrupted_thread = None;
+        self.last_signal = None;
+        if let Some(exit) = self.stop_all_at(tid)? {
+            return Ok(exit);
         }
-        Ok(waitpid(
-            dbge.pid,
-            if flags.is_empty() { None } else { Some(flags) },
-        )?)
+        Ok(Feedback::Ok)
     }
 
-    /// Runs the main debugger loop
+    /// Handles a thread of the debuggee stopping for a signal or event
     ///
-    /// This function forms the main execution loop of the debugger, processing
-    /// UI commands and executing corresponding debugger actions until the user
-    /// requests to quit.
+    /// The stopped thread becomes the selected thread and all other threads are stopped too,
+    /// before the signal is processed.
+    ///
+    /// If the thread hit a [`Breakpoint`] whose [`Condition`] is false, the debuggee is resumed
+    /// right away. So is a thread that accessed unwatched memory on a page protected for a
+    /// software [`Watchpoint`](crate::watchpoint::Watchpoint), or that hit the internal
+    /// breakpoint for [shared libraries](crate::sharedlib), unless it was stepping.
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread that stopped
+    /// * `wait_status` - The wait status reported for that thread
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - If the debugger loop completed successfully
-    /// * `Err(DebuggerError)` - If there was an error during execution
+    /// * `Ok(Some(Feedback))` - If the debuggee stopped or exited
+    /// * `Ok(None)` - If the debuggee was resumed
     ///
     /// # Errors
     ///
     /// This function can fail if:
-    /// - The debuggee already exists but can't be waited for.
+    /// - The debuggee is not running
+    /// - Signal information cannot be retrieved
+    /// - The other threads cannot be stopped
+    fn handle_stop(&mut self, tid: Pid, wait_status: WaitStatus) -> Result<Option<Feedback>> {
+        let stepping = self
+            .debuggee
+            .as_ref()
+            .and_then(|d| d.thread(tid))
+            .is_some_and(|t| t.state() == ThreadState::Stepping);
+        if let Some(exit) = self.stop_all_at(tid)? {
+            return Ok(Some(exit));
+        }
+
+        let siginfo = ptrace::getsiginfo(tid)?;
+        let sig = Signal::try_from(siginfo.si_signo)?;
+        debug!("wait status: {wait_status:?}");
+
+        for_hooks!(
+            for hook[EPreSignalHandler] in self {
+                self.hook_feedback_loop(hook.name(), |f| {
+                    hook.inner_mut().pre_handle_signal(f, &siginfo, &sig, &wait_status)
+                })?;
+            }
+        );
+
+        if sig == Signal::SIGTRAP {
+            self.handle_sigtrap(sig, siginfo)?;
+            if matches!(siginfo.si_code, TRAP_HWBKPT | TRAP_TRACE) {
+                let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+                if let Some(hit) = dbge.watchpoint_hit(tid)? {
+                    info!("thread {tid} hit the watchpoint at {}", hit.addr);
+                    return Ok(Some(Feedback::Watchpoint(hit)));
+                }
+            }
+            if matches!(siginfo.si_code, SI_KERNEL | TRAP_BRKPT)
+                && self.handle_loader_breakpoint(tid)?
+            {
+                return if stepping {
+                    Ok(Some(Feedback::Ok))
+                } else {
+                    self.resume()
+                };
+            }
+            if matches!(siginfo.si_code, SI_KERNEL | TRAP_BRKPT | TRAP_HWBKPT)
+                && !self.should_stop_at_bp(siginfo.si_code == TRAP_HWBKPT)?
+            {
+                return self.resume();
+            }
+        } else if self.signal_policies.get(sig).print || self.guarded_fault(&siginfo).is_some() {
+            if let Some(feedback) = self.handle_important_signal(sig, siginfo)? {
+                return Ok(Some(feedback));
+            }
+            // the emulated access finished the step
+            return if stepping {
+                Ok(Some(Feedback::Ok))
+            } else {
+                self.resume()
+            };
+        } else {
+            self.handle_other_signal(sig, siginfo)?;
+        }
+        Ok(Some(Feedback::Ok))
+    }
+
+    /// Marks a thread as stopped and selects it, then stops all other threads of the debuggee
     ///
-    /// # Examples
+    /// # Parameters
     ///
-    /// ```no_run
-    /// #[cfg(feature = "cli")]
-    /// # mod featguard { fn _do_thing() {
-    /// # use coreminer::debugger::Debugger;
-    /// # use coreminer::ui::cli::CliUi;
-    /// #
-    /// let ui = CliUi::build(None).unwrap();
-    /// let mut debugger = Debugger::build(ui).unwrap();
+    /// * `tid` - The thread ID of the thread that stopped
     ///
-    /// // Start the main debugger loop
-    /// // This will run until the ui exits
-    /// debugger.run_debugger().unwrap();
+    /// # Returns
     ///
-    /// debugger.cleanup().unwrap();
+    /// The same as [`Self::stop_all_threads`].
     ///
-    /// # }}
-    /// ```
-    pub fn run_debugger(&mut self) -> Result<()> {
-        if self.debuggee.as_ref().is_some() {
-            self.wait_signal()?; // wait until the debuggee is stopped
-        } else {
-            info!("debuggee not yet launched");
+    /// # Errors
+    ///
+    /// This function fails if the other threads cannot be stopped.
+    fn stop_all_at(&mut self, tid: Pid) -> Result<Option<Feedback>> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        dbge.threads
+            .entry(tid)
+            .or_insert_with(|| Thread::new(tid, ThreadState::Stopped))
+            .state = ThreadState::Stopped;
+        if dbge.selected_thread != tid {
+            debug!("switching to thread {tid}");
+            dbge.selected_thread = tid;
         }
+        self.stop_all_threads(tid)
+    }
 
-        let mut feedback: Feedback = Feedback::Ok;
-        loop {
-            let ui_res = self.ui.process(feedback);
-            feedback = {
-                match ui_res {
-                    Err(e) => {
-                        error!("{e}");
-                        return Err(e);
-                    }
-                    Ok(s) => match self.process_status(&s) {
-                        Ok(Feedback::Internal(InternalFeedback::Quit)) => break,
-                        other => other,
-                    },
+    /// Stops all threads of the debuggee except the one that caused the current stop
+    ///
+    /// This implements the all-stop semantics of the debugger. Each running thread gets a
+    /// `SIGSTOP` and is waited for. Threads that report something else while being stopped are
+    /// handled like this:
+    ///
+    /// - A thread that hit a [`Breakpoint`] is moved back onto it, so that it hits it again when
+    ///   resumed
+    /// - Other signals are stored in the thread and delivered when it is resumed, if their
+    ///   [`SignalPolicy`] says so
+    /// - New threads are added to the thread table, exited threads are removed
+    /// - A `fork` or `vfork` is stored in the thread and handled before it is resumed
+    /// - A caught syscall stop is stored in the thread and reported before it is resumed
+    /// - An `execve` is handled right away, as it ends all other threads
+    ///
+    /// # Parameters
+    ///
+    /// * `event_tid` - The thread ID of the thread that caused the stop
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(None)` - If all threads are stopped now
+    /// * `Ok(Some(Feedback::Exit))` - If the debuggee exited while its threads were being stopped
+    /// * `Ok(Some(Feedback::Exec))` - If the debuggee executed a new program while its threads
+    ///   were being stopped
+    /// * `Err(DebuggerError)` - If the threads could not be stopped
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - waitpid fails
+    /// - [Register] access fails
+    fn stop_all_threads(&mut self, event_tid: Pid) -> Result<Option<Feedback>> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        let pid = dbge.pid;
+        for thread in dbge.threads.values_mut() {
+            if thread.tid != event_tid && thread.is_running() {
+                trace!("stopping thread {}", thread.tid);
+                if let Err(e) = thread.request_stop(pid) {
+                    warn!("could not stop thread {}: {e}", thread.tid);
                 }
             }
-            .into();
+        }
 
-            // Clean up if process exited
-            if let Feedback::Exit(_) = feedback {
-                self.debuggee = None;
+        while self
+            .debuggee
+            .as_ref()
+            .ok_or(DebuggerError::NoDebugee)?
+            .threads()
+            .any(Thread::is_running)
+        {
+            let wait_status = self.wait(&[])?;
+            trace!("wait status while stopping all threads: {wait_status:?}");
+            if self.foreign_status(pid, wait_status) {
+                continue;
+            }
+            match wait_status {
+                WaitStatus::Exited(tid, exit_code) if tid == pid => {
+                    self.release_vfork_parent()?;
+                    return Ok(Some(Feedback::Exit(exit_code)));
+                }
+                WaitStatus::Signaled(tid, signal, _) if tid == pid => {
+                    info!("Debuggee terminated by signal: {}", signal);
+                    self.release_vfork_parent()?;
+                    return Ok(Some(Feedback::Exit(-1)));
+                }
+                WaitStatus::Exited(tid, _) | WaitStatus::Signaled(tid, _, _) => {
+                    self.thread_exited(tid)?;
+                }
+                WaitStatus::PtraceEvent(tid, _, nix::libc::PTRACE_EVENT_EXEC) => {
+                    return Ok(Some(self.handle_exec(tid)?));
+                }
+                WaitStatus::PtraceEvent(tid, _, event) => {
+                    match event {
+                        nix::libc::PTRACE_EVENT_CLONE => {
+                            self.register_new_thread(tid)?;
+                        }
+                        nix::libc::PTRACE_EVENT_VFORK_DONE => self
+                            .debuggee
+                            .as_mut()
+                            .ok_or(DebuggerError::NoDebugee)?
+                            .resume_breakpoints(tid)?,
+                        nix::libc::PTRACE_EVENT_FORK | nix::libc::PTRACE_EVENT_VFORK => {
+                            debug!("thread {tid} forked while stopping, handling it later");
+                            self.mark_thread_stopped(tid)?.pending_event = Some(event);
+                        }
+                        _ => (),
+                    }
+                    self.mark_thread_stopped(tid)?;
+                }
+                WaitStatus::Stopped(tid, Signal::SIGSTOP)
+                    if self
+                        .debuggee
+                        .as_ref()
+                        .and_then(|d| d.thread(tid))
+                        .map_or(true, |t| t.expect_sigstop) =>
+                {
+                    let thread = self.mark_thread_stopped(tid)?;
+                    thread.expect_sigstop = false;
+                }
+                WaitStatus::PtraceSyscall(tid) => {
+                    let event = self.decode_syscall_stop(tid)?;
+                    let caught = self.syscall_catcher.should_stop(event.number);
+                    let thread = self.mark_thread_stopped(tid)?;
+                    if caught {
+                        debug!("thread {tid} stopped at a syscall while stopping, reporting it later: {event}");
+                        thread.pending_syscall = Some(event);
+                    }
+                }
+                WaitStatus::Stopped(tid, Signal::SIGTRAP) => {
+                    self.rewind_bp_hit(tid)?;
+                    let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+                    let hit = dbge.watchpoint_hit(tid)?;
+                    self.mark_thread_stopped(tid)?.pending_watchpoint = hit;
+                }
+                WaitStatus::Stopped(tid, sig) => {
+                    // an access to a protected page faults again when the thread is resumed
+                    let pass = self.signal_policies.get(sig).pass
+                        && self.guarded_fault(&ptrace::getsiginfo(tid)?).is_none();
+                    debug!("thread {tid} received {sig} while stopping, pass it later: {pass}");
+                    self.mark_thread_stopped(tid)?.pending_signal = pass.then_some(sig);
+                }
+                other => debug!("ignoring wait status while stopping all threads: {other:?}"),
             }
         }
 
-        Ok(())
+        Ok(None)
     }
 
-    /// Process a [`Status`] by executing the specified action.
+    /// Undoes a breakpoint hit of a thread that stopped while all threads were being stopped
     ///
-    /// This function takes a [`Status`] and has the debugger perform actions to generate
-    /// a [`Feedback`].
+    /// The thread is set up to hit the breakpoint again when it is resumed, so that the hit is
+    /// reported then. After a software breakpoint, the instruction pointer is moved back onto
+    /// the `INT3`. At a hardware breakpoint, the resume flag is cleared.
     ///
-    /// For example, a [`Status`] might request that the [`Debugger`] should read some [`Word`]
-    /// from the memory of the [`Debuggee`] with [`Status::ReadMem`].
+    /// # Errors
     ///
-    /// This function will then match
-    /// the [`Status`] to the fitting implementation function, [`Self::read_mem`] for this example,
-    /// execute it, and return the [`Feedback`] ([`Feedback::Word`]).
+    /// This function fails if the registers of the thread cannot be read or written.
+    fn rewind_bp_hit(&self, tid: Pid) -> Result<()> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let maybe_bp_addr: Addr = Addr::from(crate::get_reg(tid, Register::rip)?) - 1;
+        if dbge
+            .breakpoints
+            .get(&maybe_bp_addr)
+            .is_some_and(Breakpoint::is_enabled)
+        {
+            debug!(
+                "thread {tid} hit the breakpoint at {maybe_bp_addr} while stopping, moving it back"
+            );
+            crate::set_reg(tid, Register::rip, maybe_bp_addr.into())
+        } else {
+            dbge.set_resume_flag(tid, false)
+        }
+    }
+
+    /// Marks a thread as stopped, adding it to the thread table if it is not known yet
     ///
-    /// If the action taken fails, a [`Err`] variant will be returned instead. Since [`Feedback`]
-    /// can be constructed from a [`DebuggerError`], you can wrap the error in a [`Feedback`] and
-    /// send this to the [`DebuggerUI`] or a [`Plugin`](steckrs::Plugin) with [`Self::hook_feedback_loop`].
+    /// # Errors
     ///
-    /// # Parameters
+    /// This function fails if the debuggee is not running.
+    fn mark_thread_stopped(&mut self, tid: Pid) -> Result<&mut Thread> {
+        let thread = self
+            .debuggee
+            .as_mut()
+            .ok_or(DebuggerError::NoDebugee)?
+            .threads
+            .entry(tid)
+            .or_insert_with(|| {
+                info!("new thread {tid}");
+                Thread::new(tid, ThreadState::Stopped)
+            });
+        thread.state = ThreadState::Stopped;
+        Ok(thread)
+    }
+
+    /// Removes an exited thread from the thread table
     ///
-    /// * `status` - A [`Status`] to be processed
+    /// If the selected thread exited, the thread group leader is selected instead.
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback)` - The result of the action detailed by the `status`
-    /// * `Err(DebuggerError)` - If there was an error performing the requested action
+    /// * `Ok(true)` - If no thread of the debuggee is running anymore, so nothing is left to wait for
+    /// * `Ok(false)` - If other threads are still running
     ///
     /// # Errors
     ///
-    /// This function will return an error if the taken action for that [`Status`] fails.
+    /// This function fails if the debuggee is not running.
+    fn thread_exited(&mut self, tid: Pid) -> Result<bool> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        info!("thread {tid} exited");
+        dbge.threads.remove(&tid);
+        if dbge.selected_thread == tid {
+            dbge.selected_thread = dbge.pid;
+        }
+        Ok(!dbge.threads().any(Thread::is_running))
+    }
+
+    /// Adds a thread that was just created by `clone` to the thread table
     ///
-    /// # Examples
+    /// The new thread starts with a `SIGSTOP`, which is waited for here unless it was already
+    /// reported. The new thread is left stopped.
     ///
-    /// ```no_run
-    /// #[cfg(feature = "cli")]
-    /// # mod featguard { fn _do_thing() {
-    /// # use coreminer::debugger::Debugger;
-    /// # use coreminer::ui::cli::CliUi;
-    /// # use coreminer::addr::Addr;
-    /// # use coreminer::feedback::{Feedback, Status};
-    /// #
-    /// let ui = CliUi::build(None).unwrap();
-    /// let mut debugger = Debugger::build(ui).unwrap();
+    /// # Parameters
     ///
-    /// let status = Status::ReadMem(Addr::from(98421479usize));
-    /// let feedback: Feedback = debugger.process_status(&status).unwrap();
+    /// * `parent` - The thread ID of the thread that reported the `PTRACE_EVENT_CLONE`
     ///
-    /// if let Feedback::Word(w) = feedback {
+    /// # Returns
+    ///
+    /// * `Ok(Pid)` - The thread ID of the new thread
+    /// * `Err(DebuggerError)` - If the new thread could not be added
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The thread ID of the new thread cannot be retrieved
+    /// - waitpid fails
+    fn register_new_thread(&mut self, parent: Pid) -> Result<Pid> {
+        let new_tid = Pid::from_raw(ptrace::getevent(parent)? as i32);
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        if dbge.thread(new_tid).is_none() {
+            let status = waitpid(new_tid, Some(WaitPidFlag::__WALL))?;
+            trace!("initial wait status of new thread {new_tid}: {status:?}");
+            info!("new thread {new_tid}");
+            dbge.threads
+                .insert(new_tid, Thread::new(new_tid, ThreadState::Stopped));
+        }
+        Ok(new_tid)
+    }
+
+    /// Handles a `PTRACE_EVENT_CLONE` while the debuggee is running
+    ///
+    /// The new thread is added to the thread table. Both threads are then resumed the same way
+    /// the parent thread was resumed, meaning a new thread is kept stopped while stepping.
+    ///
+    /// # Parameters
+    ///
+    /// * `parent` - The thread ID of the thread that reported the event
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The new thread cannot be added
+    /// - One of the threads cannot be resumed
+    fn handle_clone(&mut self, parent: Pid) -> Result<()> {
+        let new_tid = self.register_new_thread(parent)?;
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let parent_state = dbge
+            .thread(parent)
+            .map_or(ThreadState::Running, Thread::state);
+
+        if parent_state == ThreadState::Running {
+            self.resume_as_before(new_tid, ThreadState::Running, None)?;
+        }
+        self.resume_as_before(parent, parent_state, None)
+    }
+
+    /// Resumes a thread the same way it was resumed before it reported an event
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread
+    /// * `state` - How the thread was resumed before, nothing is done for
+    ///   [`ThreadState::Stopped`]
+    /// * `sig` - A signal to deliver to the thread
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The thread cannot be resumed
+    fn resume_as_before(
+        &mut self,
+        tid: Pid,
+        state: ThreadState,
+        sig: Option<Signal>,
+    ) -> Result<()> {
+        let syscalls = self.syscall_catcher.is_active();
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        match (state, dbge.threads.get_mut(&tid)) {
+            (ThreadState::Running, Some(thread)) => thread.resume(sig, syscalls)?,
+            (ThreadState::Running, None) => ptrace::cont(tid, sig)?,
+            (ThreadState::Stepping, _) => ptrace::step(tid, sig)?,
+            (ThreadState::Stopped, _) => (),
+        }
+        Ok(())
+    }
+
+    /// Consumes a `SIGSTOP` that was caused by the debugger instead of the debuggee
+    ///
+    /// These are left over from stopping all threads, or the initial stop of a new thread whose
+    /// `PTRACE_EVENT_CLONE` was not reported yet. A thread that was resumed is resumed again
+    /// the same way.
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread that reported the `SIGSTOP`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - If the `SIGSTOP` was consumed
+    /// * `Ok(false)` - If the `SIGSTOP` did not come from the debugger and needs to be handled
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The thread cannot be resumed
+    fn swallow_sigstop(&mut self, tid: Pid) -> Result<bool> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        let Some(thread) = dbge.threads.get_mut(&tid) else {
+            info!("new thread {tid}");
+            dbge.threads
+                .insert(tid, Thread::new(tid, ThreadState::Stopped));
+            return Ok(true);
+        };
+        if !thread.expect_sigstop {
+            return Ok(false);
+        }
+        trace!("consuming SIGSTOP of thread {tid}");
+        thread.expect_sigstop = false;
+        let state = thread.state;
+        self.resume_as_before(tid, state, None)?;
+        Ok(true)
+    }
+
+    /// Handles a thread stopping at a syscall entry or exit
+    ///
+    /// If the debuggee should stop at this syscall, all threads are stopped and the syscall is
+    /// reported. Otherwise, the thread is resumed right away.
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread that reported the syscall stop
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Feedback::Syscall))` - If the debuggee stopped at the syscall
+    /// * `Ok(Some(Feedback::Exit))` - If the debuggee exited while its threads were being stopped
+    /// * `Ok(None)` - If the thread was resumed
+    /// * `Err(DebuggerError)` - If the syscall stop could not be handled
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - [Register] access fails
+    /// - The thread cannot be resumed or the other threads cannot be stopped
+    fn handle_syscall_stop(&mut self, tid: Pid) -> Result<Option<Feedback>> {
+        let event = self.decode_syscall_stop(tid)?;
+        if !self.syscall_catcher.should_stop(event.number) {
+            trace!("not stopping at syscall: {event}");
+            self.resume_as_before(tid, ThreadState::Running, None)?;
+            return Ok(None);
+        }
+
+        debug!("stopping at syscall: {event}");
+        if let Some(exit) = self.stop_all_at(tid)? {
+            return Ok(Some(exit));
+        }
+        Ok(Some(Feedback::Syscall(event)))
+    }
+
+    /// Passes a signal on to a thread without stopping the debuggee, as its [`SignalPolicy`] says
+    ///
+    /// The thread is resumed the same way as before, with the signal if the policy says to pass
+    /// it.
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread that received the signal
+    /// * `sig` - The signal
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if the debuggee is not running or the thread cannot be resumed.
+    fn pass_signal(&mut self, tid: Pid, sig: Signal) -> Result<()> {
+        let policy = self.signal_policies.get(sig);
+        if policy.print {
+            info!("thread {tid} received {sig}, pass: {}", policy.pass);
+        } else {
+            trace!("thread {tid} received {sig}, pass: {}", policy.pass);
+        }
+        let state = self
+            .debuggee
+            .as_ref()
+            .ok_or(DebuggerError::NoDebugee)?
+            .thread(tid)
+            .map_or(ThreadState::Running, Thread::state);
+        self.resume_as_before(tid, state, policy.pass.then_some(sig))
+    }
+
+    /// Decodes the syscall stop of a thread and remembers if it entered or left the syscall
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread that reported the syscall stop
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if the debuggee is not running or [Register] access fails.
+    fn decode_syscall_stop(&mut self, tid: Pid) -> Result<SyscallEvent> {
+        let regs = ptrace::getregs(tid)?;
+        let thread = self
+            .debuggee
+            .as_mut()
+            .ok_or(DebuggerError::NoDebugee)?
+            .threads
+            .entry(tid)
+            .or_insert_with(|| Thread::new(tid, ThreadState::Running));
+        let entry = is_syscall_entry(thread.in_syscall, &regs);
+        thread.in_syscall = entry;
+        Ok(SyscallEvent::from_regs(tid, &regs.into(), entry))
+    }
+
+    /// Handles a wait status that does not belong to a thread of the debuggee
+    ///
+    /// Such statuses come from other processes under the control of the debugger:
+    ///
+    /// - A new process that reported its initial stop before its parent reported the `fork`
+    /// - A held process that exited
+    ///
+    /// # Parameters
+    ///
+    /// * `pid` - The process ID of the debuggee
+    /// * `wait_status` - The wait status to check
+    ///
+    /// # Returns
+    ///
+    /// * `true` - If the status did not belong to the debuggee and was handled
+    /// * `false` - If the status belongs to a thread of the debuggee
+    fn foreign_status(&mut self, pid: Pid, wait_status: WaitStatus) -> bool {
+        let Some(tid) = wait_status.pid() else {
+            return false;
+        };
+        if self
+            .debuggee
+            .as_ref()
+            .is_some_and(|d| d.thread(tid).is_some())
+            || is_thread_of(pid, tid)
+        {
+            return false;
+        }
+
+        match wait_status {
+            WaitStatus::Stopped(_, Signal::SIGSTOP) => {
+                debug!("process {tid} stopped before its creation was reported");
+                self.early_fork_children.push(tid);
+            }
+            WaitStatus::Exited(..) | WaitStatus::Signaled(..) => {
+                info!("held process {tid} exited");
+                self.held_processes.retain(|d| d.pid != tid);
+            }
+            other => debug!("ignoring wait status of process {tid}: {other:?}"),
+        }
+        true
+    }
+
+    /// Follows the debuggee through a `fork` or `vfork`
+    ///
+    /// The new process is traced already, as the ptrace options are inherited. Depending on the
+    /// [`FollowForkMode`], one of the processes is detached or held:
+    ///
+    /// - [`FollowForkMode::Parent`]: The child is detached after its breakpoints were removed
+    /// - [`FollowForkMode::Child`]: The parent is detached after its breakpoints were removed,
+    ///   and the child becomes the debuggee
+    /// - [`FollowForkMode::Both`]: The child is held stopped, see [`Self::select_process`]
+    ///
+    /// A `vfork` child shares the memory of its parent until it executes a new program or exits.
+    /// While following the parent, its breakpoints are suspended until then. While following the
+    /// child, the parent is only detached once it has its own memory again.
+    ///
+    /// All threads of the debuggee need to be stopped already.
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread that forked
+    /// * `vfork` - Whether the new process was created with `vfork`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Fork)` - Information about the new process
+    /// * `Err(DebuggerError)` - If the new process could not be followed
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The process ID of the new process cannot be retrieved
+    /// - waitpid fails
+    /// - A [`Breakpoint`] cannot be removed or a process cannot be detached
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the debuggee disappears while switching to the child, which
+    /// should not happen.
+    fn handle_fork(&mut self, tid: Pid, vfork: bool) -> Result<Feedback> {
+        let child = Pid::from_raw(ptrace::getevent(tid)? as i32);
+        if let Some(idx) = self.early_fork_children.iter().position(|c| *c == child) {
+            self.early_fork_children.swap_remove(idx);
+        } else {
+            let status = waitpid(child, Some(WaitPidFlag::__WALL))?;
+            trace!("initial wait status of new process {child}: {status:?}");
+        }
+
+        let mode = self.follow_fork;
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        let parent = dbge.pid;
+        info!("process {parent} created process {child}, following {mode}");
+        let mut child_dbge = dbge.fork_child(child);
+        if !vfork {
+            // the child has its own copy of the protected pages
+            child_dbge.unguard_pages(child)?;
+        }
+
+        match mode {
+            FollowForkMode::Parent | FollowForkMode::Both => {
+                if vfork {
+                    // the breakpoints are in the memory of the parent, which the child uses too
+                    child_dbge.forget_breakpoints();
+                    dbge.suspend_breakpoints(tid)?;
+                }
+                if mode == FollowForkMode::Both {
+                    self.held_processes.push(child_dbge);
+                } else {
+                    child_dbge.detach(None)?;
+                }
+            }
+            FollowForkMode::Child => {
+                self.release_vfork_parent()?;
+                let mut parent_dbge = self
+                    .debuggee
+                    .replace(child_dbge)
+                    .expect("debuggee disappeared");
+                if vfork {
+                    self.vfork_parent = Some(parent_dbge);
+                } else {
+                    parent_dbge.detach(None)?;
+                }
+                self.last_signal = None;
+            }
+        }
+
+        Ok(Feedback::Fork(ForkEvent {
+            parent,
+            child,
+            vfork,
+            mode,
+        }))
+    }
+
+    /// Handles a `fork`, `vfork` or caught syscall that was reported while all threads were
+    /// being stopped
+    ///
+    /// The thread that reported it is selected. A new process is followed with
+    /// [`Self::handle_fork`], a syscall stop is reported as it is.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Feedback::Fork))` - If a thread had a `fork` or `vfork`
+    /// * `Ok(Some(Feedback::Syscall))` - If a thread had a caught syscall stop
+    /// * `Ok(None)` - If no thread had such an event
+    /// * `Err(DebuggerError)` - If the new process could not be followed
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if the debuggee is not running or [`Self::handle_fork`] fails.
+    fn handle_pending_event(&mut self) -> Result<Option<Feedback>> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        if let Some((tid, hit)) = dbge
+            .threads
+            .values_mut()
+            .find_map(|t| t.pending_watchpoint.take().map(|hit| (t.tid, hit)))
+        {
+            self.select_thread(tid)?;
+            return Ok(Some(Feedback::Watchpoint(hit)));
+        }
+        if let Some(event) = dbge
+            .threads
+            .values_mut()
+            .find_map(|t| t.pending_syscall.take())
+        {
+            self.select_thread(event.tid)?;
+            return Ok(Some(Feedback::Syscall(event)));
+        }
+        let Some((tid, event)) = dbge
+            .threads
+            .values_mut()
+            .find_map(|t| t.pending_event.take().map(|e| (t.tid, e)))
+        else {
+            return Ok(None);
+        };
+        self.select_thread(tid)?;
+        Ok(Some(self.handle_fork(
+            tid,
+            event == nix::libc::PTRACE_EVENT_VFORK,
+        )?))
+    }
+
+    /// Enables the suspended breakpoints again after a `vfork` child executed a new program or
+    /// exited, then resumes the thread that created the child
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread that reported the `PTRACE_EVENT_VFORK_DONE`
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - A [`Breakpoint`] cannot be enabled
+    /// - The thread cannot be resumed
+    fn handle_vfork_done(&mut self, tid: Pid) -> Result<()> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        dbge.resume_breakpoints(tid)?;
+        let state = dbge.thread(tid).map_or(ThreadState::Running, Thread::state);
+        self.resume_as_before(tid, state, None)
+    }
+
+    /// Reloads the debug information after the debuggee executed a new program
+    ///
+    /// The memory of the debuggee was replaced, so all breakpoints are gone. All threads
+    /// except the thread group leader have ended.
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID that reported the `PTRACE_EVENT_EXEC`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Exec)` - The path of the new executable
+    /// * `Err(DebuggerError)` - If the new executable could not be loaded
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The new executable cannot be read
+    /// - Debug information cannot be parsed
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the object data of the executable was loaded but not stored, which
+    /// should not happen.
+    fn handle_exec(&mut self, tid: Pid) -> Result<Feedback> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        let pid = dbge.pid;
+        trace!("thread {tid} executed a new program");
+        dbge.forget_breakpoints();
+        dbge.threads.clear();
+        dbge.threads
+            .insert(pid, Thread::new(pid, ThreadState::Stopped));
+        dbge.selected_thread = pid;
+        let attached = dbge.attached;
+        self.last_signal = None;
+
+        let exe = std::fs::read_link(format!("/proc/{pid}/exe"))?;
+        info!("debuggee executed '{}'", exe.to_string_lossy());
+        self.load_executable(&exe)?;
+        let executable_obj_data: object::File<'_> = self
+            .stored_obj_data
+            .take()
+            .expect("object data was loaded but is not stored");
+        let dbginfo: CMDebugInfo = CMDebugInfo::build(executable_obj_data)?;
+        let mut dbge = Debuggee::build(pid, &dbginfo, HashMap::new())?;
+        dbge.attached = attached;
+        self.debuggee = Some(dbge);
+
+        self.release_vfork_parent()?;
+        Ok(Feedback::Exec(exe))
+    }
+
+    /// Detaches from the parent of a followed `vfork` child
+    ///
+    /// The parent keeps its breakpoints until the child executed a new program or exited, as
+    /// they are in the memory it shared with the child.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the parent cannot be detached.
+    fn release_vfork_parent(&mut self) -> Result<()> {
+        if let Some(mut parent) = self.vfork_parent.take() {
+            info!("detaching from vfork parent {}", parent.pid);
+            parent.detach(None)?;
+        }
+        Ok(())
+    }
+
+    /// Low-level wait for a change in the state of any thread of the debuggee
+    ///
+    /// # Parameters
+    ///
+    /// * `options` - Options to pass to waitpid, usually `&[]`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(WaitStatus)` - The status of the wait operation
+    /// * `Err(DebuggerError)` - If there was an error during waiting
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - waitpid fails
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use nix::sys::wait::{WaitPidFlag, WaitStatus};
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Wait for any status change without options
+    /// let status = debugger.wait(&[]).unwrap();
+    ///
+    /// // Wait with the WNOHANG option (non-blocking)
+    /// let status = debugger.wait(&[WaitPidFlag::WNOHANG]).unwrap();
+    ///
+    /// match status {
+    ///     WaitStatus::Exited(_, code) => println!("Process exited with code {}", code),
+    ///     WaitStatus::Stopped(_, signal) => println!("Process stopped by signal {:?}", signal),
+    ///     _ => println!("Other status change"),
+    /// }
+    ///
+    /// # }}
+    /// ```
+    pub fn wait(&self, options: &[WaitPidFlag]) -> Result<WaitStatus> {
+        if self.debuggee.is_none() {
+            return Err(DebuggerError::NoDebugee);
+        }
+        // threads are not children of the debugger, so __WALL is needed to wait for them
+        let mut flags = WaitPidFlag::__WALL;
+        for f in options {
+            flags |= *f;
+        }
+        trace!("wait flags: {flags:?}");
+        Ok(waitpid(None, Some(flags))?)
+    }
+
+    /// Runs the main debugger loop
+    ///
+    /// This function forms the main execution loop of the debugger, processing
+    /// UI commands and executing corresponding debugger actions until the user
+    /// requests to quit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the debugger loop completed successfully
+    /// * `Err(DebuggerError)` - If there was an error during execution
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee already exists but can't be waited for.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// #
+    /// let ui = CliUi::build(None).unwrap();
+    /// let mut debugger = Debugger::build(ui).unwrap();
+    ///
+    /// // Start the main debugger loop
+    /// // This will run until the ui exits
+    /// debugger.run_debugger().unwrap();
+    ///
+    /// debugger.cleanup().unwrap();
+    ///
+    /// # }}
+    /// ```
+    pub fn run_debugger(&mut self) -> Result<()> {
+        if self.debuggee.as_ref().is_some() {
+            self.wait_signal()?; // wait until the debuggee is stopped
+        } else {
+            info!("debuggee not yet launched");
+        }
+
+        let mut feedback: Feedback = Feedback::Ok;
+        loop {
+            let ui_res = if self.is_running() {
+                self.process_running(feedback)
+            } else {
+                self.ui.process(feedback)
+            };
+            feedback = {
+                match ui_res {
+                    Err(e) => {
+                        error!("{e}");
+                        return Err(e);
+                    }
+                    Ok(s) => match self.process_status(&s) {
+                        Ok(Feedback::Internal(InternalFeedback::Quit)) => break,
+                        other => other,
+                    },
+                }
+            }
+            .into();
+
+            // Clean up if process exited
+            if let Feedback::Exit(_) = feedback {
+                self.debuggee = None;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Talks to the [`DebuggerUI`] while the debuggee runs in the background
+    ///
+    /// The `feedback` is shown with [`DebuggerUI::notify`]. Then the debuggee and the
+    /// [`DebuggerUI`] are polled in turns, until either the debuggee stops, or the
+    /// [`DebuggerUI`] has a new [`Status`]. A stop of the debuggee is given to
+    /// [`DebuggerUI::process`], like for a blocking [`Status::Continue`].
+    ///
+    /// # Parameters
+    ///
+    /// * `feedback` - The feedback of the last [`Status`]
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Status)` - The next [`Status`] from the [`DebuggerUI`]
+    /// * `Err(DebuggerError)` - If the [`DebuggerUI`] failed
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the [`DebuggerUI`] fails. Errors of the debuggee are given to the
+    /// [`DebuggerUI`] as feedback.
+    fn process_running(&mut self, feedback: Feedback) -> Result<Status> {
+        self.ui.notify(feedback)?;
+        loop {
+            if let Some(status) = self.ui.poll()? {
+                return Ok(status);
+            }
+            let stop: Feedback = match self.poll_signal() {
+                Ok(None) => {
+                    std::thread::sleep(POLL_INTERVAL);
+                    continue;
+                }
+                Ok(Some(stop)) => stop,
+                Err(e) => {
+                    error!("error while the debuggee was running: {e}");
+                    Feedback::Error(e)
+                }
+            };
+            if let Feedback::Exit(_) = stop {
+                self.debuggee = None;
+            }
+            return self.ui.process(stop);
+        }
+    }
+
+    /// Process a [`Status`] by executing the specified action.
+    ///
+    /// This function takes a [`Status`] and has the debugger perform actions to generate
+    /// a [`Feedback`].
+    ///
+    /// For example, a [`Status`] might request that the [`Debugger`] should read some [`Word`]
+    /// from the memory of the [`Debuggee`] with [`Status::ReadMem`].
+    ///
+    /// This function will then match
+    /// the [`Status`] to the fitting implementation function, [`Self::read_mem`] for this example,
+    /// execute it, and return the [`Feedback`] ([`Feedback::Word`]).
+    ///
+    /// If the action taken fails, a [`Err`] variant will be returned instead. Since [`Feedback`]
+    /// can be constructed from a [`DebuggerError`], you can wrap the error in a [`Feedback`] and
+    /// send this to the [`DebuggerUI`] or a [`Plugin`](steckrs::Plugin) with [`Self::hook_feedback_loop`].
+    ///
+    /// # Parameters
+    ///
+    /// * `status` - A [`Status`] to be processed
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback)` - The result of the action detailed by the `status`
+    /// * `Err(DebuggerError)` - If there was an error performing the requested action
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the taken action for that [`Status`] fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::addr::Addr;
+    /// # use coreminer::feedback::{Feedback, Status};
+    /// #
+    /// let ui = CliUi::build(None).unwrap();
+    /// let mut debugger = Debugger::build(ui).unwrap();
+    ///
+    /// let status = Status::ReadMem(Addr::from(98421479usize));
+    /// let feedback: Feedback = debugger.process_status(&status).unwrap();
+    ///
+    /// if let Feedback::Word(w) = feedback {
     ///     println!("read the word: {w}");
     /// } else {
     ///     eprintln!("could not read the word");
@@ -540,57 +1628,3056 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     ///
     /// # }}
     /// ```
-    pub fn process_status(&mut self, status: &Status) -> Result<Feedback> {
-        match status {
-            Status::Infos => self.infos(),
-            Status::DebuggerQuit => Ok(Feedback::Internal(InternalFeedback::Quit)),
-            Status::Continue => self.cont(),
-            Status::SetBreakpoint(addr) => self.set_bp(*addr),
-            Status::DelBreakpoint(addr) => self.del_bp(*addr),
-            Status::DumpRegisters => self.dump_regs(),
-            Status::SetRegister(r, v) => self.set_reg(*r, *v),
-            Status::WriteMem(a, v) => self.write_mem(*a, *v),
-            Status::ReadMem(a) => self.read_mem(*a),
-            Status::DisassembleAt(a, l, literal) => self.disassemble_at(*a, *l, *literal),
-            Status::GetSymbolsByName(s) => self.get_symbol_by_name(s),
-            Status::StepSingle => self.single_step(),
-            Status::StepOut => self.step_out(),
-            Status::StepInto => self.step_into(),
-            Status::StepOver => self.step_over(),
-            Status::Backtrace => self.backtrace(),
-            Status::ReadVariable(va) => self.read_variable(va),
-            Status::WriteVariable(va, val) => self.write_variable(va, *val),
-            Status::GetStack => self.get_stack(),
-            Status::ProcMap => self.get_process_map(),
-            Status::Run(exe, args) => self.run(exe, args),
-            Status::GetBreakpoint(addr) => self.get_bp(*addr),
-            Status::SetLastSignal(signum) => self.set_last_signal(*signum),
-            #[cfg(feature = "plugins")]
-            Status::PluginContinue => Err(DebuggerError::UiUsedPluginContinue),
-            #[cfg(feature = "plugins")]
-            Status::PluginSetEnable(id, status) => self.plugin_set_enable(id, *status),
-            #[cfg(feature = "plugins")]
-            Status::PluginGetStatus(id) => self.plugin_get_status(id),
-            #[cfg(feature = "plugins")]
-            Status::PluginGetList => self.list_plugins(),
-        }
+    pub fn process_status(&mut self, status: &Status) -> Result<Feedback> {
+        if self.is_running()
+            && !matches!(
+                status,
+                Status::Interrupt
+                    | Status::DebuggerQuit
+                    | Status::Restart
+                    | Status::SetFollowFork(_)
+                    | Status::SetSignalPolicy(..)
+                    | Status::ListSignalPolicies
+            )
+        {
+            return Err(DebuggerError::DebuggeeRunning);
+        }
+        if self.debuggee.as_ref().is_some_and(Debuggee::is_core) && status.needs_live_debuggee() {
+            return Err(DebuggerError::CoreDumpNotLive);
+        }
+        match status {
+            Status::DebuggerQuit => Ok(Feedback::Internal(InternalFeedback::Quit)),
+            Status::Continue => self.cont(),
+            Status::ContinueAsync => self.cont_async(),
+            Status::Interrupt => self.interrupt(),
+            Status::SetBreakpoint(addr) => self.set_bp(*addr),
+            Status::SetBreakpointAtSymbol(name) => self.set_bp_at_symbol(name),
+            Status::SetBreakpointAtLine(file, line) => self.set_bp_at_line(file, *line),
+            Status::SetBreakpointCondition(addr, condition) => {
+                self.set_bp_condition(*addr, condition.clone())
+            }
+            Status::SetBreakpointIgnoreCount(addr, count) => {
+                self.set_bp_ignore_count(*addr, *count)
+            }
+            Status::SetTemporaryBreakpoint(addr) => self.set_temporary_bp(*addr),
+            Status::SetHardwareBreakpoint(addr) => self.set_hw_bp(*addr),
+            Status::SetWatchpoint(addr, len, kind) => self.set_watchpoint(*addr, *len, *kind),
+            Status::SetWatchpointAtVariable(expression, kind) => {
+                self.set_watchpoint_at_variable(expression, *kind)
+            }
+            Status::DelWatchpoint(addr) => self.del_watchpoint(*addr),
+            Status::ListWatchpoints => self.list_watchpoints(),
+            Status::ListLibraries => self.list_libraries(),
+            Status::SetBreakpointCommands(addr, commands) => {
+                self.set_bp_commands(*addr, commands.clone())
+            }
+            Status::ListBreakpoints => self.list_bps(),
+            Status::DelBreakpoint(addr) => self.del_bp(*addr),
+            Status::DumpRegisters => self.dump_regs(),
+            Status::SetRegister(r, v) => self.set_reg(*r, *v),
+            Status::WriteMem(a, v) => self.write_mem(*a, *v),
+            Status::ReadMem(a) => self.read_mem(*a),
+            Status::DisassembleAt(a, l, literal) => self.disassemble_at(*a, *l, *literal),
+            Status::GetSymbolsByName(s) => self.get_symbol_by_name(s),
+            Status::StepSingle => self.single_step(),
+            Status::StepOut => self.step_out(),
+            Status::Finish => self.finish(),
+            Status::RunUntil(location) => self.run_until(location),
+            Status::StepInto => self.step_into(),
+            Status::StepOver => self.step_over(),
+            Status::StepLine => self.step_line(),
+            Status::NextLine => self.next_line(),
+            Status::Backtrace => self.backtrace(),
+            Status::ReadVariable(va) => self.read_variable(va),
+            Status::WriteVariable(va, val) => self.write_variable(va, *val),
+            Status::GetStack => self.get_stack(),
+            Status::ProcMap => self.get_process_map(),
+            Status::Run(exe, args, options) => self.run(exe, args, options),
+            Status::Restart => self.restart(),
+            Status::Attach(pid) => self.attach(Pid::from_raw(*pid)),
+            Status::Detach => self.detach(),
+            Status::LoadCore(core, exe) => self.load_core(core, exe.as_deref()),
+            Status::GenerateCore(path) => self.generate_core(path),
+            Status::SaveSession(path) => self.save_session(path),
+            Status::LoadSession(path) => self.load_session(path),
+            Status::GetBreakpoint(addr) => self.get_bp(*addr),
+            Status::SetLastSignal(signum) => self.set_last_signal(*signum),
+            Status::ListThreads => self.list_threads(),
+            Status::SelectThread(tid) => self.select_thread(Pid::from_raw(*tid)),
+            Status::SetFollowFork(mode) => self.set_follow_fork(*mode),
+            Status::ListProcesses => self.list_processes(),
+            Status::SelectProcess(pid) => self.select_process(Pid::from_raw(*pid)),
+            Status::TraceSyscalls(enable) => self.trace_syscalls(*enable),
+            Status::CatchSyscall(syscall) => self.catch_syscall(syscall),
+            Status::DelCatchSyscall(syscall) => self.del_catch_syscall(syscall),
+            Status::SetSignalPolicy(signum, policy) => self.set_signal_policy(*signum, *policy),
+            Status::ListSignalPolicies => self.list_signal_policies(),
+            Status::AddSkip(rule) => self.add_skip(rule.clone()),
+            Status::DelSkip(rule) => self.del_skip(rule),
+            Status::ListSkips => self.list_skips(),
+            #[cfg(feature = "plugins")]
+            Status::PluginContinue => Err(DebuggerError::UiUsedPluginContinue),
+            #[cfg(feature = "plugins")]
+            Status::PluginSetEnable(id, status) => self.plugin_set_enable(id, *status),
+            #[cfg(feature = "plugins")]
+            Status::PluginGetStatus(id) => self.plugin_get_status(id),
+            #[cfg(feature = "plugins")]
+            Status::PluginGetList => self.list_plugins(),
+        }
+    }
+
+    /// Continues execution of the debuggee, optionally delivering a signal
+    ///
+    /// This function tells the debuggee to continue execution from its current state.
+    /// All threads are resumed. If a signal is provided, it will be delivered to the
+    /// selected thread.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback)` - The result of the continuation
+    /// * `Err(DebuggerError)` - If there was an error during continuation
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - ptrace's cont operation fails
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use nix::sys::signal::Signal;
+    /// #
+    /// let ui = CliUi::build(None).unwrap();
+    /// let mut debugger = Debugger::build(ui).unwrap();
+    /// // Assume debuggee is already running
+    ///
+    /// // Continue execution
+    /// debugger.cont().unwrap();
+    ///
+    /// # }}
+    /// ```
+    pub fn cont(&mut self) -> Result<Feedback> {
+        if let Some(feedback) = self.resume()? {
+            return Ok(feedback);
+        }
+
+        self.wait_signal() // wait until the debuggee is stopped again!!!
+    }
+
+    /// Continues execution of the debuggee in the background
+    ///
+    /// Unlike [`Self::cont`], this function returns right after all threads are resumed. While
+    /// the debuggee runs, [`Self::run_debugger`] checks for a stop with [`Self::poll_signal`] and
+    /// asks the [`DebuggerUI`] for a [`Status`] with [`DebuggerUI::poll`], so that the debuggee
+    /// can be stopped with [`Self::interrupt`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Running)` - If the debuggee runs in the background now
+    /// * `Ok(Feedback)` - If an event that happened before was reported instead, like a `fork`
+    /// * `Err(DebuggerError)` - If there was an error during continuation
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - ptrace's cont operation fails
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::feedback::Feedback;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// debugger.cont_async().unwrap();
+    ///
+    /// // do something else, then stop the debuggee if it did not stop by itself
+    /// if debugger.poll_signal().unwrap().is_none() {
+    ///     debugger.interrupt().unwrap();
+    /// }
+    ///
+    /// # }}
+    /// ```
+    pub fn cont_async(&mut self) -> Result<Feedback> {
+        if let Some(feedback) = self.resume()? {
+            return Ok(feedback);
+        }
+
+        Ok(Feedback::Running)
+    }
+
+    /// Stops the debuggee while it runs in the background
+    ///
+    /// All threads are stopped and the selected thread becomes the one that was interrupted.
+    /// If the debuggee stopped by itself in the meantime, that stop is reported instead.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback)` - The stop of the debuggee, like from [`Self::cont`]
+    /// * `Err(DebuggerError)` - If the debuggee could not be stopped
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running in the background
+    /// - The debuggee cannot be stopped
+    /// - waitpid fails
+    pub fn interrupt(&mut self) -> Result<Feedback> {
+        if !self.is_running() {
+            return Err(DebuggerError::DebuggeeNotRunning);
+        }
+        self.request_interrupt()?;
+        self.wait_signal()
+    }
+
+    /// Checks if the debuggee runs in the background
+    ///
+    /// This is the case after [`Self::cont_async`], until the debuggee stops.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.debuggee
+            .as_ref()
+            .is_some_and(|dbge| dbge.threads().any(Thread::is_running))
+    }
+
+    /// Resumes all threads of the debuggee, unless an event that happened before is reported
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Feedback))` - The event that happened before, the debuggee was not resumed
+    /// * `Ok(None)` - If all threads were resumed
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if the debuggee is not running or cannot be resumed.
+    fn resume(&mut self) -> Result<Option<Feedback>> {
+        if let Some(feedback) = self.handle_pending_event()? {
+            return Ok(Some(feedback));
+        }
+        if self.go_back_step_over_bp()? {
+            info!("breakpoint before, caught up and continueing with single step");
+            // the step over the breakpoint may have hit a watchpoint
+            if let Some(hit) = self.take_stepped_watchpoint_hit()? {
+                return Ok(Some(Feedback::Watchpoint(hit)));
+            }
+        }
+        let sig = self.take_last_status();
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        dbge.set_resume_flag(dbge.selected_thread(), true)?;
+        dbge.resume_all(sig, self.syscall_catcher.is_active())?;
+        Ok(None)
+    }
+
+    /// Gets the current registers of the selected thread of the debuggee
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Registers)` - The registers
+    /// * `Err(DebuggerError)` - If there was an error retrieving registers
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - ptrace's getregs operation fails
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::feedback::Feedback;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// if let Ok(Feedback::Registers(regs)) = debugger.dump_regs() {
+    ///     println!("RIP: {:#x}", regs.rip);
+    ///     println!("RSP: {:#x}", regs.rsp);
+    ///     println!("RAX: {:#x}", regs.rax);
+    /// }
+    ///
+    /// # }}
+    /// ```
+    pub fn dump_regs(&self) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let regs = dbge.get_regs(dbge.selected_thread())?;
+        Ok(Feedback::Registers(regs.into()))
+    }
+
+    /// Cleans up resources used by the debugger
+    ///
+    /// This function terminates the debuggee if it's still running
+    /// and releases any resources held by the debugger. If the debuggee was
+    /// attached to with [`Self::attach`], it is detached instead. Processes held after a `fork`
+    /// are handled the same way.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If cleanup was successful
+    /// * `Err(DebuggerError)` - If there was an error during cleanup
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The exists but debuggee cannot be killed with [`Debuggee::kill`]
+    /// - The debuggee was attached to but cannot be detached with [`Self::detach`]
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// #
+    /// // Clean up resources at the end of debugging
+    /// debugger.cleanup().unwrap();
+    ///
+    /// # }}
+    /// ```
+    pub fn cleanup(&mut self) -> Result<()> {
+        // ptrace only works on stopped threads
+        if self.is_running() {
+            if let Feedback::Exit(_) = self.interrupt()? {
+                self.debuggee = None;
+            }
+        }
+        for mut held in std::mem::take(&mut self.held_processes) {
+            if held.is_attached() {
+                held.detach(None)?;
+            } else {
+                held.kill()?;
+            }
+        }
+        self.release_vfork_parent()?;
+        if let Some(dbge) = &self.debuggee {
+            if dbge.is_attached() {
+                self.detach()?;
+            } else {
+                dbge.kill()?;
+            }
+            self.debuggee = None;
+        }
+        Ok(())
+    }
+
+    /// Sets a breakpoint at the specified address
+    ///
+    /// The location of the breakpoint is also remembered as an offset into the mapped file that
+    /// contains it, so that [`Self::restart`] can set it again, even if the program is loaded at
+    /// a different address.
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The address to set the breakpoint at
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the breakpoint was set successfully
+    /// * `Err(DebuggerError)` - If there was an error setting the breakpoint
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The breakpoint could not be enabled
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::addr::Addr;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Set a breakpoint at absolute address 0x1000
+    /// debugger.set_bp(Addr::from(0x1000usize)).unwrap();
+    ///
+    /// // Set a breakpoint at the program's entry point
+    /// let base = debugger.get_current_addr().unwrap();
+    /// debugger.set_bp(base).unwrap();
+    ///
+    /// // Set a breakpoint at the relative address 0x1000
+    /// let base = debugger.get_current_addr().unwrap();
+    /// debugger.set_bp(base + 0x1000).unwrap();
+    ///
+    /// # }}
+    /// ```
+    pub fn set_bp(&mut self, addr: Addr) -> Result<Feedback> {
+        self.set_bp_with(
+            addr,
+            BreakpointKind::Software,
+            BreakpointSettings::default(),
+        )
+    }
+
+    /// Sets a breakpoint of a kind at the specified address with the given settings
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if the debuggee is not running or the breakpoint could not be
+    /// enabled.
+    fn set_bp_with(
+        &mut self,
+        addr: Addr,
+        kind: BreakpointKind,
+        settings: BreakpointSettings,
+    ) -> Result<Feedback> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        match kind {
+            BreakpointKind::Software => {
+                dbge.del_hw_breakpoint(addr)?;
+                // the loader still sees the hits of the breakpoint that replaces its own
+                if dbge
+                    .breakpoints
+                    .get(&addr)
+                    .is_some_and(Breakpoint::is_internal)
+                {
+                    dbge.breakpoints.remove(&addr);
+                }
+                let mut bp = Breakpoint::new(dbge.pid, addr);
+                bp.set_settings(settings.clone());
+                bp.enable()?;
+                dbge.breakpoints.insert(addr, bp);
+            }
+            BreakpointKind::Hardware => dbge.set_hw_breakpoint(addr, settings.clone())?,
+        }
+
+        if let Some(location) = dbge.get_process_map()?.module_offset(addr) {
+            trace!("breakpoint at {addr} is at {location}");
+            self.breakpoint_locations.insert(location, (kind, settings));
+        } else {
+            warn!(
+                "breakpoint at {addr} is not in a mapped file, it will not be restored on restart"
+            );
+        }
+
+        Ok(Feedback::Ok)
+    }
+
+    /// Sets a breakpoint at a function, given by its name
+    ///
+    /// The address is resolved with [`Debuggee::resolve_function`], so that the breakpoint is
+    /// after the prologue of the function and its parameters can be read when it is hit. If
+    /// there already is a breakpoint at that address, it is kept.
+    ///
+    /// If neither the executable nor a loaded shared library has the function, but the debuggee
+    /// is dynamically linked, the breakpoint is pending. It is set as soon as a library that has
+    /// the function is loaded, and reported in the [`LibraryEvent`]. Pending breakpoints are
+    /// kept on [restart](Self::restart).
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name of the function
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Addr)` - The address the breakpoint was set at
+    /// * `Ok(Feedback::PendingBreakpoint)` - If the function is not loaded yet
+    /// * `Err(DebuggerError)` - If there was an error setting the breakpoint
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The function cannot be resolved and the debuggee is statically linked
+    /// - The breakpoint could not be enabled
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::feedback::Feedback;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// if let Ok(Feedback::Addr(addr)) = debugger.set_bp_at_symbol("main") {
+    ///     println!("breakpoint at {addr}");
+    /// }
+    /// # }}
+    /// ```
+    pub fn set_bp_at_symbol(&mut self, name: &str) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let addr = match dbge.resolve_function(name) {
+            Ok(addr) => addr,
+            Err(DebuggerError::FunctionNotFound(_)) if dbge.tracks_libraries() => {
+                info!("breakpoint for {name} is pending until a library that has it is loaded");
+                if !self.pending_breakpoints.iter().any(|p| p == name) {
+                    self.pending_breakpoints.push(name.to_string());
+                }
+                return Ok(Feedback::PendingBreakpoint(name.to_string()));
+            }
+            Err(e) => return Err(e),
+        };
+        if dbge
+            .breakpoints
+            .get(&addr)
+            .is_some_and(|bp| !bp.is_internal())
+        {
+            debug!("there already is a breakpoint for {name} at {addr}");
+        } else {
+            self.set_bp(addr)?;
+        }
+        info!("breakpoint for {name} at {addr}");
+
+        Ok(Feedback::Addr(addr))
+    }
+
+    /// Sets breakpoints at a line in a source file
+    ///
+    /// The addresses are resolved with [`Debuggee::resolve_line`] from the line table. A line
+    /// can have code at several addresses, for example in inlined functions or template
+    /// instances, so a breakpoint is set at each of them. Breakpoints that already exist are
+    /// kept.
+    ///
+    /// # Parameters
+    ///
+    /// * `file` - The source file, or the end of its path, like `main.c`
+    /// * `line` - The line in the source file
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::LineAddrs)` - The line that has code, at or after `line`, and the
+    ///   addresses the breakpoints were set at
+    /// * `Err(DebuggerError)` - If there was an error setting the breakpoints
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - No line at or after `line` in `file` has code
+    /// - A breakpoint could not be enabled
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::feedback::Feedback;
+    /// # use std::path::Path;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// if let Ok(Feedback::LineAddrs(line, addrs)) = debugger.set_bp_at_line(Path::new("main.rs"), 5) {
+    ///     println!("breakpoints for line {line} at {addrs:?}");
+    /// }
+    /// # }}
+    /// ```
+    pub fn set_bp_at_line(&mut self, file: &Path, line: u64) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let (found, addrs) = dbge.resolve_line(file, line)?;
+        if found != line {
+            info!("line {line} has no code, using line {found}");
+        }
+
+        for addr in &addrs {
+            let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+            if !dbge.breakpoints.contains_key(addr) {
+                self.set_bp(*addr)?;
+            }
+        }
+
+        Ok(Feedback::LineAddrs(found, addrs))
+    }
+
+    /// Sets or removes the condition of a breakpoint
+    ///
+    /// When the breakpoint is hit and the [`Condition`] is false, the debugger steps over the
+    /// breakpoint and continues without returning to the [`DebuggerUI`]. The condition is kept
+    /// when the debuggee is [restarted](Self::restart).
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The address of the breakpoint
+    /// * `condition` - The new condition, [`None`] to stop every time
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the condition was set
+    /// * `Err(DebuggerError)` - If there was an error setting the condition
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - There is no breakpoint at `addr`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::addr::Addr;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// let addr = Addr::from(0x55555555513cusize);
+    /// debugger.set_bp(addr).unwrap();
+    /// debugger.set_bp_condition(addr, Some("rdi == 0x10".parse().unwrap())).unwrap();
+    ///
+    /// // only stops when rdi is 0x10
+    /// debugger.cont().unwrap();
+    /// # }}
+    /// ```
+    pub fn set_bp_condition(
+        &mut self,
+        addr: Addr,
+        condition: Option<Condition>,
+    ) -> Result<Feedback> {
+        if let Some(c) = &condition {
+            info!("breakpoint at {addr} stops if {c}");
+        } else {
+            info!("breakpoint at {addr} stops every time");
+        }
+        self.bp_settings_mut(addr)?.condition = condition;
+        self.remember_bp_settings(addr)?;
+
+        Ok(Feedback::Ok)
+    }
+
+    /// Ignores the next hits of a breakpoint
+    ///
+    /// Only hits where the [`Condition`] of the breakpoint is true are counted. The ignore count
+    /// is kept when the debuggee is [restarted](Self::restart).
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The address of the breakpoint
+    /// * `count` - How many hits are ignored, 0 to stop at the next hit
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the ignore count was set
+    /// * `Err(DebuggerError)` - If there was an error setting the ignore count
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - There is no breakpoint at `addr`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::addr::Addr;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// let addr = Addr::from(0x55555555513cusize);
+    /// debugger.set_bp(addr).unwrap();
+    /// debugger.set_bp_ignore_count(addr, 9).unwrap();
+    ///
+    /// // stops at the tenth hit
+    /// debugger.cont().unwrap();
+    /// # }}
+    /// ```
+    pub fn set_bp_ignore_count(&mut self, addr: Addr, count: u64) -> Result<Feedback> {
+        info!("breakpoint at {addr} ignores the next {count} hits");
+        self.bp_settings_mut(addr)?.ignore_count = count;
+        self.remember_bp_settings(addr)?;
+
+        Ok(Feedback::Ok)
+    }
+
+    /// Sets the commands that run when a breakpoint is hit, making it a logpoint
+    ///
+    /// When a breakpoint with commands is hit, the debuggee does not stop. Instead, the commands
+    /// are processed like any other [`Status`], their feedback is shown with
+    /// [`DebuggerUI::notify`] as a single [`Feedback::Logpoint`], and the debuggee continues.
+    /// This allows tracing a program, like with `printf`, without recompiling it. The commands
+    /// are kept when the debuggee is [restarted](Self::restart).
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The address of the breakpoint
+    /// * `commands` - The commands to run, an empty list makes it a normal breakpoint again
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the commands were set
+    /// * `Err(DebuggerError)` - If there was an error setting the commands
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - There is no breakpoint at `addr`
+    /// - A command controls the execution of the debuggee, like [`Status::Continue`] or
+    ///   [`Status::SelectThread`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::feedback::Status;
+    /// # use coreminer::addr::Addr;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// let addr = Addr::from(0x55555555513cusize);
+    /// debugger.set_bp(addr).unwrap();
+    /// debugger
+    ///     .set_bp_commands(addr, vec![Status::ReadVariable("counter".to_string())])
+    ///     .unwrap();
+    ///
+    /// // shows counter every time the breakpoint is hit, until the debuggee stops otherwise
+    /// debugger.cont().unwrap();
+    /// # }}
+    /// ```
+    pub fn set_bp_commands(&mut self, addr: Addr, commands: Vec<Status>) -> Result<Feedback> {
+        if let Some(command) = commands.iter().find(|c| c.controls_execution()) {
+            return Err(DebuggerError::NotABreakpointCommand(format!("{command:?}")));
+        }
+        info!("breakpoint at {addr} runs {} commands", commands.len());
+        self.bp_settings_mut(addr)?.commands = commands;
+        self.remember_bp_settings(addr)?;
+
+        Ok(Feedback::Ok)
+    }
+
+    /// Runs the commands of the breakpoint at an address and shows their feedback
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the feedback cannot be shown by the [`DebuggerUI`]. Errors of the
+    /// commands are part of the feedback.
+    fn run_bp_commands(&mut self, addr: Addr, commands: &[Status]) -> Result<()> {
+        let mut feedbacks = Vec::with_capacity(commands.len());
+        for command in commands {
+            trace!("running command {command:?} of breakpoint at {addr}");
+            feedbacks.push(self.process_status(command).into());
+        }
+        self.ui.notify(Feedback::Logpoint(addr, feedbacks))
+    }
+
+    /// Sets a temporary breakpoint at the specified address
+    ///
+    /// A temporary breakpoint is removed when the debuggee stops at it for the first time. An
+    /// existing breakpoint at the address is replaced.
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The address to set the breakpoint at
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the breakpoint was set successfully
+    /// * `Err(DebuggerError)` - If there was an error setting the breakpoint
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The breakpoint could not be enabled
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::addr::Addr;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// debugger.set_temporary_bp(Addr::from(0x55555555513cusize)).unwrap();
+    ///
+    /// // stops at the breakpoint and removes it
+    /// debugger.cont().unwrap();
+    /// # }}
+    /// ```
+    pub fn set_temporary_bp(&mut self, addr: Addr) -> Result<Feedback> {
+        let settings = BreakpointSettings {
+            temporary: true,
+            ..Default::default()
+        };
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        let kind = if dbge.hw_breakpoints.contains_key(&addr) {
+            BreakpointKind::Hardware
+        } else {
+            BreakpointKind::Software
+        };
+        if dbge.breakpoints.remove(&addr).is_some() {
+            debug!("replacing the breakpoint at {addr} with a temporary one");
+        }
+        self.set_bp_with(addr, kind, settings)
+    }
+
+    /// Sets a hardware breakpoint at the specified address
+    ///
+    /// A hardware breakpoint uses one of the debug registers instead of writing `INT3` into the
+    /// code, so it also works for code that checks itself. The debuggee stops before the
+    /// instruction at the address is executed. An existing breakpoint at the address is
+    /// replaced.
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The address to set the breakpoint at
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the breakpoint was set successfully
+    /// * `Err(DebuggerError)` - If there was an error setting the breakpoint
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - All four debug registers are in use
+    /// - The debug registers could not be written, for example for an address in kernel space
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::addr::Addr;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// debugger.set_hw_bp(Addr::from(0x55555555513cusize)).unwrap();
+    ///
+    /// // stops at the breakpoint, the code of the debuggee is unchanged
+    /// debugger.cont().unwrap();
+    /// # }}
+    /// ```
+    pub fn set_hw_bp(&mut self, addr: Addr) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let settings = match dbge.breakpoints.get(&addr) {
+            Some(bp) => {
+                debug!("replacing the breakpoint at {addr} with a hardware breakpoint");
+                let settings = bp.settings().clone();
+                self.del_bp(addr)?;
+                settings
+            }
+            None => BreakpointSettings::default(),
+        };
+        self.set_bp_with(addr, BreakpointKind::Hardware, settings)
+    }
+
+    /// Watches a region in the memory of the debuggee
+    ///
+    /// The debuggee stops after an instruction accessed the region, and the access is
+    /// reported as [`Feedback::Watchpoint`], with the address of the instruction and the content
+    /// before and after it. A [`WatchKind::Write`] watchpoint also stops for writes that do not
+    /// change the content.
+    ///
+    /// A region of 1, 2, 4 or 8 bytes that is aligned to its length is watched with a debug
+    /// register if one is free. Other regions, like buffers and structs, are watched in
+    /// software by protecting their pages, which is much slower. See
+    /// [`watchpoint`](crate::watchpoint) for the details.
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The address of the region
+    /// * `len` - The length of the region in bytes
+    /// * `kind` - Which accesses to stop at
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the watchpoint was set successfully
+    /// * `Err(DebuggerError)` - If there was an error setting the watchpoint
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The region is empty or not mapped
+    /// - The debug registers or the protection of the pages could not be written
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::addr::Addr;
+    /// # use coreminer::feedback::Feedback;
+    /// # use coreminer::watchpoint::WatchKind;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// debugger
+    ///     .set_watchpoint(Addr::from(0x555555558010usize), 4, WatchKind::Write)
+    ///     .unwrap();
+    ///
+    /// if let Ok(Feedback::Watchpoint(hit)) = debugger.cont() {
+    ///     println!("{:?} changed {:?} to {:?}", hit.instruction, hit.old, hit.new);
+    /// }
+    /// # }}
+    /// ```
+    pub fn set_watchpoint(&mut self, addr: Addr, len: usize, kind: WatchKind) -> Result<Feedback> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        dbge.set_watchpoint(dbge.selected_thread(), addr, len, kind)?;
+        self.remember_watchpoint(addr, len, kind)?;
+        Ok(Feedback::Ok)
+    }
+
+    /// Watches the location of a variable in the current function
+    ///
+    /// See [`Debugger::set_watchpoint`]. The variable must be in memory, larger variables like
+    /// structs and arrays are watched in software. A local variable is only valid while its
+    /// function runs, the watchpoint should be removed before it returns.
+    ///
+    /// # Parameters
+    ///
+    /// * `expression` - The variable to watch
+    /// * `kind` - Which accesses to stop at
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Addr)` - The address of the variable, where the watchpoint is
+    /// * `Err(DebuggerError)` - If there was an error setting the watchpoint
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The variable is not found or is kept in a register
+    /// - The watchpoint cannot be set, see [`Debugger::set_watchpoint`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::watchpoint::WatchKind;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// debugger
+    ///     .set_watchpoint_at_variable(&"counter".to_string(), WatchKind::ReadWrite)
+    ///     .unwrap();
+    /// # }}
+    /// ```
+    pub fn set_watchpoint_at_variable(
+        &mut self,
+        expression: &VariableExpression,
+        kind: WatchKind,
+    ) -> Result<Feedback> {
+        let (_, symbol, frame_info) = self.prepare_variable_access(expression)?;
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        let (addr, len) = dbge.var_location(&symbol, &frame_info)?;
+        info!("watching {expression} at {addr}, {len} bytes");
+        dbge.set_watchpoint(dbge.selected_thread(), addr, len, kind)?;
+        self.remember_watchpoint(addr, len, kind)?;
+        Ok(Feedback::Addr(addr))
+    }
+
+    /// Removes the watchpoint at the specified address
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if the debuggee is not running, there is no watchpoint at `addr`
+    /// or the debug registers could not be written.
+    pub fn del_watchpoint(&mut self, addr: Addr) -> Result<Feedback> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        dbge.del_watchpoint(dbge.selected_thread(), addr)?;
+        if let Some(location) = dbge.get_process_map()?.module_offset(addr) {
+            self.watchpoint_locations.remove(&location);
+        }
+        Ok(Feedback::Ok)
+    }
+
+    /// Stores where a new watchpoint is in its mapped file, so that it can be set again on
+    /// [restart](Self::restart) and is part of a [session](crate::session)
+    ///
+    /// Watchpoints on the stack or the heap are not stored, their addresses change with every
+    /// run.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the process map cannot be read.
+    fn remember_watchpoint(&mut self, addr: Addr, len: usize, kind: WatchKind) -> Result<()> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        if let Some(location) = dbge.get_process_map()?.module_offset(addr) {
+            trace!("watchpoint at {addr} is at {location}");
+            self.watchpoint_locations.insert(location, (len, kind));
+        } else {
+            debug!("watchpoint at {addr} is not in a mapped file, it will not be restored");
+        }
+        Ok(())
+    }
+
+    /// Lists all watchpoints with their hit counts and the values at their last hit
+    ///
+    /// # Errors
+    ///
+    /// This function fails if there is no debuggee.
+    pub fn list_watchpoints(&self) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        Ok(Feedback::Watchpoints(dbge.watchpoints().cloned().collect()))
+    }
+
+    /// Lists the shared libraries that are loaded into the debuggee, in the order they were
+    /// loaded
+    ///
+    /// # Errors
+    ///
+    /// This function fails if there is no debuggee.
+    pub fn list_libraries(&self) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        Ok(Feedback::Libraries(
+            dbge.libraries().iter().map(SharedLibrary::info).collect(),
+        ))
+    }
+
+    /// Lists all breakpoints with their state, hit counts and settings
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Breakpoints)` - The breakpoints, sorted by address
+    /// * `Err(DebuggerError)` - If there is no debuggee
+    ///
+    /// # Errors
+    ///
+    /// This function fails if there is no debuggee.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::feedback::Feedback;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// if let Ok(Feedback::Breakpoints(bps)) = debugger.list_bps() {
+    ///     for bp in bps {
+    ///         println!("{} was hit {} times", bp.addr, bp.hits);
+    ///     }
+    /// }
+    /// # }}
+    /// ```
+    pub fn list_bps(&self) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let mut bps: Vec<_> = dbge
+            .breakpoints
+            .values()
+            .filter(|bp| !bp.is_internal())
+            .map(Breakpoint::info)
+            .chain(dbge.hw_breakpoints().map(HardwareBreakpoint::info))
+            .collect();
+        bps.sort_by_key(|bp| bp.addr);
+        Ok(Feedback::Breakpoints(bps))
+    }
+
+    /// Stores the settings of the breakpoint at an address, so that they are restored on restart
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the process map cannot be read.
+    fn remember_bp_settings(&mut self, addr: Addr) -> Result<()> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let (kind, settings) = if let Some(bp) = dbge.breakpoints.get(&addr) {
+            (BreakpointKind::Software, bp.settings())
+        } else if let Some(bp) = dbge.hw_breakpoints.get(&addr) {
+            (BreakpointKind::Hardware, bp.settings())
+        } else {
+            return Ok(());
+        };
+        if let Some(location) = dbge.get_process_map()?.module_offset(addr) {
+            self.breakpoint_locations
+                .insert(location, (kind, settings.clone()));
+        }
+        Ok(())
+    }
+
+    /// Returns the settings of the software or hardware breakpoint at an address to change them
+    ///
+    /// # Errors
+    ///
+    /// This function fails if there is no debuggee or no breakpoint at `addr`.
+    fn bp_settings_mut(&mut self, addr: Addr) -> Result<&mut BreakpointSettings> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        if let Some(bp) = dbge.breakpoints.get_mut(&addr) {
+            return Ok(bp.settings_mut());
+        }
+        dbge.hw_breakpoints
+            .get_mut(&addr)
+            .map(HardwareBreakpoint::settings_mut)
+            .ok_or(DebuggerError::NoSuchBreakpoint(addr))
+    }
+
+    /// Checks if the debuggee should stop at the breakpoint the selected thread just hit
+    ///
+    /// This is the case if the breakpoint has no [`Condition`] or the condition is true, the
+    /// hit is not [ignored](Breakpoint::hit) and the breakpoint has no commands. If the condition
+    /// cannot be evaluated, for example because a variable is not in scope, the debuggee stops
+    /// too, so that the user can fix the condition. The commands of the breakpoint are run here.
+    /// A temporary breakpoint is removed after its first hit that is not ignored.
+    ///
+    /// # Parameters
+    ///
+    /// * `hardware` - Whether a [`HardwareBreakpoint`] was hit, which stops before the
+    ///   instruction instead of after the `INT3`
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the registers of the selected thread cannot be read, the feedback
+    /// of the commands cannot be shown or a temporary breakpoint cannot be removed.
+    fn should_stop_at_bp(&mut self, hardware: bool) -> Result<bool> {
+        if hardware {
+            let bp_addr = self.get_current_addr()?;
+            return self.check_bp_hit(bp_addr, true);
+        }
+
+        // the condition and the commands see the thread at the breakpoint, not in the middle of
+        // the instruction after the `INT3`
+        let bp_addr = self.get_current_addr()? - 1;
+        let rewound = self.is_enabled_bp(bp_addr)?;
+        if rewound {
+            self.set_reg(Register::rip, bp_addr.into())?;
+        }
+        let stop = self.check_bp_hit(bp_addr, false);
+        // stopping and resuming expect the thread right after the `INT3`, unless the breakpoint
+        // is gone and the original instruction is next
+        if rewound && self.is_enabled_bp(bp_addr)? {
+            self.set_reg(Register::rip, (bp_addr + 1).into())?;
+        }
+        stop
+    }
+
+    /// Checks if there is an enabled software breakpoint at an address
+    ///
+    /// # Errors
+    ///
+    /// This function fails if there is no debuggee.
+    fn is_enabled_bp(&self, addr: Addr) -> Result<bool> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        Ok(dbge
+            .breakpoints
+            .get(&addr)
+            .is_some_and(Breakpoint::is_enabled))
+    }
+
+    /// Counts a hit of the breakpoint at `bp_addr`, see [`Self::should_stop_at_bp`]
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the feedback of the commands cannot be shown or a temporary
+    /// breakpoint cannot be removed.
+    fn check_bp_hit(&mut self, bp_addr: Addr, hardware: bool) -> Result<bool> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let settings = if hardware {
+            dbge.hw_breakpoints
+                .get(&bp_addr)
+                .map(HardwareBreakpoint::settings)
+        } else {
+            dbge.breakpoints
+                .get(&bp_addr)
+                .filter(|bp| bp.is_enabled())
+                .map(Breakpoint::settings)
+        };
+        let Some(settings) = settings else {
+            return Ok(true);
+        };
+
+        if let Some(condition) = &settings.condition {
+            match condition.evaluate(|operand| self.condition_operand(operand)) {
+                Ok(true) => trace!("condition {condition} of breakpoint at {bp_addr} is true"),
+                Ok(false) => {
+                    trace!("condition {condition} of breakpoint at {bp_addr} is false");
+                    return Ok(false);
+                }
+                Err(e) => {
+                    warn!(
+                        "could not evaluate condition {condition} of breakpoint at {bp_addr}: {e}"
+                    );
+                }
+            }
+        }
+
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        let hit = if hardware {
+            dbge.hw_breakpoints
+                .get_mut(&bp_addr)
+                .map(HardwareBreakpoint::hit)
+        } else {
+            dbge.breakpoints.get_mut(&bp_addr).map(Breakpoint::hit)
+        };
+        let Some(hit) = hit else {
+            return Ok(true);
+        };
+        let settings = self.bp_settings_mut(bp_addr)?;
+        if !hit {
+            debug!(
+                "ignoring hit of breakpoint at {bp_addr}, {} more to ignore",
+                settings.ignore_count
+            );
+            self.remember_bp_settings(bp_addr)?;
+            return Ok(false);
+        }
+        let temporary = settings.temporary;
+        let commands = settings.commands.clone();
+
+        if !commands.is_empty() {
+            self.run_bp_commands(bp_addr, &commands)?;
+        }
+        if temporary {
+            info!("removing temporary breakpoint at {bp_addr}");
+            self.del_bp(bp_addr)?;
+        }
+        Ok(commands.is_empty())
+    }
+
+    /// Reads the value of an [`Operand`] of a [`Condition`] from the debuggee
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the register, memory or variable cannot be read.
+    fn condition_operand(&self, operand: &Operand) -> Result<i64> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        Ok(match operand {
+            Operand::Value(v) => *v,
+            Operand::Register(r) => dbge.get_reg(dbge.selected_thread(), *r)? as i64,
+            Operand::Memory(addr) => dbge.read_word(*addr)? as i64,
+            Operand::Variable(name) => {
+                let (_, symbol, frame_info) = self.prepare_variable_access(name)?;
+                let encoding = match dbge.get_type_for_symbol(&symbol)? {
+                    Some(datatype) => dbge.strip_typedefs(datatype)?.base_encoding(),
+                    None => None,
+                };
+                variable_to_i64(&dbge.var_read(&symbol, &frame_info)?, encoding)?
+            }
+        })
+    }
+
+    /// Removes a breakpoint at the specified address
+    ///
+    /// If the selected thread is stopped right after the removed breakpoint, its instruction
+    /// pointer is moved back onto the address of the breakpoint.
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The address to remove the breakpoint from
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the breakpoint was removed successfully
+    /// * `Err(DebuggerError)` - If there was an error removing the breakpoint
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The breakpoint could not be disabled
+    /// - [Register] access fails
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::addr::Addr;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Remove a breakpoint at address 0x1000
+    /// debugger.del_bp(Addr::from(0x1000usize)).unwrap();
+    ///
+    /// // Remove a breakpoint at the relative address 0x1000
+    /// let base = debugger.get_current_addr().unwrap();
+    /// debugger.del_bp(base + 0x1000).unwrap();
+    ///
+    /// # }}
+    /// ```
+    pub fn del_bp(&mut self, addr: Addr) -> Result<Feedback> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+
+        if let Some(location) = dbge.get_process_map()?.module_offset(addr) {
+            self.breakpoint_locations.remove(&location);
+        }
+
+        if dbge.del_hw_breakpoint(addr)? {
+            trace!("removed the hardware breakpoint at {addr}");
+        } else if dbge
+            .breakpoints
+            .get(&addr)
+            .is_some_and(|bp| !bp.is_internal())
+        {
+            dbge.breakpoints.remove(&addr); // gets disabled on dropping
+            dbge.restore_loader_breakpoint(addr)?;
+            if self.get_current_addr()? == addr + 1 {
+                // we stopped at this breakpoint, without it we would continue in the middle of
+                // the instruction
+                trace!("stopped after the deleted breakpoint, setting rip back to {addr}");
+                self.set_reg(Register::rip, addr.into())?;
+            }
+        } else {
+            warn!("removed a breakpoint at {addr:x?} that did not exist");
+        }
+
+        Ok(Feedback::Ok)
+    }
+
+    /// Performs a single, atomic step of exactly one instruction through the selected thread
+    ///
+    /// All other threads stay stopped.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the step was successful
+    /// * `Err(DebuggerError)` - If there was an error during stepping
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - ptrace's step operation fails
+    fn atomic_single_step(&mut self) -> Result<()> {
+        let sig = self.take_last_status();
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        let tid = dbge.selected_thread();
+        if let Some(thread) = dbge.threads.get_mut(&tid) {
+            dbge.debug_registers.apply(thread)?;
+        }
+        dbge.set_resume_flag(tid, true)?;
+
+        // FIXME: this is probably noticeable
+        if let Err(e) = ptrace::step(tid, sig) {
+            error!("could not do atomic step: {e}");
+            return Err(e.into());
+        }
+        if let Some(thread) = dbge.threads.get_mut(&tid) {
+            thread.state = ThreadState::Stepping;
+            // stepping does not report the exit of a syscall that was already entered
+            thread.in_syscall = false;
+        }
+
+        Ok(())
+    }
+
+    /// Steps a single instruction in the debuggee
+    ///
+    /// This function advances execution by a single instruction, taking
+    /// care to handle breakpoints appropriately.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the step was successful
+    /// * `Err(DebuggerError)` - If there was an error during stepping
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - ptrace operations fail
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Step through one instruction
+    /// debugger.single_step().unwrap();
+    ///
+    /// // Step through multiple instructions
+    /// for _ in 0..5 {
+    ///     debugger.single_step().unwrap();
+    /// }
+    ///
+    /// # }}
+    /// ```
+    pub fn single_step(&mut self) -> Result<Feedback> {
+        if self.go_back_step_over_bp()? {
+            info!("breakpoint before, caught up and continueing with single step");
+        }
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+
+        let maybe_bp_addr: Addr = self.get_current_addr()?;
+        if dbge.breakpoints.contains_key(&maybe_bp_addr) {
+            trace!("step over instruction with breakpoint");
+            self.dse(maybe_bp_addr)?;
+            if let Some(hit) = self.take_stepped_watchpoint_hit()? {
+                return Ok(Feedback::Watchpoint(hit));
+            }
+        } else {
+            trace!("step regular instruction");
+            self.atomic_single_step()?;
+            let feedback = self.wait_signal()?;
+            if matches!(
+                feedback,
+                Feedback::Fork(_) | Feedback::Exec(_) | Feedback::Watchpoint(_)
+            ) {
+                // the step ended in a ptrace event, the UI needs to know about it
+                return Ok(feedback);
+            }
+        }
+        trace!("now at {:018x}", self.get_reg(Register::rip)?);
+
+        Ok(Feedback::Ok)
+    }
+
+    /// Steps out of the current function
+    ///
+    /// The return address and the stack frame are found with the
+    /// [call frame information](crate::callframe), so this also works in prologues and in code
+    /// without frame pointers. This function sets a temporary breakpoint at the return address
+    /// and continues execution until that breakpoint is hit by this frame.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the step-out was successful, or stopped at a breakpoint
+    /// * `Ok(Feedback)` - If something else stopped the debuggee first, like a watchpoint or its
+    ///   exit
+    /// * `Err(DebuggerError)` - If there was an error during step-out
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - No valid return address of the current function can be found
+    /// - The current function is main (cannot step out)
+    /// - Could not read or write a [Register].
+    /// - Could not read from memory.
+    /// - Could not set or delete a [Breakpoint].
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running and in a function
+    /// #
+    /// // Step out of the current function
+    /// debugger.step_out().unwrap();
+    ///
+    /// # }}
+    /// ```
+    pub fn step_out(&mut self) -> Result<Feedback> {
+        Ok(self.leave_function()?.1.unwrap_or(Feedback::Ok))
+    }
+
+    /// Steps out of the current function and reports the value it returned
+    ///
+    /// Like [`Self::step_out`], but once the function has returned, its return value is decoded
+    /// with its DWARF return type, see [`crate::returnvalue`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::ReturnValue)` - If the function returned
+    /// * `Ok(Feedback)` - If something else stopped the debuggee first, like a breakpoint in a
+    ///   called function or the exit of the debuggee
+    /// * `Err(DebuggerError)` - If there was an error during step-out, or the return value could
+    ///   not be decoded
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - No valid return address of the current function can be found
+    /// - The current function is main (cannot step out)
+    /// - The return type of the function is not supported
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::feedback::Feedback;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running and in a function
+    /// #
+    /// if let Feedback::ReturnValue(returned) = debugger.finish().unwrap() {
+    ///     println!("{returned}");
+    /// }
+    /// # }}
+    /// ```
+    pub fn finish(&mut self) -> Result<Feedback> {
+        let rip = self.get_current_addr()?;
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let function = dbge.get_function_by_addr(rip)?;
+
+        let (frame, stop) = self.leave_function()?;
+        if let Some(feedback) = stop {
+            // a breakpoint of the user at the return address is hit right when returning
+            let returned = matches!(feedback, Feedback::Ok)
+                && frame.return_addr.map(|addr| addr + 1) == Some(self.get_current_addr()?)
+                && Addr::from(self.get_reg(Register::rsp)?) == frame.cfa;
+            if !returned {
+                return Ok(feedback);
+            }
+        }
+
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let value = match &function {
+            Some(function) => dbge.return_value(function)?,
+            None => None,
+        };
+        Ok(Feedback::ReturnValue(ReturnValue {
+            function: function.and_then(|f| f.name().map(str::to_string)),
+            value,
+        }))
+    }
+
+    /// Continues until the debuggee reaches a [`Location`]
+    ///
+    /// Internal one-shot breakpoints are set at the addresses of the location: after the
+    /// prologue of a function, at the code of a source line, or at the return address of the
+    /// current function for [`Location::Caller`]. They are removed again when the debuggee
+    /// stops, also if it stopped for something else first. Breakpoints of the user stay as they
+    /// are.
+    ///
+    /// # Parameters
+    ///
+    /// * `location` - Where to run to
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::RunUntil)` - Why the debuggee stopped: it reached the location, hit
+    ///   another breakpoint, received a signal, or something else happened first
+    /// * `Err(DebuggerError)` - If there was an error continuing the debuggee
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The location cannot be resolved to an address
+    /// - The breakpoints could not be set or removed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::feedback::Feedback;
+    /// # use coreminer::location::{Location, StopReason};
+    /// # use std::path::PathBuf;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// let location = Location::Line(PathBuf::from("main.c"), 12);
+    /// if let Ok(Feedback::RunUntil(stop)) = debugger.run_until(&location) {
+    ///     if !matches!(stop.reason, StopReason::Reached(_)) {
+    ///         println!("{stop}");
+    ///     }
+    /// }
+    /// # }}
+    /// ```
+    pub fn run_until(&mut self, location: &Location) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let (targets, sp) = match location {
+            Location::Addr(addr) => (vec![*addr], None),
+            Location::Function(name) => (vec![dbge.resolve_function(name)?], None),
+            Location::Line(file, line) => {
+                let (found, addrs) = dbge.resolve_line(file, *line)?;
+                if found != *line {
+                    info!("line {line} has no code, running to line {found}");
+                }
+                (addrs, None)
+            }
+            Location::Caller => {
+                let (frame, return_addr) = self.current_frame()?;
+                (vec![return_addr], Some(frame.cfa))
+            }
+        };
+        debug!("running until {location} at {targets:?}");
+
+        let stop = self.run_to(&targets, sp)?;
+        let reason = match stop {
+            None => StopReason::Reached(self.get_current_addr()?),
+            Some(Feedback::Ok) => match self.hit_breakpoint()? {
+                Some(addr) if targets.contains(&addr) => StopReason::Reached(addr),
+                Some(addr) => StopReason::Breakpoint(addr),
+                None => {
+                    let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+                    let signo = ptrace::getsiginfo(dbge.selected_thread())?.si_signo;
+                    if signo == Signal::SIGTRAP as i32 {
+                        StopReason::Other(Box::new(Feedback::Ok))
+                    } else {
+                        StopReason::Signal(signo)
+                    }
+                }
+            },
+            Some(feedback) => StopReason::Other(Box::new(feedback)),
+        };
+
+        Ok(Feedback::RunUntil(UntilStop {
+            location: location.clone(),
+            reason,
+        }))
+    }
+
+    /// Finds the breakpoint that the selected thread has just hit, if any
+    ///
+    /// A software breakpoint leaves the thread after its `int3`, a hardware breakpoint before
+    /// the instruction.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the registers cannot be read.
+    fn hit_breakpoint(&self) -> Result<Option<Addr>> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let rip = self.get_current_addr()?;
+        if dbge
+            .breakpoints
+            .get(&(rip - 1))
+            .is_some_and(|bp| bp.is_enabled() && !bp.is_internal())
+        {
+            Ok(Some(rip - 1))
+        } else if dbge.hw_breakpoints.contains_key(&rip) {
+            Ok(Some(rip))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Continues until the current function returns to its caller, see [`Self::step_out`]
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((CallFrame, None))` - The frame of the function, which has returned
+    /// * `Ok((CallFrame, Some(Feedback)))` - If something else stopped the debuggee first
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the frame of the function cannot be found, the function is
+    /// `main`, or the debuggee cannot be continued.
+    fn leave_function(&mut self) -> Result<(CallFrame, Option<Feedback>)> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        {
+            let a = dbge.get_function_by_addr(self.get_reg(Register::rip)?.into())?;
+            if let Some(s) = a {
+                debug!("step out in following function: {s:#?}");
+                if s.name() == Some("main") {
+                    error!("you're about to do something stupid: no stepping out of the earliest stack frame allowed");
+                    return Err(DebuggerError::StepOutMain);
+                }
+            } else {
+                warn!("did not find debug symbol for current address");
+            }
+        }
+
+        let (frame, return_addr) = self.current_frame()?;
+
+        // the stack pointer is the CFA again once the function has returned
+        let stop = self.run_to(&[return_addr], Some(frame.cfa))?;
+        Ok((frame, stop))
+    }
+
+    /// Finds the frame of the current function of the selected thread and its return address
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the frame cannot be found or has no return address.
+    fn current_frame(&self) -> Result<(CallFrame, Addr)> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+
+        // after a breakpoint was hit, the frame is the one at the breakpoint
+        let mut rip = self.get_current_addr()?;
+        if dbge
+            .breakpoints
+            .get(&(rip - 1))
+            .is_some_and(Breakpoint::is_enabled)
+        {
+            rip -= 1;
+        }
+        let frame = dbge.call_frame(rip)?;
+        let return_addr = frame
+            .return_addr
+            .ok_or(DebuggerError::NoReturnAddress(rip))?;
+        trace!("cfa: {}", frame.cfa);
+        trace!("ret_addr: {return_addr}");
+        Ok((frame, return_addr))
+    }
+
+    /// Temporarily disables a breakpoint, steps over it, and then re-enables it
+    ///
+    /// # Parameters
+    ///
+    /// * `here` - The address of the breakpoint
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the operation was successful
+    /// * `Err(DebuggerError)` - If there was an error
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - Breakpoint operations fail
+    /// - Step operations fail
+    fn dse(&mut self, here: Addr) -> Result<()> {
+        trace!("disabling the breakpoint");
+        self.debuggee
+            .as_mut()
+            .unwrap()
+            .breakpoints
+            .get_mut(&here)
+            .unwrap()
+            .disable()?;
+
+        trace!("atomic step");
+        self.atomic_single_step()?;
+        trace!("waiting");
+        let feedback = self
+            .wait_signal()
+            .inspect_err(|e| warn!("weird wait_signal error: {e}"))?;
+        if let Feedback::Watchpoint(hit) = feedback {
+            // reported by the caller, see take_stepped_watchpoint_hit
+            let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+            let tid = dbge.selected_thread();
+            if let Some(thread) = dbge.threads.get_mut(&tid) {
+                thread.pending_watchpoint = Some(hit);
+            }
+        }
+        trace!("enable stepped over bp again");
+        self.debuggee
+            .as_mut()
+            .unwrap()
+            .breakpoints
+            .get_mut(&here)
+            .unwrap()
+            .enable()?;
+        trace!("dse done");
+
+        Ok(())
+    }
+
+    /// Checks if we need to restore an instruction pointer after hitting a breakpoint
+    ///
+    /// When a breakpoint is hit, the instruction pointer is just after the INT3 instruction.
+    /// This function checks if we need to move the instruction pointer back to the breakpoint
+    /// address and execute the original instruction. If so, it does that.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - True if the IP was adjusted and a breakpoint was stepped over
+    /// * `Err(DebuggerError)` - If there was an error
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - Register operations fail
+    /// - Breakpoint operations fail
+    #[allow(clippy::missing_panics_doc)] // this function cant panic
+    pub fn go_back_step_over_bp(&mut self) -> Result<bool> {
+        if self.debuggee.is_none() {
+            return Err(DebuggerError::NoDebugee);
+        }
+
+        let maybe_bp_addr: Addr = self.get_current_addr()? - 1;
+        trace!("Checkinf if {maybe_bp_addr} had a breakpoint");
+
+        if self
+            .debuggee
+            .as_mut()
+            // safe because we check earlier, needed because we use a mutable reference
+            // and can only drop in place this way
+            .unwrap()
+            .breakpoints
+            .get_mut(&maybe_bp_addr)
+            .is_some_and(|a| a.is_enabled())
+        {
+            let here = maybe_bp_addr;
+            trace!("set register to {here}");
+            self.set_reg(Register::rip, here.into())?;
+
+            self.dse(here)?;
+            Ok(true)
+        } else {
+            trace!("breakpoint is disabled or does not exist, doing nothing");
+            Ok(false)
+        }
+    }
+
+    /// Takes the watchpoint hit of the selected thread that happened while it stepped over a
+    /// breakpoint
+    ///
+    /// # Errors
+    ///
+    /// This function fails if there is no debuggee.
+    fn take_stepped_watchpoint_hit(&mut self) -> Result<Option<WatchpointHit>> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        let tid = dbge.selected_thread();
+        Ok(dbge
+            .threads
+            .get_mut(&tid)
+            .and_then(|t| t.pending_watchpoint.take()))
+    }
+
+    /// Disassembles memory at the specified address
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The starting address
+    /// * `len` - The number of bytes to disassemble
+    /// * `literal` - Whether to show literal bytes instead of original code (including breakpoint instructions)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Disassembly)` - The disassembly result
+    /// * `Err(DebuggerError)` - If there was an error during disassembly
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - Memory cannot be read
+    /// - Disassembly fails
+    ///
+    /// # Panics
+    ///
+    /// If a [Breakpoint] is enabled but has no saved data, this will panic.
+    /// If a [Breakpoint] was found before making the [Disassembly], but the same breakpoint does
+    /// not exist after the [Disassembly] was created, this will also panic.
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::addr::Addr;
+    /// # use coreminer::feedback::Feedback;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Disassemble 16 bytes at address 0x1000
+    /// if let Ok(Feedback::Disassembly(disasm)) = debugger.disassemble_at(Addr::from(0x1000usize), 16, false) {
+    ///     for (addr, raw, content, has_bp) in disasm.inner() {
+    ///         println!("{}: {} {}", addr, if *has_bp { "*" } else { " " }, content[0].0);
+    ///     }
+    /// }
+    ///
+    /// # }}
+    /// ```
+    pub fn disassemble_at(&self, addr: Addr, len: usize, literal: bool) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+
+        let t = dbge.disassemble(addr, len, literal)?;
+
+        Ok(Feedback::Disassembly(t))
+    }
+
+    /// Searches for symbols by name
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - The name to search for
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Symbols)` - The matching symbols
+    /// * `Err(DebuggerError)` - If there was an error during search
+    ///
+    /// Note: If the executable that is being debugged has no DWARF information (was stripped), this will always
+    /// return no symbols.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debug information is not loaded
+    /// - Symbol information is not available
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::feedback::Feedback;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Find the "main" function
+    /// if let Ok(Feedback::Symbols(symbols)) = debugger.get_symbol_by_name("main") {
+    ///     for symbol in symbols {
+    ///         println!("Found main at: {:?}", symbol.low_addr());
+    ///     }
+    /// }
+    ///
+    /// # }}
+    /// ```
+    pub fn get_symbol_by_name(&self, name: impl Display) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+
+        let symbols: Vec<OwnedSymbol> = dbge.get_symbol_by_name(name)?;
+        Ok(Feedback::Symbols(symbols))
+    }
+
+    /// Handles a SIGTRAP signal from the debuggee
+    ///
+    /// # Parameters
+    ///
+    /// * `sig` - The signal
+    /// * `siginfo` - The signal information
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the signal was handled successfully
+    /// * `Err(DebuggerError)` - If there was an error handling the signal
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    pub fn handle_sigtrap(
+        &mut self,
+        sig: nix::sys::signal::Signal,
+        siginfo: nix::libc::siginfo_t,
+    ) -> Result<()> {
+        #[allow(unused_mut)] // used for plugins
+        let mut return_early = false;
+
+        for_hooks!(
+            for hook[EPreSigtrap] in self {
+                trace!("process hook {}", hook.name());
+                self.hook_feedback_loop(hook.name(), |f| {
+                    match hook.inner_mut().pre_handle_sigtrap(f, &siginfo, &sig) {
+                        Ok((status, ret_e)) => {
+                            return_early |= ret_e;
+                            Ok(status)
+                        },
+                        Err(e) => Err(e)
+                    }
+                })?;
+            }
+        );
+
+        if return_early {
+            return Ok(());
+        }
+
+        info!("debugee received {}: {}", sig.as_str(), siginfo.si_code);
+
+        match siginfo.si_code {
+            SI_KERNEL => trace!("SI_KERNEL"), // we don't know what do do?
+            TRAP_BRKPT => {
+                trace!("TRAP_BRKPT");
+            }
+            TRAP_HWBKPT => trace!("TRAP_HWBKPT"), // hardware breakpoint
+            TRAP_TRACE => trace!("TRAP_TRACE"),   // single stepping
+            _ => warn!("Strange SIGTRAP code: {}", siginfo.si_code),
+        }
+
+        Ok(())
+    }
+
+    /// Handles important signals from the debuggee
+    ///
+    /// These are the signals whose [`SignalPolicy`] says to print them. The signal is delivered
+    /// when the debuggee is resumed, if the policy says to pass it.
+    ///
+    /// A `SIGSEGV` for a page that is protected for a software
+    /// [`Watchpoint`](crate::watchpoint::Watchpoint) is never delivered. The access of the
+    /// selected thread is emulated instead, and reported if a watchpoint cares about it.
+    ///
+    /// # Parameters
+    ///
+    /// * `sig` - The signal
+    /// * `siginfo` - The signal information
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Feedback::Ok))` - If the signal was handled successfully
+    /// * `Ok(Some(Feedback::Watchpoint))` - If the signal was an access to a watched region
+    /// * `Ok(None)` - If the signal was an access to unwatched memory on a protected page
+    /// * `Err(DebuggerError)` - If there was an error handling the signal
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The access to a protected page cannot be emulated
+    pub fn handle_important_signal(
+        &mut self,
+        sig: Signal,
+        siginfo: nix::libc::siginfo_t,
+    ) -> Result<Option<Feedback>> {
+        if let Some(fault) = self.guarded_fault(&siginfo) {
+            let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+            let tid = dbge.selected_thread();
+            trace!("thread {tid} accessed the protected page of {fault}");
+            let hit = dbge.emulate_guarded_access(tid, fault)?;
+            if let Some(hit) = &hit {
+                info!("thread {tid} hit the watchpoint at {}", hit.addr);
+            }
+            return Ok(hit.map(Feedback::Watchpoint));
+        }
+        info!("debugee received {}: {}", sig.as_str(), siginfo.si_code);
+        self.last_signal = self.signal_policies.get(sig).pass.then_some(sig);
+        Ok(Some(Feedback::Ok))
+    }
+
+    /// Checks if a signal is a `SIGSEGV` for a page that is protected for a software
+    /// [`Watchpoint`](crate::watchpoint::Watchpoint)
+    ///
+    /// # Returns
+    ///
+    /// The address that could not be accessed, if it is on a protected page.
+    fn guarded_fault(&self, siginfo: &nix::libc::siginfo_t) -> Option<Addr> {
+        if siginfo.si_signo != Signal::SIGSEGV as i32 || siginfo.si_code != SEGV_ACCERR {
+            return None;
+        }
+        // SAFETY: the kernel sets the address for a SIGSEGV with SEGV_ACCERR
+        let fault = Addr::from(unsafe { siginfo.si_addr() });
+        self.debuggee
+            .as_ref()
+            .is_some_and(|dbge| dbge.is_guarded(fault))
+            .then_some(fault)
+    }
+
+    /// Handles a thread that might have hit the internal breakpoint for
+    /// [shared libraries](crate::sharedlib)
+    ///
+    /// The libraries are read again and the breakpoints that wait for them are set, see
+    /// [`Self::set_pending_breakpoints`]. The [`LibraryEvent`] is shown by the [`DebuggerUI`]
+    /// right away, as the debuggee continues after it.
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread that hit a breakpoint
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - If the thread hit only the internal breakpoint, so that it should continue
+    /// * `Ok(false)` - If the thread hit another breakpoint, or the user has a breakpoint at the
+    ///   same address
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the libraries cannot be read, a pending breakpoint cannot be set or
+    /// the [`LibraryEvent`] cannot be shown.
+    fn handle_loader_breakpoint(&mut self, tid: Pid) -> Result<bool> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        let bp_addr = Addr::from(crate::get_reg(tid, Register::rip)?) - 1;
+        if dbge.loader_breakpoint() != Some(bp_addr) {
+            return Ok(false);
+        }
+        trace!("thread {tid} hit the breakpoint for the dynamic loader");
+
+        if let Some(mut event) = dbge.library_event(tid)? {
+            if !event.loaded.is_empty() {
+                self.set_pending_breakpoints(&mut event)?;
+            }
+            if !event.is_empty() {
+                self.ui.notify(Feedback::Library(event))?;
+            }
+        }
+
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        Ok(!dbge
+            .breakpoints
+            .get(&bp_addr)
+            .is_some_and(|bp| !bp.is_internal()))
+    }
+
+    /// Sets the breakpoints that wait for shared libraries to be loaded
+    ///
+    /// These are the [pending breakpoints](Self::set_bp_at_symbol) of functions that are found
+    /// now, and the breakpoints in files that were not mapped when the debuggee was
+    /// [restarted](Self::restart) or that were unloaded and loaded again.
+    ///
+    /// # Parameters
+    ///
+    /// * `event` - The event of the loaded libraries, the pending breakpoints that were set are
+    ///   added to it
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the process map cannot be read or a breakpoint cannot be set.
+    fn set_pending_breakpoints(&mut self, event: &mut LibraryEvent) -> Result<()> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let map = dbge.get_process_map()?;
+        for (location, (kind, settings)) in self.breakpoint_locations.clone() {
+            let Some(addr) = map.resolve(&location) else {
+                continue;
+            };
+            let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+            if !dbge.libraries().iter().any(|lib| lib.contains(addr))
+                || dbge
+                    .breakpoints
+                    .get(&addr)
+                    .is_some_and(|bp| !bp.is_internal())
+                || dbge.hw_breakpoints.contains_key(&addr)
+            {
+                continue;
+            }
+            debug!("restoring breakpoint at {location} to {addr}");
+            self.set_bp_with(addr, kind, settings)?;
+        }
+
+        for name in std::mem::take(&mut self.pending_breakpoints) {
+            let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+            match dbge.resolve_function(&name) {
+                Ok(addr) => {
+                    info!("breakpoint for {name} at {addr}");
+                    if !dbge
+                        .breakpoints
+                        .get(&addr)
+                        .is_some_and(|bp| !bp.is_internal())
+                    {
+                        self.set_bp(addr)?;
+                    }
+                    event.breakpoints.push((name, addr));
+                }
+                Err(DebuggerError::FunctionNotFound(_)) => self.pending_breakpoints.push(name),
+                Err(e) => {
+                    warn!("could not resolve the pending breakpoint for {name}: {e}");
+                    self.pending_breakpoints.push(name);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles other signals from the debuggee
+    ///
+    /// These are the signals whose [`SignalPolicy`] says to stop but not to print them. The
+    /// signal is delivered when the debuggee is resumed, if the policy says to pass it.
+    ///
+    /// # Parameters
+    ///
+    /// * `sig` - The signal
+    /// * `siginfo` - The signal information
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the signal was handled successfully
+    /// * `Err(DebuggerError)` - If there was an error handling the signal
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    pub fn handle_other_signal(
+        &mut self,
+        sig: Signal,
+        siginfo: nix::libc::siginfo_t,
+    ) -> Result<()> {
+        trace!("handle other");
+        debug!("debugee received {}: {}", sig.as_str(), siginfo.si_code);
+        self.last_signal = self.signal_policies.get(sig).pass.then_some(sig);
+        Ok(())
+    }
+
+    /// Steps into a function call
+    ///
+    /// This function steps through instructions until a call instruction is found,
+    /// then steps into that function. A called function on the [skip list](crate::skiplist)
+    /// runs until it returns, and the search goes on after the call.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the step-into was successful
+    /// * `Err(DebuggerError)` - If there was an error during step-into
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - No call instruction is found
+    /// - Step operations fail
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Step into the next function call
+    /// debugger.step_into().unwrap();
+    ///
+    /// # }}
+    /// ```
+    #[allow(clippy::missing_panics_doc)] // this function cannot panic
+    pub fn step_into(&mut self) -> Result<Feedback> {
+        if self.debuggee.is_none() {
+            return Err(DebuggerError::NoDebugee);
+        }
+        self.go_back_step_over_bp()?;
+
+        loop {
+            let rip: Addr = (self.get_reg(Register::rip)?).into();
+            let disassembly: Disassembly =
+                // unwrap is safe because we check earlier, needed because we need the mutable
+                // reference
+                self.debuggee.as_ref().unwrap().disassemble(rip, 8, true)?;
+            let next_instruction = &disassembly.inner()[0];
+            let operator = next_instruction.2[0].clone();
+
+            if operator.1 != FormatterTextKind::Mnemonic {
+                error!("could not read operator from disassembly");
+            }
+            // PERF: this is very inefficient :/ maybe remove the autostepper or work with continue
+            // somehow
+            if operator.0.trim() == "call" {
+                let sp: Addr = self.get_reg(Register::rsp)?.into();
+                let flow = self.debuggee.as_ref().unwrap().instruction_flow(rip)?;
+                self.single_step()?;
+                let target = self.get_current_addr()?;
+                match flow {
+                    InstructionFlow::Call { return_addr }
+                        if target != return_addr && self.is_skipped(target)? =>
+                    {
+                        // look for the next call after the skipped one
+                        if let Some(feedback) = self.run_to(&[return_addr], Some(sp))? {
+                            return Ok(feedback);
+                        }
+                    }
+                    _ => break,
+                }
+                continue;
+            }
+            self.single_step()?;
+        }
+
+        Ok(Feedback::Ok)
+    }
+
+    /// Steps over a function call
+    ///
+    /// This function combines [`Self::step_into`] and [`Self::step_out`] to step over a function call.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the step-over was successful
+    /// * `Err(DebuggerError)` - If there was an error during step-over
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - Step operations fail
+    pub fn step_over(&mut self) -> Result<Feedback> {
+        self.go_back_step_over_bp()?;
+
+        self.step_into()?;
+        self.step_out()
+    }
+
+    /// Steps to the next source line, into called functions
+    ///
+    /// The selected thread is stepped until it reaches the start of a different source line, as
+    /// recorded in the [line table](crate::linetable) with `is_stmt`. A called function that has
+    /// line information is entered and the step stops after its prologue. Other calls, like
+    /// calls into shared libraries, are stepped over like with [`Self::next_line`].
+    ///
+    /// Called functions on the [skip list](crate::skiplist) are stepped over too.
+    ///
+    /// If the current function returns, the step stops in the caller, right after the call.
+    /// If the caller is on the skip list, it runs until the code of the user is reached again.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the step reached a new line, or stopped at a breakpoint
+    /// * `Ok(Feedback)` - If something else stopped the step, like a watchpoint or the exit of
+    ///   the debuggee
+    /// * `Err(DebuggerError)` - If there was an error during stepping
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The current address has no line information
+    /// - The code cannot be read or the debuggee cannot be stepped
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running and stopped in main
+    /// #
+    /// debugger.step_line().unwrap();
+    /// # }}
+    /// ```
+    pub fn step_line(&mut self) -> Result<Feedback> {
+        self.line_step(true)
+    }
+
+    /// Steps to the next source line, over called functions
+    ///
+    /// Like [`Self::step_line`], but calls are not entered: the callee runs until it returns,
+    /// with an internal breakpoint at the return address. A breakpoint in the callee still
+    /// stops the step.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the step reached a new line, or stopped at a breakpoint
+    /// * `Ok(Feedback)` - If something else stopped the step, like a watchpoint or the exit of
+    ///   the debuggee
+    /// * `Err(DebuggerError)` - If there was an error during stepping
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The current address has no line information
+    /// - The code cannot be read or the debuggee cannot be stepped
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running and stopped in main
+    /// #
+    /// for _ in 0..3 {
+    ///     debugger.next_line().unwrap();
+    /// }
+    /// # }}
+    /// ```
+    pub fn next_line(&mut self) -> Result<Feedback> {
+        self.line_step(false)
+    }
+
+    /// Steps the selected thread to the start of a new source line, see [`Self::step_line`]
+    /// and [`Self::next_line`]
+    ///
+    /// # Parameters
+    ///
+    /// * `into` - Whether to enter called functions that have line information
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the current address has no line information, or the debuggee
+    /// cannot be stepped.
+    fn line_step(&mut self, into: bool) -> Result<Feedback> {
+        self.rewind_breakpoint_hit()?;
+        let start = self.get_current_addr()?;
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let line = dbge
+            .lines()
+            .row_for_addr(start)
+            .filter(|row| row.line != 0)
+            .cloned()
+            .ok_or(DebuggerError::NoLineInfo(start))?;
+        debug!("stepping from line {} of {:?}", line.line, line.file);
+
+        loop {
+            let rip = self.get_current_addr()?;
+            let sp: Addr = self.get_reg(Register::rsp)?.into();
+            let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+            let flow = dbge.instruction_flow(rip)?;
+
+            if let Some(feedback) = self.step_instruction()? {
+                return Ok(feedback);
+            }
+            match flow {
+                InstructionFlow::Call { return_addr } => {
+                    let target = self.get_current_addr()?;
+                    if into && self.has_line_info(target)? && !self.is_skipped(target)? {
+                        trace!("stepped into the function at {target}");
+                        return self.step_prologue(target);
+                    }
+                    if target != return_addr {
+                        if let Some(feedback) = self.run_to(&[return_addr], Some(sp))? {
+                            return Ok(feedback);
+                        }
+                    }
+                }
+                InstructionFlow::Return => {
+                    trace!("returned from the function of line {}", line.line);
+                    return Ok(self.leave_skipped_code()?.unwrap_or(Feedback::Ok));
+                }
+                InstructionFlow::Other => (),
+            }
+
+            let rip = self.get_current_addr()?;
+            let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+            if dbge.lines().row_for_addr(rip).is_some_and(|row| {
+                row.addr == rip
+                    && row.is_stmt
+                    && row.line != 0
+                    && (row.line != line.line || row.file != line.file)
+            }) {
+                trace!("reached a new line at {rip}");
+                return Ok(Feedback::Ok);
+            }
+        }
+    }
+
+    /// Steps exactly one instruction of the selected thread, a breakpoint at the instruction is
+    /// stepped over
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(None)` - If the instruction was executed
+    /// * `Ok(Some(Feedback))` - If the step ended in an event the UI needs to know about, like a
+    ///   watchpoint hit or the exit of the debuggee
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the debuggee cannot be stepped.
+    fn step_instruction(&mut self) -> Result<Option<Feedback>> {
+        let rip = self.get_current_addr()?;
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        if dbge
+            .breakpoints
+            .get(&rip)
+            .is_some_and(Breakpoint::is_enabled)
+        {
+            self.dse(rip)?;
+            return Ok(self
+                .take_stepped_watchpoint_hit()?
+                .map(Feedback::Watchpoint));
+        }
+
+        self.atomic_single_step()?;
+        match self.wait_signal()? {
+            Feedback::Ok => Ok(None),
+            feedback => Ok(Some(feedback)),
+        }
+    }
+
+    /// Moves the instruction pointer of the selected thread back onto a software breakpoint it
+    /// has just hit, so that the original instruction is executed next
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the registers cannot be read or written.
+    fn rewind_breakpoint_hit(&mut self) -> Result<()> {
+        let bp_addr = self.get_current_addr()? - 1;
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        if dbge
+            .breakpoints
+            .get(&bp_addr)
+            .is_some_and(Breakpoint::is_enabled)
+        {
+            trace!("stopped after the breakpoint at {bp_addr}, setting rip back");
+            self.set_reg(Register::rip, bp_addr.into())?;
+        }
+        Ok(())
+    }
+
+    /// Checks if the code at an address has source line information
+    ///
+    /// # Errors
+    ///
+    /// This function fails if there is no debuggee.
+    fn has_line_info(&self, addr: Addr) -> Result<bool> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        Ok(dbge
+            .lines()
+            .row_for_addr(addr)
+            .is_some_and(|row| row.line != 0))
+    }
+
+    /// Checks if the code at an address is on the [skip list](crate::skiplist)
+    ///
+    /// A PLT stub of the executable is checked as the function in the shared library that it
+    /// jumps to.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if there is no debuggee, or the code at `addr` cannot be read.
+    fn is_skipped(&self, addr: Addr) -> Result<bool> {
+        if self.skip_list.is_empty() {
+            return Ok(false);
+        }
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let origin = match dbge.plt_target(addr)? {
+            // the library may know the function by an alias, like `_IO_printf` for `printf`
+            Some((name, code)) => CodeOrigin {
+                function: Some(name),
+                ..dbge.code_origin(code)
+            },
+            None => dbge.code_origin(addr),
+        };
+        if let Some(rule) = self.skip_list.matching(&origin) {
+            debug!("skipping the code at {addr} because of '{rule}': {origin:?}");
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Continues until the selected thread leaves the code on the
+    /// [skip list](crate::skiplist) it returned into, one function at a time
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(None)` - If the selected thread is not in skipped code
+    /// * `Ok(Some(Feedback))` - If something else stopped the debuggee first
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the frame of a skipped function cannot be found, or the debuggee
+    /// cannot be continued.
+    fn leave_skipped_code(&mut self) -> Result<Option<Feedback>> {
+        while self.is_skipped(self.get_current_addr()?)? {
+            let (frame, return_addr) = self.current_frame()?;
+            trace!("returned into skipped code, continuing to {return_addr}");
+            if let Some(feedback) = self.run_to(&[return_addr], Some(frame.cfa))? {
+                return Ok(Some(feedback));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Steps through the prologue of a function that was just entered
+    ///
+    /// # Parameters
+    ///
+    /// * `entry` - The address the function was entered at
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the code of the function cannot be read or the debuggee cannot
+    /// be stepped.
+    fn step_prologue(&mut self, entry: Addr) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let high = dbge
+            .get_function_by_addr(entry)?
+            .and_then(|function| function.high_addr());
+        let body = dbge.skip_prologue(entry, high)?;
+        while (entry..body).contains(&self.get_current_addr()?) {
+            if let Some(feedback) = self.step_instruction()? {
+                return Ok(feedback);
+            }
+        }
+        Ok(Feedback::Ok)
+    }
+
+    /// Continues until the debuggee reaches one of the target addresses
+    ///
+    /// An internal breakpoint is set at each target that has no breakpoint yet. When a function
+    /// returns to a target, the breakpoint is also hit when a recursive call returns there, so
+    /// the stack pointer is compared to the one at the `call` to find the right return. The
+    /// internal breakpoints are removed again however the debuggee stopped.
+    ///
+    /// # Parameters
+    ///
+    /// * `targets` - The addresses to stop at
+    /// * `sp` - The stack pointer right before the `call`, if the targets are return addresses
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(None)` - If the selected thread reached a target and is at its address now
+    /// * `Ok(Some(Feedback))` - If something else stopped the debuggee first, like a breakpoint
+    ///   in a called function, or a breakpoint of the user at a target was hit
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the breakpoints cannot be set or removed, or the debuggee cannot
+    /// be continued.
+    fn run_to(&mut self, targets: &[Addr], sp: Option<Addr>) -> Result<Option<Feedback>> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let internal: Vec<Addr> = targets
+            .iter()
+            .copied()
+            .filter(|addr| {
+                !dbge.breakpoints.contains_key(addr) && !dbge.hw_breakpoints.contains_key(addr)
+            })
+            .collect();
+
+        let result = self
+            .set_internal_breakpoints(&internal)
+            .and_then(|()| self.wait_for_target(targets, sp, &internal));
+
+        if let Some(dbge) = self.debuggee.as_mut() {
+            for addr in &internal {
+                // a breakpoint of the user may have replaced it when a library was loaded
+                if dbge
+                    .breakpoints
+                    .get(addr)
+                    .is_some_and(Breakpoint::is_internal)
+                {
+                    dbge.breakpoints.remove(addr);
+                }
+            }
+            if let Ok(rip) = self.get_current_addr() {
+                if internal.iter().any(|addr| rip == *addr + 1) {
+                    self.set_reg(Register::rip, (rip - 1).into())?;
+                }
+            }
+        }
+        result
+    }
+
+    /// Sets enabled internal breakpoints, see [`Breakpoint::new_internal`]
+    ///
+    /// # Errors
+    ///
+    /// This function fails if a breakpoint cannot be enabled.
+    fn set_internal_breakpoints(&mut self, addrs: &[Addr]) -> Result<()> {
+        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+        for addr in addrs {
+            let mut bp = Breakpoint::new_internal(dbge.pid, *addr);
+            bp.enable()?;
+            dbge.breakpoints.insert(*addr, bp);
+        }
+        Ok(())
+    }
+
+    /// Continues until a breakpoint at one of the `targets` is hit with a stack pointer of at
+    /// least `sp`, see [`Self::run_to`]
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the debuggee cannot be continued.
+    fn wait_for_target(
+        &mut self,
+        targets: &[Addr],
+        sp: Option<Addr>,
+        internal: &[Addr],
+    ) -> Result<Option<Feedback>> {
+        loop {
+            let feedback = self.cont()?;
+            // the debuggee may have exited, then there are no registers to read
+            if !matches!(feedback, Feedback::Ok) {
+                return Ok(Some(feedback));
+            }
+            let rip = self.get_current_addr()?;
+            if !targets.iter().any(|addr| rip == *addr + 1) {
+                return Ok(Some(feedback));
+            }
+            if let Some(sp) = sp {
+                if Addr::from(self.get_reg(Register::rsp)?) < sp {
+                    trace!("a recursive call returned to {}, continuing", rip - 1);
+                    continue;
+                }
+            }
+            // a breakpoint of the user is reported as usual
+            return Ok((!internal.contains(&(rip - 1))).then_some(feedback));
+        }
+    }
+
+    /// Gets a backtrace of the current call stack of the selected thread
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Backtrace)` - The backtrace
+    /// * `Err(DebuggerError)` - If there was an error generating the backtrace
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - Stack unwinding fails
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::feedback::Feedback;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Get a backtrace of the call stack
+    /// if let Ok(Feedback::Backtrace(bt)) = debugger.backtrace() {
+    ///     for (i, frame) in bt.frames.iter().enumerate() {
+    ///         println!("#{} {} at {:?}", i, frame.name.as_deref().unwrap_or("??"), frame.addr);
+    ///     }
+    /// }
+    ///
+    /// # }}
+    /// ```
+    pub fn backtrace(&self) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+
+        let backtrace = dbge.backtrace()?;
+
+        Ok(Feedback::Backtrace(backtrace))
+    }
+
+    /// Gets the current instruction pointer address
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Addr)` - The current address
+    /// * `Err(DebuggerError)` - If there was an error getting the address
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - [Register] read fails
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Get the current instruction pointer
+    /// let curr_addr = debugger.get_current_addr().unwrap();
+    /// println!("Current IP: {}", curr_addr);
+    ///
+    /// # }}
+    /// ```
+    pub fn get_current_addr(&self) -> Result<Addr> {
+        Ok((self.get_reg(Register::rip)?).into())
+    }
+
+    /// Prepares for variable access by gathering necessary context
+    ///
+    /// # Parameters
+    ///
+    /// * `expression` - The variable expression to access
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((OwnedSymbol, OwnedSymbol, FrameInfo))` - Function, variable symbols and frame info
+    /// * `Err(DebuggerError)` - If preparation failed
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The current location is not in a function
+    /// - The variable is not found
+    /// - Frame information cannot be constructed
+    #[allow(clippy::missing_panics_doc)] // this function cant panic
+    pub fn prepare_variable_access(
+        &self,
+        expression: &VariableExpression,
+    ) -> Result<(OwnedSymbol, OwnedSymbol, FrameInfo)> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let rip: Addr = self.get_current_addr()?;
+
+        // Get current function
+        let current_function = match dbge.get_function_by_addr(rip)? {
+            Some(f) if f.frame_base().is_some() => f,
+            Some(_) => {
+                return Err(DebuggerError::AttributeDoesNotExist(
+                    gimli::DW_AT_frame_base,
+                ))
+            }
+            None => return Err(DebuggerError::NotInFunction),
+        };
+
+        // Find variable
+        let locals = dbge.get_local_variables(rip)?;
+        let vars = dbge.filter_expressions(&locals, expression)?;
+        let var = match vars.len() {
+            0 => return Err(DebuggerError::VarExprReturnedNothing(expression.clone())),
+            1 => vars[0].clone(),
+            _ => return Err(DebuggerError::AmbiguousVarExpr(expression.clone())),
+        };
+
+        // Build frame info
+        let cfa = dbge
+            .call_frame(rip)
+            .inspect_err(|e| warn!("could not find the frame of {rip}: {e}"))
+            .ok()
+            .map(|frame| frame.cfa);
+        let mut frame_info = FrameInfo::new(None, cfa);
+
+        let frame_base = dbge.parse_location(
+            current_function.frame_base().unwrap(), // safe: we check above if this is some
+            &frame_info,
+            current_function.encoding(),
+        )?;
+
+        let frame_base: Addr = match frame_base {
+            gimli::Location::Address { address } => address.into(),
+            other => unimplemented!(
+                "frame base DWARF location was not an address as expected: is {other:?}"
+            ),
+        };
+
+        frame_info.frame_base = Some(frame_base);
+
+        Ok((current_function, var, frame_info))
+    }
+
+    /// Reads the value of a variable
+    ///
+    /// # Parameters
+    ///
+    /// * `expression` - The variable name to read
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Variable)` - The variable value
+    /// * `Err(DebuggerError)` - If the variable could not be read
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The variable is not found
+    /// - The variable cannot be accessed
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::feedback::Feedback;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Read the value of a variable named "count"
+    /// if let Ok(Feedback::Variable(value)) = debugger.read_variable(&"count".to_string()) {
+    ///     println!("count = {:?}", value);
+    /// }
+    ///
+    /// # }}
+    /// ```
+    pub fn read_variable(&self, expression: &VariableExpression) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+
+        let (_, symbol, frame_info) = self.prepare_variable_access(expression)?;
+
+        let val = dbge.var_read(&symbol, &frame_info)?;
+
+        Ok(Feedback::Variable(val))
+    }
+
+    /// Writes a value to a variable
+    ///
+    /// # Parameters
+    ///
+    /// * `expression` - The variable name to write to
+    /// * `value` - The value to write
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Ok)` - If the write was successful
+    /// * `Err(DebuggerError)` - If the variable could not be written
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The variable is not found
+    /// - The variable cannot be accessed
+    /// - The value is incompatible with the variable type
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Set the value of a variable named "count" to 42
+    /// debugger.write_variable(&"count".to_string(), 42).unwrap();
+    ///
+    /// # }}
+    /// ```
+    pub fn write_variable(
+        &self,
+        expression: &VariableExpression,
+        value: impl Into<VariableValue>,
+    ) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+
+        let (_, var, frame_info) = self.prepare_variable_access(expression)?;
+
+        dbge.var_write(&var, &frame_info, &value.into())?;
+
+        Ok(Feedback::Ok)
+    }
+
+    /// Reads a single [Word] from memory at the specified address
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The address to read from
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Word)` - The value read from memory
+    /// * `Err(DebuggerError)` - If the memory could not be read
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - The memory address is invalid
+    /// - The memory cannot be accessed
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::addr::Addr;
+    /// # use coreminer::feedback::Feedback;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Read a word from memory at address 0x1000
+    /// if let Ok(Feedback::Word(value)) = debugger.read_mem(Addr::from(0x1000usize)) {
+    ///     println!("Memory at 0x1000: {:#x}", value);
+    /// }
+    ///
+    /// # }}
+    /// ```
+    pub fn read_mem(&self, addr: Addr) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+
+        let w = dbge.read_word(addr)?;
+
+        Ok(Feedback::Word(w))
     }
 
-    /// Continues execution of the debuggee, optionally delivering a signal
+    /// Writes a [Word] to memory at the specified address
     ///
-    /// This function tells the debuggee to continue execution from its current state.
-    /// If a signal is provided, it will be delivered to the debuggee.
+    /// # Parameters
+    ///
+    /// * `addr` - The address to write to
+    /// * `value` - The value to write
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback)` - The result of the continuation
-    /// * `Err(DebuggerError)` - If there was an error during continuation
+    /// * `Ok(Feedback::Ok)` - If the write was successful
+    /// * `Err(DebuggerError)` - If the memory could not be written
     ///
     /// # Errors
     ///
     /// This function can fail if:
     /// - The debuggee is not running
-    /// - ptrace's cont operation fails
+    /// - The memory address is invalid
+    /// - The memory cannot be accessed
     ///
     /// # Examples
     ///
@@ -600,39 +4687,41 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use nix::sys::signal::Signal;
+    /// # use coreminer::addr::Addr;
     /// #
-    /// let ui = CliUi::build(None).unwrap();
-    /// let mut debugger = Debugger::build(ui).unwrap();
-    /// // Assume debuggee is already running
-    ///
-    /// // Continue execution
-    /// debugger.cont().unwrap();
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Write the value 0x42 to memory at address 0x1000
+    /// debugger.write_mem(Addr::from(0x1000usize), 0x42).unwrap();
     ///
     /// # }}
     /// ```
-    pub fn cont(&mut self) -> Result<Feedback> {
-        if self.go_back_step_over_bp()? {
-            info!("breakpoint before, caught up and continueing with single step");
-        }
+    pub fn write_mem(&self, addr: Addr, value: Word) -> Result<Feedback> {
         let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-        ptrace::cont(dbge.pid, self.take_last_status())?;
 
-        self.wait_signal() // wait until the debuggee is stopped again!!!
+        mem_write_word(dbge.pid, addr, value)?;
+
+        Ok(Feedback::Ok)
     }
 
-    /// Gets the current registers of the debuggee
+    /// Gets the value of a register of the selected thread
+    ///
+    /// # Parameters
+    ///
+    /// * `r` - The register to get
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Registers)` - The registers
-    /// * `Err(DebuggerError)` - If there was an error retrieving registers
+    /// * `Ok(u64)` - The register value
+    /// * `Err(DebuggerError)` - If the register could not be read
     ///
     /// # Errors
     ///
     /// This function can fail if:
     /// - The debuggee is not running
-    /// - ptrace's getregs operation fails
+    /// - Register access fails
     ///
     /// # Examples
     ///
@@ -642,40 +4731,41 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use coreminer::feedback::Feedback;
+    /// # use coreminer::Register;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
     /// # let debugger = Debugger::build(ui).unwrap();
     /// # // Assume debuggee is already running
     /// #
-    /// if let Ok(Feedback::Registers(regs)) = debugger.dump_regs() {
-    ///     println!("RIP: {:#x}", regs.rip);
-    ///     println!("RSP: {:#x}", regs.rsp);
-    ///     println!("RAX: {:#x}", regs.rax);
-    /// }
+    /// // Get the value of the rax register
+    /// let rax = debugger.get_reg(Register::rax).unwrap();
+    /// println!("RAX: {:#x}", rax);
     ///
     /// # }}
     /// ```
-    pub fn dump_regs(&self) -> Result<Feedback> {
+    pub fn get_reg(&self, r: Register) -> Result<u64> {
         let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-        let regs = ptrace::getregs(dbge.pid)?;
-        Ok(Feedback::Registers(regs.into()))
+
+        dbge.get_reg(dbge.selected_thread(), r)
     }
 
-    /// Cleans up resources used by the debugger
+    /// Sets the value of a register of the selected thread
     ///
-    /// This function terminates the debuggee if it's still running
-    /// and releases any resources held by the debugger.
+    /// # Parameters
+    ///
+    /// * `r` - The register to set
+    /// * `v` - The value to set
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - If cleanup was successful
-    /// * `Err(DebuggerError)` - If there was an error during cleanup
+    /// * `Ok(Feedback::Ok)` - If the register was set successfully
+    /// * `Err(DebuggerError)` - If the register could not be set
     ///
     /// # Errors
     ///
     /// This function can fail if:
-    /// - The exists but debuggee cannot be killed with [`Debuggee::kill`]
+    /// - The debuggee is not running
+    /// - Register access fails
     ///
     /// # Examples
     ///
@@ -685,39 +4775,76 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::Register;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
-    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
     /// #
-    /// // Clean up resources at the end of debugging
-    /// debugger.cleanup().unwrap();
+    /// // Set the value of the rax register to 0x42
+    /// debugger.set_reg(Register::rax, 0x42).unwrap();
     ///
     /// # }}
     /// ```
-    pub fn cleanup(&mut self) -> Result<()> {
-        if let Some(dbge) = &self.debuggee {
-            dbge.kill()?;
-            self.debuggee = None;
-        }
-        Ok(())
+    pub fn set_reg(&self, r: Register, v: u64) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        crate::set_reg(dbge.selected_thread(), r, v)?;
+        Ok(Feedback::Ok)
     }
 
-    /// Sets a breakpoint at the specified address
+    /// Gets the current stack of the debugged process
     ///
-    /// # Parameters
+    /// # Returns
     ///
-    /// * `addr` - The address to set the breakpoint at
+    /// * `Ok(Feedback::Stack)` - The stack
+    /// * `Err(DebuggerError)` - If the stack could not be retrieved
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running
+    /// - Stack memory cannot be accessed
+    ///
+    /// # Examples
+    ///
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::feedback::Feedback;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is already running
+    /// #
+    /// // Get the current stack
+    /// if let Ok(Feedback::Stack(stack)) = debugger.get_stack() {
+    ///     println!("Stack: {}", stack);
+    /// }
+    ///
+    /// # }}
+    /// ```
+    pub fn get_stack(&self) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+
+        let stack = dbge.get_stack()?;
+        Ok(Feedback::Stack(stack))
+    }
+
+    /// Gets the process memory map
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Ok)` - If the breakpoint was set successfully
-    /// * `Err(DebuggerError)` - If there was an error setting the breakpoint
+    /// * `Ok(Feedback::ProcessMap)` - The process memory map
+    /// * `Err(DebuggerError)` - If the memory map could not be retrieved
     ///
     /// # Errors
     ///
     /// This function can fail if:
     /// - The debuggee is not running
-    /// - The breakpoint could not be enabled
+    /// - The memory map cannot be accessed
     ///
     /// # Examples
     ///
@@ -727,125 +4854,155 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use coreminer::addr::Addr;
+    /// # use coreminer::feedback::Feedback;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
-    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
     /// # // Assume debuggee is already running
     /// #
-    /// // Set a breakpoint at absolute address 0x1000
-    /// debugger.set_bp(Addr::from(0x1000usize)).unwrap();
+    /// // Get the process memory map
+    /// if let Ok(Feedback::ProcessMap(map)) = debugger.get_process_map() {
+    ///     println!("{:?}", map);
+    /// }
     ///
-    /// // Set a breakpoint at the program's entry point
-    /// let base = debugger.get_current_addr().unwrap();
-    /// debugger.set_bp(base).unwrap();
+    /// # }}
+    /// ```
+    pub fn get_process_map(&self) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+
+        let pm = dbge.get_process_map()?;
+
+        Ok(Feedback::ProcessMap(pm))
+    }
+
+    /// Gets a [`Breakpoint`] at the specified address
     ///
-    /// // Set a breakpoint at the relative address 0x1000
-    /// let base = debugger.get_current_addr().unwrap();
-    /// debugger.set_bp(base + 0x1000).unwrap();
+    /// This method retrieves a [`Breakpoint`] object at the given address, if one exists.
+    /// It's primarily used by plugins to determine whether a [`Breakpoint`] exists at a
+    /// specific location.
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The memory [`Addr`] to check for a [`Breakpoint`]
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Feedback::Breakpoint)` - Feedback containing either `Some(Breakpoint)` if a
+    ///   [`Breakpoint`] exists at the address, or [`None`] if there is no [`Breakpoint`]
+    /// * `Err(DebuggerError)` - If there was an error accessing the debuggee
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The debuggee is not running (`DebuggerError::NoDebugee`)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::addr::Addr;
+    /// # use coreminer::feedback::Feedback;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Set a breakpoint first
+    /// # debugger.set_bp(Addr::from(0x1000usize)).unwrap();
     ///
+    /// // Check if a breakpoint exists at address 0x1000
+    /// if let Ok(Feedback::Breakpoint(maybe_bp)) = debugger.get_bp(Addr::from(0x1000usize)) {
+    ///     match maybe_bp {
+    ///         Some(bp) => println!("Found breakpoint at address 0x1000"),
+    ///         None => println!("No breakpoint at address 0x1000"),
+    ///     }
+    /// }
     /// # }}
-    /// ```
-    pub fn set_bp(&mut self, addr: Addr) -> Result<Feedback> {
-        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
-        let mut bp = Breakpoint::new(dbge.pid, addr);
-        bp.enable()?;
-        dbge.breakpoints.insert(addr, bp);
+    /// ```
+    pub fn get_bp(&self, addr: Addr) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
 
-        Ok(Feedback::Ok)
+        let bp = dbge.breakpoints.get(&addr);
+
+        Ok(Feedback::Breakpoint(bp.cloned()))
     }
 
-    /// Removes a breakpoint at the specified address
+    /// Sets the last signal received from the [`Debuggee`]
+    ///
+    /// This method allows the [`Debugger`] or a plugin to set or modify the `last_signal`
+    /// that will be delivered to the [`Debuggee`].
+    ///
+    /// The set signal will be used the next time the debugger continues execution with
+    /// [`Self::cont()`] or one of the stepping methods.
     ///
     /// # Parameters
     ///
-    /// * `addr` - The address to remove the breakpoint from
+    /// * `sig` - The signal number to set as the last signal
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Ok)` - If the breakpoint was removed successfully
-    /// * `Err(DebuggerError)` - If there was an error removing the breakpoint
+    /// * `Ok(Feedback::Ok)` - If the signal was successfully set
+    /// * `Err(DebuggerError)` - If the signal number could not be converted to a valid signal
     ///
     /// # Errors
     ///
     /// This function can fail if:
-    /// - The debuggee is not running
-    /// - The breakpoint could not be disabled
+    /// - The signal number cannot be converted to a valid [`Signal`] type
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use coreminer::addr::Addr;
+    /// # use nix::sys::signal::Signal;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
     /// # let mut debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
-    /// #
-    /// // Remove a breakpoint at address 0x1000
-    /// debugger.del_bp(Addr::from(0x1000usize)).unwrap();
     ///
-    /// // Remove a breakpoint at the relative address 0x1000
-    /// let base = debugger.get_current_addr().unwrap();
-    /// debugger.del_bp(base + 0x1000).unwrap();
+    /// // Set SIGTRAP as the last signal
+    /// debugger.set_last_signal(Signal::SIGTRAP as i32).unwrap();
     ///
+    /// // When continuing execution, this signal will be passed to the debuggee
+    /// debugger.cont().unwrap();
     /// # }}
     /// ```
-    pub fn del_bp(&mut self, addr: Addr) -> Result<Feedback> {
-        let dbge = self.debuggee.as_mut().ok_or(DebuggerError::NoDebugee)?;
+    pub fn set_last_signal(&mut self, sig: i32) -> Result<Feedback> {
+        let sig = Signal::try_from(sig)?;
 
-        if let Some(_bp) = dbge.breakpoints.get_mut(&addr) {
-            dbge.breakpoints.remove(&addr); // gets disabled on dropping
-        } else {
-            warn!("removed a breakpoint at {addr:x?} that did not exist");
-        }
+        info!("set last signal to {sig}");
+        self.last_signal = Some(sig);
 
         Ok(Feedback::Ok)
     }
 
-    /// Performs a single, atomic step of exactly one instruction through the debuggee
-    ///
-    /// # Returns
-    ///
-    /// * `Ok(())` - If the step was successful
-    /// * `Err(DebuggerError)` - If there was an error during stepping
+    /// Runs a program for debugging
     ///
-    /// # Errors
+    /// This function loads an executable, parses its debug information, and
+    /// launches it under debugger control.
     ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    /// - ptrace's step operation fails
-    fn atomic_single_step(&mut self) -> Result<()> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-
-        // FIXME: this is probably noticeable
-        if let Err(e) = ptrace::step(dbge.pid, self.take_last_status()) {
-            error!("could not do atomic step: {e}");
-            return Err(e.into());
-        }
-
-        Ok(())
-    }
-
-    /// Steps a single instruction in the debuggee
+    /// # Parameters
     ///
-    /// This function advances execution by a single instruction, taking
-    /// care to handle breakpoints appropriately.
+    /// * `executable_path` - Path to the executable
+    /// * `arguments` - Command-line arguments for the executable
+    /// * `options` - Environment, working directory, redirections and ASLR of the debuggee
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Ok)` - If the step was successful
-    /// * `Err(DebuggerError)` - If there was an error during stepping
+    /// * `Ok(Feedback::Ok)` - If the program was launched successfully
+    /// * `Err(DebuggerError)` - If the program could not be launched
     ///
     /// # Errors
     ///
     /// This function can fail if:
-    /// - The debuggee is not running
-    /// - ptrace operations fail
+    /// - A debuggee is already running
+    /// - The executable does not exist
+    /// - The executable is not a valid file
+    /// - Debug information cannot be parsed
+    /// - The process cannot be forked
+    /// - The [`LaunchOptions`] cannot be applied
     ///
     /// # Examples
     ///
@@ -855,289 +5012,383 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::launch::LaunchOptions;
+    /// # use std::path::{Path, PathBuf};
+    /// # use std::ffi::CString;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
     /// # let mut debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
     /// #
-    /// // Step through one instruction
-    /// debugger.single_step().unwrap();
+    /// // Run a program with arguments
+    /// let program = Path::new("./target/debug/my_program");
+    /// let args = vec![
+    ///     CString::new("my_program").unwrap(),
+    ///     CString::new("--arg1").unwrap(),
+    ///     CString::new("value1").unwrap()
+    /// ];
     ///
-    /// // Step through multiple instructions
-    /// for _ in 0..5 {
-    ///     debugger.single_step().unwrap();
-    /// }
+    /// // without ASLR and with the output in a file
+    /// let mut options = LaunchOptions::default();
+    /// options.disable_aslr = true;
+    /// options.stdout = Some(PathBuf::from("/tmp/my_program.out"));
+    ///
+    /// debugger.run(program, &args, &options).unwrap();
     ///
     /// # }}
     /// ```
-    pub fn single_step(&mut self) -> Result<Feedback> {
-        if self.go_back_step_over_bp()? {
-            info!("breakpoint before, caught up and continueing with single step");
+    pub fn run(
+        &mut self,
+        executable_path: impl AsRef<Path>,
+        arguments: &[CString],
+        options: &LaunchOptions,
+    ) -> Result<Feedback> {
+        if self.debuggee.as_ref().is_some_and(|d| !d.is_core()) {
+            return Err(DebuggerError::AlreadyRunning);
         }
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
 
-        let maybe_bp_addr: Addr = self.get_current_addr()?;
-        if dbge.breakpoints.contains_key(&maybe_bp_addr) {
-            trace!("step over instruction with breakpoint");
-            self.dse(maybe_bp_addr)?;
-        } else {
-            trace!("step regular instruction");
-            self.atomic_single_step()?;
-            self.wait_signal()?;
-        }
-        trace!("now at {:018x}", self.get_reg(Register::rip)?);
+        // breakpoints of a previous program make no sense for this one
+        self.breakpoint_locations.clear();
+        self.watchpoint_locations.clear();
+        self.pending_breakpoints.clear();
+        self.launch(LaunchConfig {
+            executable: executable_path.as_ref().to_path_buf(),
+            arguments: arguments.to_vec(),
+            options: options.clone(),
+        })?;
 
         Ok(Feedback::Ok)
     }
 
-    /// Steps out of the current function
+    /// Loads the executable of a [`LaunchConfig`] and launches it as the new debuggee
+    ///
+    /// The [`LaunchConfig`] is remembered for [`Self::restart`].
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if the executable cannot be loaded or launched, see
+    /// [`Self::load_executable`] and [`Self::launch_debuggee`].
+    fn launch(&mut self, config: LaunchConfig) -> Result<()> {
+        debug!("exe to run are: {}", config.executable.to_string_lossy());
+        debug!("arguments to run are: {:?}", config.arguments);
+        debug!("launch options are: {:?}", config.options);
+
+        // NOTE: the lifetimes of the raw object data have given us many problems. It would be
+        // possible to read the object data out in the main function and passing it to the
+        // constructor of Debugger, but that would mean that we cannot debug a different program in
+        // the same session.
+        let exe: &Path = &config.executable;
+        let exe: PathBuf = which(exe).unwrap_or(exe.into());
+        info!("using executable path '{}'", exe.to_string_lossy());
+
+        self.load_executable(&exe)?;
+
+        // Now launch the debuggee
+        self.launch_debuggee(&exe, &config.arguments, &config.options)?;
+        self.last_signal = None;
+        self.last_launch = Some(config);
+
+        Ok(())
+    }
+
+    /// Kills the debuggee and runs the last program again
     ///
-    /// This function sets a temporary breakpoint at the return address
-    /// and continues execution until that breakpoint is hit.
+    /// The program is launched with the same path, arguments and [`LaunchOptions`] as in the last
+    /// [`Self::run`]. All breakpoints and watchpoints are set again. They are stored as offsets
+    /// into the mapped files, so they end up at the same place, even if the new process is
+    /// loaded at a different address. Watchpoints on the stack or the heap are not kept. Breakpoints in files that are not mapped right after the launch, like
+    /// shared libraries that are loaded later, are set when the file is mapped, see
+    /// [`sharedlib`](crate::sharedlib).
+    ///
+    /// A process that was [attached](Self::attach) to is not killed but detached.
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Ok)` - If the step-out was successful
-    /// * `Err(DebuggerError)` - If there was an error during step-out
+    /// * `Ok(Feedback::Ok)` - If the program was launched again
+    /// * `Err(DebuggerError)` - If the program could not be restarted
     ///
     /// # Errors
     ///
     /// This function can fail if:
-    /// - The debuggee is not running
-    /// - No valid return address of the current function can be found
-    /// - The current function is main (cannot step out)
-    /// - Could not read or write a [Register].
-    /// - Could not read from memory.
-    /// - Could not set or delete a [Breakpoint].
+    /// - No program was run before
+    /// - The old debuggee cannot be killed
+    /// - The program cannot be launched again
+    /// - A breakpoint or watchpoint cannot be set again
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::launch::LaunchOptions;
+    /// # use coreminer::addr::Addr;
+    /// # use std::path::Path;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
     /// # let mut debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running and in a function
     /// #
-    /// // Step out of the current function
-    /// debugger.step_out().unwrap();
+    /// debugger.run(Path::new("./target/debug/dummy"), &[], &LaunchOptions::default()).unwrap();
+    /// let base = debugger.get_current_addr().unwrap();
+    /// debugger.set_bp(base + 0x10).unwrap();
+    /// debugger.cont().unwrap();
+    ///
+    /// // run again from the start, the breakpoint is kept
+    /// debugger.restart().unwrap();
+    /// debugger.cont().unwrap();
     ///
     /// # }}
     /// ```
-    pub fn step_out(&mut self) -> Result<Feedback> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-        {
-            let a = dbge.get_function_by_addr(self.get_reg(Register::rip)?.into())?;
-            if let Some(s) = a {
-                debug!("step out in following function: {s:#?}");
-                if s.name() == Some("main") {
-                    error!("you're about to do something stupid: no stepping out of the earliest stack frame allowed");
-                    return Err(DebuggerError::StepOutMain);
-                }
-            } else {
-                warn!("did not find debug symbol for current address");
-            }
-        }
-
-        let stack_frame_pointer: Addr = self.get_reg(Register::rbp)?.into();
-        let return_addr: Addr = mem_read_word(dbge.pid, stack_frame_pointer + 8)?.into();
-        trace!("rsb: {stack_frame_pointer}");
-        trace!("ret_addr: {return_addr}");
+    pub fn restart(&mut self) -> Result<Feedback> {
+        let config = self
+            .last_launch
+            .clone()
+            .ok_or(DebuggerError::NoPreviousRun)?;
 
-        let should_remove_breakpoint = if dbge.breakpoints.contains_key(&return_addr) {
-            false
-        } else {
-            self.set_bp(return_addr)?;
-            true
-        };
+        let old = self
+            .debuggee
+            .as_ref()
+            .filter(|dbge| !dbge.is_attached() && !dbge.is_core())
+            .map(|dbge| dbge.pid);
+        self.cleanup()?;
+        if let Some(pid) = old {
+            Self::reap(pid)?;
+        }
 
-        self.cont()?;
+        info!("restarting '{}'", config.executable.to_string_lossy());
+        self.launch(config)?;
 
-        if should_remove_breakpoint {
-            self.del_bp(return_addr)?;
-            self.set_reg(Register::rip, self.get_reg(Register::rip)? - 1)?; // we need to go back
-                                                                            // else we skip an instruction
+        let map = self
+            .debuggee
+            .as_ref()
+            .ok_or(DebuggerError::NoDebugee)?
+            .get_process_map()?;
+        for (location, (kind, settings)) in self.breakpoint_locations.clone() {
+            if let Some(addr) = map.resolve(&location) {
+                debug!("restoring breakpoint at {location} to {addr}");
+                self.set_bp_with(addr, kind, settings)?;
+            } else {
+                debug!("breakpoint at {location} is not mapped yet, it is restored when it is");
+            }
+        }
+        for (location, (len, kind)) in self.watchpoint_locations.clone() {
+            if let Some(addr) = map.resolve(&location) {
+                debug!("restoring watchpoint at {location} to {addr}");
+                self.set_watchpoint(addr, len, kind)?;
+            } else {
+                warn!("watchpoint at {location} is not mapped, it is not restored");
+            }
         }
+
         Ok(Feedback::Ok)
     }
 
-    /// Temporarily disables a breakpoint, steps over it, and then re-enables it
-    ///
-    /// # Parameters
-    ///
-    /// * `here` - The address of the breakpoint
-    ///
-    /// # Returns
-    ///
-    /// * `Ok(())` - If the operation was successful
-    /// * `Err(DebuggerError)` - If there was an error
+    /// Makes sure a killed child process is gone, so that it does not stay around as a zombie
     ///
     /// # Errors
     ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    /// - Breakpoint operations fail
-    /// - Step operations fail
-    fn dse(&mut self, here: Addr) -> Result<()> {
-        trace!("disabling the breakpoint");
-        self.debuggee
-            .as_mut()
-            .unwrap()
-            .breakpoints
-            .get_mut(&here)
-            .unwrap()
-            .disable()?;
-
-        trace!("atomic step");
-        self.atomic_single_step()?;
-        trace!("waiting");
-        self.wait_signal()
-            .inspect_err(|e| warn!("weird wait_signal error: {e}"))?;
-        trace!("enable stepped over bp again");
-        self.debuggee
-            .as_mut()
-            .unwrap()
-            .breakpoints
-            .get_mut(&here)
-            .unwrap()
-            .enable()?;
-        trace!("dse done");
-
+    /// This function can fail if waiting for the process fails.
+    fn reap(pid: Pid) -> Result<()> {
+        // PTRACE_KILL does not always work, this does
+        match nix::sys::signal::kill(pid, Signal::SIGKILL) {
+            Ok(()) | Err(Errno::ESRCH) => (),
+            Err(e) => return Err(e.into()),
+        }
+        loop {
+            // the exits of the other threads are reported too
+            match waitpid(None, Some(WaitPidFlag::__WALL)) {
+                Ok(WaitStatus::Exited(p, _) | WaitStatus::Signaled(p, _, _)) if p == pid => break,
+                Ok(status) => trace!("reaping {pid}, got {status:?}"),
+                Err(Errno::ECHILD) => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        debug!("reaped old debuggee {pid}");
         Ok(())
     }
 
-    /// Checks if we need to restore an instruction pointer after hitting a breakpoint
-    ///
-    /// When a breakpoint is hit, the instruction pointer is just after the INT3 instruction.
-    /// This function checks if we need to move the instruction pointer back to the breakpoint
-    /// address and execute the original instruction. If so, it does that.
-    ///
-    /// # Returns
-    ///
-    /// * `Ok(bool)` - True if the IP was adjusted and a breakpoint was stepped over
-    /// * `Err(DebuggerError)` - If there was an error
-    ///
-    /// # Errors
-    ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    /// - Register operations fail
-    /// - Breakpoint operations fail
-    #[allow(clippy::missing_panics_doc)] // this function cant panic
-    pub fn go_back_step_over_bp(&mut self) -> Result<bool> {
-        if self.debuggee.is_none() {
-            return Err(DebuggerError::NoDebugee);
-        }
-
-        let maybe_bp_addr: Addr = self.get_current_addr()? - 1;
-        trace!("Checkinf if {maybe_bp_addr} had a breakpoint");
-
-        if self
-            .debuggee
-            .as_mut()
-            // safe because we check earlier, needed because we use a mutable reference
-            // and can only drop in place this way
-            .unwrap()
-            .breakpoints
-            .get_mut(&maybe_bp_addr)
-            .is_some_and(|a| a.is_enabled())
+    /// Reads and parses an executable file, storing the object data in the debugger
+    ///
+    /// The stored object data is later used by [`Self::launch_debuggee`] or [`Self::attach`] to
+    /// build the [`CMDebugInfo`] of the [`Debuggee`].
+    ///
+    /// # Parameters
+    ///
+    /// * `exe` - Path to the executable
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The executable cannot be read
+    /// - The executable is not a valid object file
+    fn load_executable(&mut self, exe: &Path) -> Result<()> {
+        // First, read the file data
+        self.stored_obj_data_raw = std::fs::read(exe)?;
+
+        // Create a new scope to handle the borrow checker
         {
-            let here = maybe_bp_addr;
-            trace!("set register to {here}");
-            self.set_reg(Register::rip, here.into())?;
+            // Create a reference to the raw data that matches the 'executable lifetime
+            let raw_data: &'executable [u8] = unsafe {
+                std::mem::transmute::<&[u8], &'executable [u8]>(&self.stored_obj_data_raw)
+            };
 
-            self.dse(here)?;
-            Ok(true)
-        } else {
-            trace!("breakpoint is disabled or does not exist, doing nothing");
-            Ok(false)
+            // Parse the object file
+            let obj_data = object::File::parse(raw_data)?;
+            self.stored_obj_data = Some(obj_data);
         }
+
+        Ok(())
     }
 
-    /// Disassembles memory at the specified address
+    /// Attaches to an already running process
+    ///
+    /// This function reads the executable of the process from `/proc/<pid>/exe`, parses its
+    /// debug information, attaches to every thread of the process with [`ptrace::attach`] and
+    /// waits until they are stopped.
+    ///
+    /// Processes that were attached to are detached instead of killed in [`Self::cleanup`].
     ///
     /// # Parameters
     ///
-    /// * `addr` - The starting address
-    /// * `len` - The number of bytes to disassemble
-    /// * `literal` - Whether to show literal bytes instead of original code (including breakpoint instructions)
+    /// * `pid` - The process ID of the process to attach to
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Disassembly)` - The disassembly result
-    /// * `Err(DebuggerError)` - If there was an error during disassembly
+    /// * `Ok(Feedback::Ok)` - If the process was attached to and is now stopped
+    /// * `Err(DebuggerError)` - If the process could not be attached to
     ///
     /// # Errors
     ///
     /// This function can fail if:
-    /// - The debuggee is not running
-    /// - Memory cannot be read
-    /// - Disassembly fails
+    /// - A debuggee is already running
+    /// - The executable of the process cannot be read
+    /// - Debug information cannot be parsed
+    /// - The process cannot be attached to, e.g. because of missing permissions or because it
+    ///   is already being traced
     ///
     /// # Panics
     ///
-    /// If a [Breakpoint] is enabled but has no saved data, this will panic.
-    /// If a [Breakpoint] was found before making the [Disassembly], but the same breakpoint does
-    /// not exist after the [Disassembly] was created, this will also panic.
+    /// This function panics if the object data of the executable was loaded but not stored, which
+    /// should not happen.
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use coreminer::addr::Addr;
-    /// # use coreminer::feedback::Feedback;
+    /// # use nix::unistd::Pid;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
-    /// # let debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
+    /// # let mut debugger = Debugger::build(ui).unwrap();
     /// #
-    /// // Disassemble 16 bytes at address 0x1000
-    /// if let Ok(Feedback::Disassembly(disasm)) = debugger.disassemble_at(Addr::from(0x1000usize), 16, false) {
-    ///     for (addr, raw, content, has_bp) in disasm.inner() {
-    ///         println!("{}: {} {}", addr, if *has_bp { "*" } else { " " }, content[0].0);
-    ///     }
-    /// }
+    /// // Attach to the process with PID 1337
+    /// debugger.attach(Pid::from_raw(1337)).unwrap();
+    ///
+    /// // inspect the process...
+    ///
+    /// // Let it run on without the debugger
+    /// debugger.detach().unwrap();
     ///
     /// # }}
     /// ```
-    pub fn disassemble_at(&self, addr: Addr, len: usize, literal: bool) -> Result<Feedback> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+    pub fn attach(&mut self, pid: Pid) -> Result<Feedback> {
+        if self.debuggee.as_ref().is_some_and(|d| !d.is_core()) {
+            return Err(DebuggerError::AlreadyRunning);
+        }
 
-        let t = dbge.disassemble(addr, len, literal)?;
+        let exe: PathBuf = PathBuf::from(format!("/proc/{pid}/exe"));
+        info!(
+            "attaching to process {pid} with executable '{}'",
+            std::fs::read_link(&exe)
+                .unwrap_or_else(|_| exe.clone())
+                .to_string_lossy()
+        );
 
-        Ok(Feedback::Disassembly(t))
+        self.load_executable(&exe)?;
+        let executable_obj_data: object::File<'_> = self
+            .stored_obj_data
+            .take()
+            .expect("object data was loaded but is not stored");
+        let dbginfo: CMDebugInfo = CMDebugInfo::build(executable_obj_data)?;
+
+        ptrace::attach(pid).inspect_err(|e| error!("could not attach to {pid}: {e}"))?;
+        // the process is sent a SIGSTOP by the attach, wait until it is actually stopped
+        let status = waitpid(pid, Some(WaitPidFlag::__WALL))?;
+        debug!("wait status after attach: {status:?}");
+        ptrace::setoptions(pid, Self::ptrace_options())?;
+
+        let mut dbge = Debuggee::build(pid, &dbginfo, HashMap::new())?;
+        dbge.attached = true;
+
+        // attach to the other threads too, repeat until no new threads show up
+        let mut found_new = true;
+        while found_new {
+            found_new = false;
+            for tid in list_tids(pid)? {
+                if dbge.thread(tid).is_some() {
+                    continue;
+                }
+                if let Err(e) = ptrace::attach(tid) {
+                    warn!("could not attach to thread {tid}: {e}");
+                    continue;
+                }
+                let status = waitpid(tid, Some(WaitPidFlag::__WALL))?;
+                debug!("wait status after attaching to thread {tid}: {status:?}");
+                ptrace::setoptions(tid, Self::ptrace_options())?;
+                dbge.threads
+                    .insert(tid, Thread::new(tid, ThreadState::Stopped));
+                found_new = true;
+            }
+        }
+
+        self.debuggee = Some(dbge);
+        self.last_signal = None;
+        // an attached process cannot be restarted
+        self.last_launch = None;
+        self.breakpoint_locations.clear();
+        self.watchpoint_locations.clear();
+        self.pending_breakpoints.clear();
+
+        Ok(Feedback::Ok)
     }
 
-    /// Searches for symbols by name
+    /// Loads the core file of a crashed process as the debuggee
+    ///
+    /// The registers and memory of the process are read from the core file, the debug symbols
+    /// from the executable. Everything that inspects the debuggee works like for a stopped
+    /// process, like [`Self::dump_regs`], [`Self::read_mem`], [`Self::disassemble_at`],
+    /// [`Self::backtrace`], [`Self::read_variable`] and [`Self::get_process_map`]. Everything
+    /// that would run or change the process fails with [`DebuggerError::CoreDumpNotLive`].
+    ///
+    /// The thread that crashed is selected. A core file that was loaded before is replaced.
     ///
     /// # Parameters
     ///
-    /// * `name` - The name to search for
+    /// * `core` - Path to the core file
+    /// * `executable` - Path to the executable of the crashed process, [`None`] to use the path
+    ///   recorded in the core file
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Symbols)` - The matching symbols
-    /// * `Err(DebuggerError)` - If there was an error during search
-    ///
-    /// Note: If the executable that is being debugged has no DWARF information (was stripped), this will always
-    /// return no symbols.
+    /// * `Ok(Feedback::Ok)` - If the core file was loaded
+    /// * `Err(DebuggerError)` - If the core file could not be loaded
     ///
     /// # Errors
     ///
     /// This function can fail if:
-    /// - The debug information is not loaded
-    /// - Symbol information is not available
+    /// - A live debuggee is already running
+    /// - The core file cannot be read or is not a valid core file
+    /// - The executable cannot be read
+    /// - Debug information cannot be parsed
     ///
-    /// # Examples
+    /// # Panics
+    ///
+    /// This function panics if the object data of the executable was loaded but not stored, which
+    /// should not happen.
     ///
+    /// # Examples
     ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
@@ -1145,257 +5396,336 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
     /// # use coreminer::feedback::Feedback;
+    /// # use std::path::Path;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
-    /// # let debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
+    /// # let mut debugger = Debugger::build(ui).unwrap();
     /// #
-    /// // Find the "main" function
-    /// if let Ok(Feedback::Symbols(symbols)) = debugger.get_symbol_by_name("main") {
-    ///     for symbol in symbols {
-    ///         println!("Found main at: {:?}", symbol.low_addr());
-    ///     }
+    /// debugger.load_core(Path::new("./core"), Some(Path::new("./target/debug/dummy"))).unwrap();
+    ///
+    /// if let Ok(Feedback::Backtrace(bt)) = debugger.backtrace() {
+    ///     println!("crashed in {:?}", bt.frames[0].name);
     /// }
     ///
     /// # }}
     /// ```
-    pub fn get_symbol_by_name(&self, name: impl Display) -> Result<Feedback> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-
-        let symbols: Vec<OwnedSymbol> = dbge.get_symbol_by_name(name)?;
-        Ok(Feedback::Symbols(symbols))
-    }
-
-    /// Handles a SIGTRAP signal from the debuggee
-    ///
-    /// # Parameters
-    ///
-    /// * `sig` - The signal
-    /// * `siginfo` - The signal information
-    ///
-    /// # Returns
-    ///
-    /// * `Ok(())` - If the signal was handled successfully
-    /// * `Err(DebuggerError)` - If there was an error handling the signal
-    ///
-    /// # Errors
-    ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    pub fn handle_sigtrap(
-        &mut self,
-        sig: nix::sys::signal::Signal,
-        siginfo: nix::libc::siginfo_t,
-    ) -> Result<()> {
-        #[allow(unused_mut)] // used for plugins
-        let mut return_early = false;
+    pub fn load_core(&mut self, core: &Path, executable: Option<&Path>) -> Result<Feedback> {
+        if self.debuggee.as_ref().is_some_and(|d| !d.is_core()) {
+            return Err(DebuggerError::AlreadyRunning);
+        }
 
-        for_hooks!(
-            for hook[EPreSigtrap] in self {
-                trace!("process hook {}", hook.name());
-                self.hook_feedback_loop(hook.name(), |f| {
-                    match hook.inner_mut().pre_handle_sigtrap(f, &siginfo, &sig) {
-                        Ok((status, ret_e)) => {
-                            return_early |= ret_e;
-                            Ok(status)
-                        },
-                        Err(e) => Err(e)
-                    }
-                })?;
+        let mut core = CoreDump::open(core)?;
+        let exe: PathBuf = match executable {
+            Some(exe) => {
+                core.set_executable(exe);
+                exe.to_path_buf()
             }
+            None => core
+                .executable()
+                .ok_or_else(|| {
+                    DebuggerError::InvalidCoreDump(
+                        "the core file does not name the executable".to_string(),
+                    )
+                })?
+                .to_path_buf(),
+        };
+        info!(
+            "loading core of process {} with executable '{}'",
+            core.pid(),
+            exe.to_string_lossy()
         );
 
-        if return_early {
-            return Ok(());
-        }
-
-        info!("debugee received {}: {}", sig.as_str(), siginfo.si_code);
+        self.load_executable(&exe)?;
+        let executable_obj_data: object::File<'_> = self
+            .stored_obj_data
+            .take()
+            .expect("object data was loaded but is not stored");
+        let dbginfo: CMDebugInfo = CMDebugInfo::build(executable_obj_data)?;
 
-        match siginfo.si_code {
-            SI_KERNEL => trace!("SI_KERNEL"), // we don't know what do do?
-            TRAP_BRKPT => {
-                trace!("TRAP_BRKPT");
-            }
-            TRAP_TRACE => trace!("TRAP_TRACE"), // single stepping
-            _ => warn!("Strange SIGTRAP code: {}", siginfo.si_code),
+        if let Some(sig) = core.signal() {
+            info!("process {} was terminated by {sig}", core.pid());
         }
+        self.debuggee = Some(Debuggee::build_core(core, &dbginfo)?);
+        self.last_signal = None;
+        // a core file cannot be restarted
+        self.last_launch = None;
+        self.breakpoint_locations.clear();
+        self.watchpoint_locations.clear();
+        self.pending_breakpoints.clear();
 
-        Ok(())
+        Ok(Feedback::Ok)
     }
 
-    /// Handles important signals from the debuggee
+    /// Writes a core file of the debuggee, like `gcore` does
+    ///
+    /// The debuggee is not changed and can be debugged further. The core file can be inspected
+    /// later with [`Self::load_core`] or with other debuggers. See
+    /// [`Debuggee::generate_core`] for what it contains.
     ///
     /// # Parameters
     ///
-    /// * `sig` - The signal
-    /// * `siginfo` - The signal information
+    /// * `path` - Where to write the core file
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - If the signal was handled successfully
-    /// * `Err(DebuggerError)` - If there was an error handling the signal
+    /// * `Ok(Feedback::Ok)` - If the core file was written
+    /// * `Err(DebuggerError)` - If the core file could not be written
     ///
     /// # Errors
     ///
     /// This function can fail if:
-    /// - The debuggee is not running
-    pub fn handle_important_signal(
-        &mut self,
-        sig: Signal,
-        siginfo: nix::libc::siginfo_t,
-    ) -> Result<()> {
-        info!("debugee received {}: {}", sig.as_str(), siginfo.si_code);
-        self.last_signal = Some(sig);
-        Ok(())
+    /// - No debuggee is running, or the debuggee is a core file
+    /// - The memory map, the registers or the information in `/proc` cannot be read
+    /// - The core file cannot be written
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::launch::LaunchOptions;
+    /// # use std::path::Path;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// #
+    /// debugger.run(Path::new("./target/debug/dummy"), &[], &LaunchOptions::default()).unwrap();
+    /// debugger.generate_core(Path::new("/tmp/dummy.core")).unwrap();
+    /// # }}
+    /// ```
+    pub fn generate_core(&self, path: &Path) -> Result<Feedback> {
+        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+        let size = dbge.generate_core(path, self.last_signal)?;
+        info!(
+            "wrote core file of process {} to '{}' ({size} bytes)",
+            dbge.pid,
+            path.to_string_lossy()
+        );
+        Ok(Feedback::Ok)
     }
 
-    /// Handles other signals from the debuggee
+    /// Writes the current setup of the debugger to a [session file](crate::session)
+    ///
+    /// The session contains the last program that was [run](Self::run), the breakpoints and
+    /// watchpoints as offsets into their mapped files, the pending breakpoints, the enabled
+    /// status of the plugins and the signal policies. It can be restored with
+    /// [`Self::load_session`].
     ///
     /// # Parameters
     ///
-    /// * `sig` - The signal
-    /// * `siginfo` - The signal information
+    /// * `path` - Where to write the session file
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - If the signal was handled successfully
-    /// * `Err(DebuggerError)` - If there was an error handling the signal
+    /// * `Ok(Feedback::Ok)` - If the session file was written
+    /// * `Err(DebuggerError)` - If the session file could not be written
     ///
     /// # Errors
     ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    pub fn handle_other_signal(
-        &mut self,
-        sig: Signal,
-        siginfo: nix::libc::siginfo_t,
-    ) -> Result<()> {
-        trace!("handle other");
-        info!("debugee received {}: {}", sig.as_str(), siginfo.si_code);
-        self.last_signal = Some(sig);
-        Ok(())
-    }
-
-    /// Logs information about the debugger state
+    /// This function fails if the session file cannot be written.
     ///
-    /// # Returns
+    /// # Panics
     ///
-    /// * `Ok(Feedback::Ok)` - If the information was logged successfully
-    /// * `Err(DebuggerError)` - If there was an error
+    /// This method will panic if it cannot acquire a lock on the plugin manager.
     ///
-    /// # Errors
+    /// # Examples
     ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    fn infos(&self) -> std::result::Result<Feedback, DebuggerError> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-        info!("Breakpoints:\n{:#?}", dbge.breakpoints);
+    /// ```no_run
+    /// #[cfg(feature = "cli")]
+    /// # mod featguard { fn _do_thing() {
+    /// # use coreminer::debugger::Debugger;
+    /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::launch::LaunchOptions;
+    /// # use std::path::Path;
+    /// #
+    /// # let ui = CliUi::build(None).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// #
+    /// debugger.run(Path::new("./target/debug/dummy"), &[], &LaunchOptions::default()).unwrap();
+    /// debugger.set_bp_at_symbol("main").unwrap();
+    /// debugger.save_session(Path::new("/tmp/dummy-session.json")).unwrap();
+    /// # }}
+    /// ```
+    pub fn save_session(&self, path: &Path) -> Result<Feedback> {
+        let session = Session {
+            launch: self.last_launch.clone(),
+            breakpoints: self
+                .breakpoint_locations
+                .iter()
+                .map(|(location, (kind, settings))| SessionBreakpoint {
+                    location: location.clone(),
+                    kind: *kind,
+                    settings: settings.clone(),
+                })
+                .collect(),
+            pending_breakpoints: self.pending_breakpoints.clone(),
+            watchpoints: self
+                .watchpoint_locations
+                .iter()
+                .map(|(location, (len, kind))| SessionWatchpoint {
+                    location: location.clone(),
+                    len: *len,
+                    kind: *kind,
+                })
+                .collect(),
+            #[cfg(feature = "plugins")]
+            plugins: self
+                .plugins
+                .lock()
+                .expect("could not lock plugin_manager")
+                .plugins()
+                .iter()
+                .map(|plugin| (plugin.id().into(), plugin.is_enabled()))
+                .collect(),
+            signal_policies: self.signal_policies.clone(),
+            skip_list: self.skip_list.clone(),
+        };
+        session.save(path)?;
+        info!(
+            "wrote session with {} breakpoints and {} watchpoints to '{}'",
+            session.breakpoints.len() + session.pending_breakpoints.len(),
+            session.watchpoints.len(),
+            path.to_string_lossy()
+        );
         Ok(Feedback::Ok)
     }
 
-    /// Steps into a function call
+    /// Restores a [session file](crate::session) written by [`Self::save_session`]
     ///
-    /// This function steps through instructions until a call instruction is found,
-    /// then steps into that function.
+    /// The signal policies and the enabled status of the plugins are applied right away. If the
+    /// session has a program, it is launched like with [`Self::restart`], which sets the
+    /// breakpoints and watchpoints of the session, and a current debuggee is killed or
+    /// detached. Without a program, the breakpoints and watchpoints are set at the next
+    /// [`Self::restart`].
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - Path of the session file
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Ok)` - If the step-into was successful
-    /// * `Err(DebuggerError)` - If there was an error during step-into
+    /// * `Ok(Feedback::Ok)` - If the session was restored
+    /// * `Err(DebuggerError)` - If the session could not be restored
     ///
     /// # Errors
     ///
     /// This function can fail if:
-    /// - The debuggee is not running
-    /// - No call instruction is found
-    /// - Step operations fail
+    /// - The session file cannot be read or is invalid
+    /// - The program of the session cannot be launched
+    /// - A breakpoint or watchpoint cannot be set again
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
+    /// # use std::path::Path;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
     /// # let mut debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
     /// #
-    /// // Step into the next function call
-    /// debugger.step_into().unwrap();
-    ///
+    /// debugger.load_session(Path::new("/tmp/dummy-session.json")).unwrap();
+    /// debugger.cont().unwrap();
     /// # }}
     /// ```
-    #[allow(clippy::missing_panics_doc)] // this function cannot panic
-    pub fn step_into(&mut self) -> Result<Feedback> {
-        if self.debuggee.is_none() {
-            return Err(DebuggerError::NoDebugee);
-        }
-        self.go_back_step_over_bp()?;
-
-        loop {
-            let rip: Addr = (self.get_reg(Register::rip)?).into();
-            let disassembly: Disassembly =
-                // unwrap is safe because we check earlier, needed because we need the mutable
-                // reference
-                self.debuggee.as_ref().unwrap().disassemble(rip, 8, true)?;
-            let next_instruction = &disassembly.inner()[0];
-            let operator = next_instruction.2[0].clone();
+    pub fn load_session(&mut self, path: &Path) -> Result<Feedback> {
+        let session = Session::load(path)?;
+        info!("loading session from '{}'", path.to_string_lossy());
 
-            if operator.1 != FormatterTextKind::Mnemonic {
-                error!("could not read operator from disassembly");
-            }
-            // PERF: this is very inefficient :/ maybe remove the autostepper or work with continue
-            // somehow
-            if operator.0.trim() == "call" {
-                self.single_step()?;
-                break;
+        self.signal_policies = session.signal_policies;
+        self.skip_list = session.skip_list;
+        #[cfg(feature = "plugins")]
+        for (id, enabled) in &session.plugins {
+            if let Err(e) = self.plugin_set_enable(id, *enabled) {
+                warn!("could not restore the status of plugin {id}: {e}");
             }
-            self.single_step()?;
         }
+        self.breakpoint_locations = session
+            .breakpoints
+            .into_iter()
+            .map(|bp| (bp.location, (bp.kind, bp.settings)))
+            .collect();
+        self.watchpoint_locations = session
+            .watchpoints
+            .into_iter()
+            .map(|wp| (wp.location, (wp.len, wp.kind)))
+            .collect();
+        self.pending_breakpoints = session.pending_breakpoints;
 
-        Ok(Feedback::Ok)
+        if let Some(config) = session.launch {
+            self.last_launch = Some(config);
+            self.restart()
+        } else {
+            info!("the session has no program, its breakpoints are set at the next restart");
+            Ok(Feedback::Ok)
+        }
     }
 
-    /// Steps over a function call
+    /// Detaches from the debuggee, letting it continue without the debugger
     ///
-    /// This function combines [`Self::step_into`] and [`Self::step_out`] to step over a function call.
+    /// All [breakpoints](Breakpoint) are removed before the debuggee is resumed. If the debuggee
+    /// is currently stopped right after a [`Breakpoint`], the instruction pointer is moved back
+    /// so that the original instruction is executed.
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Ok)` - If the step-over was successful
-    /// * `Err(DebuggerError)` - If there was an error during step-over
+    /// * `Ok(Feedback::Ok)` - If the debuggee was detached
+    /// * `Err(DebuggerError)` - If the debuggee could not be detached
     ///
     /// # Errors
     ///
     /// This function can fail if:
     /// - The debuggee is not running
-    /// - Step operations fail
-    pub fn step_over(&mut self) -> Result<Feedback> {
-        self.go_back_step_over_bp()?;
+    /// - [Register] access fails
+    /// - The ptrace detach operation fails
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the debuggee disappears while detaching, which should not happen.
+    pub fn detach(&mut self) -> Result<Feedback> {
+        if self.debuggee.is_none() {
+            return Err(DebuggerError::NoDebugee);
+        }
 
-        self.step_into()?;
-        self.step_out()
+        let maybe_bp_addr: Addr = self.get_current_addr()? - 1;
+        if self
+            .debuggee
+            .as_ref()
+            .expect("debuggee disappeared")
+            .breakpoints
+            .get(&maybe_bp_addr)
+            .is_some_and(Breakpoint::is_enabled)
+        {
+            trace!("stopped after a breakpoint, setting rip back to {maybe_bp_addr}");
+            self.set_reg(Register::rip, maybe_bp_addr.into())?;
+        }
+
+        let sig = self.take_last_status();
+        let mut dbge = self.debuggee.take().expect("debuggee disappeared");
+        dbge.detach(sig)?;
+        info!("detached from process {}", dbge.pid);
+
+        Ok(Feedback::Ok)
     }
 
-    /// Gets a backtrace of the current call stack
+    /// Lists all threads of the debuggee
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Backtrace)` - The backtrace
-    /// * `Err(DebuggerError)` - If there was an error generating the backtrace
+    /// * `Ok(Feedback::Threads)` - Information about each thread, including which one is selected
+    /// * `Err(DebuggerError)` - If the threads could not be listed
     ///
     /// # Errors
     ///
     /// This function can fail if:
     /// - The debuggee is not running
-    /// - Stack unwinding fails
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
@@ -1407,471 +5737,449 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     /// # let debugger = Debugger::build(ui).unwrap();
     /// # // Assume debuggee is already running
     /// #
-    /// // Get a backtrace of the call stack
-    /// if let Ok(Feedback::Backtrace(bt)) = debugger.backtrace() {
-    ///     for (i, frame) in bt.frames.iter().enumerate() {
-    ///         println!("#{} {} at {:?}", i, frame.name.as_deref().unwrap_or("??"), frame.addr);
+    /// if let Ok(Feedback::Threads(threads)) = debugger.list_threads() {
+    ///     for thread in threads {
+    ///         println!("{} {:?} at {:?}", thread.tid, thread.name, thread.rip);
     ///     }
     /// }
     ///
     /// # }}
     /// ```
-    pub fn backtrace(&self) -> Result<Feedback> {
+    pub fn list_threads(&self) -> Result<Feedback> {
         let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
 
-        let backtrace = unwind::unwind(dbge.pid)?;
-
-        Ok(Feedback::Backtrace(backtrace))
+        Ok(Feedback::Threads(dbge.list_threads()))
     }
 
-    /// Gets the current instruction pointer address
+    /// Selects the thread that register, backtrace and stepping operations act on
+    ///
+    /// If the previously selected thread is stopped right after a [`Breakpoint`], its
+    /// instruction pointer is moved back onto the [`Breakpoint`], so that it hits the
+    /// [`Breakpoint`] again once it is resumed instead of continuing in the middle of an
+    /// instruction. A signal that is still to be delivered stays with the thread that received
+    /// it.
+    ///
+    /// When the debuggee stops, the thread that caused the stop is selected automatically.
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread to select
     ///
     /// # Returns
     ///
-    /// * `Ok(Addr)` - The current address
-    /// * `Err(DebuggerError)` - If there was an error getting the address
+    /// * `Ok(Feedback::Ok)` - If the thread was selected
+    /// * `Err(DebuggerError)` - If the thread could not be selected
     ///
     /// # Errors
     ///
     /// This function can fail if:
     /// - The debuggee is not running
-    /// - [Register] read fails
+    /// - The debuggee has no thread with that thread ID
+    /// - [Register] access fails
     ///
-    /// # Examples
+    /// # Panics
+    ///
+    /// This function panics if the debuggee disappears while selecting, which should not happen.
     ///
+    /// # Examples
     ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
+    /// # use nix::unistd::Pid;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
-    /// # let debugger = Debugger::build(ui).unwrap();
+    /// # let mut debugger = Debugger::build(ui).unwrap();
     /// # // Assume debuggee is already running
     /// #
-    /// // Get the current instruction pointer
-    /// let curr_addr = debugger.get_current_addr().unwrap();
-    /// println!("Current IP: {}", curr_addr);
+    /// debugger.select_thread(Pid::from_raw(1338)).unwrap();
+    ///
+    /// // now this is the backtrace of thread 1338
+    /// debugger.backtrace().unwrap();
     ///
     /// # }}
     /// ```
-    pub fn get_current_addr(&self) -> Result<Addr> {
-        Ok((self.get_reg(Register::rip)?).into())
-    }
-
-    /// Prepares for variable access by gathering necessary context
-    ///
-    /// # Parameters
-    ///
-    /// * `expression` - The variable expression to access
-    ///
-    /// # Returns
-    ///
-    /// * `Ok((OwnedSymbol, OwnedSymbol, FrameInfo))` - Function, variable symbols and frame info
-    /// * `Err(DebuggerError)` - If preparation failed
-    ///
-    /// # Errors
-    ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    /// - The current location is not in a function
-    /// - The variable is not found
-    /// - Frame information cannot be constructed
-    #[allow(clippy::missing_panics_doc)] // this function cant panic
-    pub fn prepare_variable_access(
-        &self,
-        expression: &VariableExpression,
-    ) -> Result<(OwnedSymbol, OwnedSymbol, FrameInfo)> {
+    pub fn select_thread(&mut self, tid: Pid) -> Result<Feedback> {
         let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-        let rip: Addr = self.get_current_addr()?;
+        let old = dbge.selected_thread();
+        if dbge.thread(tid).is_none() {
+            return Err(DebuggerError::NoSuchThread(tid.as_raw()));
+        }
+        if old == tid {
+            return Ok(Feedback::Ok);
+        }
 
-        // Get current function
-        let current_function = match dbge.get_function_by_addr(rip)? {
-            Some(f) if f.frame_base().is_some() => f,
-            Some(_) => {
-                return Err(DebuggerError::AttributeDoesNotExist(
-                    gimli::DW_AT_frame_base,
-                ))
-            }
-            None => return Err(DebuggerError::NotInFunction),
-        };
+        let maybe_bp_addr: Addr = self.get_current_addr()? - 1;
+        if dbge
+            .breakpoints
+            .get(&maybe_bp_addr)
+            .is_some_and(Breakpoint::is_enabled)
+        {
+            trace!("thread {old} stopped after a breakpoint, setting rip back to {maybe_bp_addr}");
+            self.set_reg(Register::rip, maybe_bp_addr.into())?;
+        }
 
-        // Find variable
-        let locals = dbge.get_local_variables(rip)?;
-        let vars = dbge.filter_expressions(&locals, expression)?;
-        let var = match vars.len() {
-            0 => {
-                return Err(DebuggerError::VarExprReturnedNothing(
-                    expression.to_string(),
-                ))
+        let sig = self.take_last_status();
+        let dbge = self.debuggee.as_mut().expect("debuggee disappeared");
+        if let Some(thread) = dbge.threads.get_mut(&old) {
+            if sig.is_some() {
+                thread.pending_signal = sig;
             }
-            1 => vars[0].clone(),
-            _ => return Err(DebuggerError::AmbiguousVarExpr(expression.to_string())),
-        };
-
-        // Build frame info
-        let mut frame_info = FrameInfo::new(
-            None,
-            Some(Into::<Addr>::into(self.get_reg(Register::rbp)?) + 16usize),
-        );
-
-        let frame_base = dbge.parse_location(
-            current_function.frame_base().unwrap(), // safe: we check above if this is some
-            &frame_info,
-            current_function.encoding(),
-        )?;
-
-        let frame_base: Addr = match frame_base {
-            gimli::Location::Address { address } => address.into(),
-            other => unimplemented!(
-                "frame base DWARF location was not an address as expected: is {other:?}"
-            ),
-        };
-
-        frame_info.frame_base = Some(frame_base);
+        }
+        dbge.select_thread(tid)?;
+        self.last_signal = dbge
+            .threads
+            .get_mut(&tid)
+            .and_then(|t| t.pending_signal.take());
+        info!("selected thread {tid}");
 
-        Ok((current_function, var, frame_info))
+        Ok(Feedback::Ok)
     }
 
-    /// Reads the value of a variable
+    /// Sets which process is debugged after the debuggee created a new process
+    ///
+    /// See [`FollowForkMode`] for the available modes. The mode is kept for the whole debugging
+    /// session, also when a new program is run.
     ///
     /// # Parameters
     ///
-    /// * `expression` - The variable name to read
+    /// * `mode` - The new follow-fork mode
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Variable)` - The variable value
-    /// * `Err(DebuggerError)` - If the variable could not be read
+    /// * `Ok(Feedback::Ok)` - The mode was set
     ///
     /// # Errors
     ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    /// - The variable is not found
-    /// - The variable cannot be accessed
+    /// Cannot fail.
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use coreminer::feedback::Feedback;
+    /// # use coreminer::process::FollowForkMode;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
-    /// # let debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
+    /// # let mut debugger = Debugger::build(ui).unwrap();
     /// #
-    /// // Read the value of a variable named "count"
-    /// if let Ok(Feedback::Variable(value)) = debugger.read_variable(&"count".to_string()) {
-    ///     println!("count = {:?}", value);
-    /// }
+    /// // debug the child the next time the debuggee forks
+    /// debugger.set_follow_fork(FollowForkMode::Child).unwrap();
     ///
     /// # }}
     /// ```
-    pub fn read_variable(&self, expression: &VariableExpression) -> Result<Feedback> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-
-        let (_, symbol, frame_info) = self.prepare_variable_access(expression)?;
-
-        let val = dbge.var_read(&symbol, &frame_info)?;
-
-        Ok(Feedback::Variable(val))
+    pub fn set_follow_fork(&mut self, mode: FollowForkMode) -> Result<Feedback> {
+        info!("following {mode} on fork");
+        self.follow_fork = mode;
+        Ok(Feedback::Ok)
     }
 
-    /// Writes a value to a variable
-    ///
-    /// # Parameters
+    /// Lists all processes under the control of the debugger
     ///
-    /// * `expression` - The variable name to write to
-    /// * `value` - The value to write
+    /// These are the debuggee and the processes that are held after a `fork` with
+    /// [`FollowForkMode::Both`].
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Ok)` - If the write was successful
-    /// * `Err(DebuggerError)` - If the variable could not be written
+    /// * `Ok(Feedback::Processes)` - Information about each process, the debuggee first
     ///
     /// # Errors
     ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    /// - The variable is not found
-    /// - The variable cannot be accessed
-    /// - The value is incompatible with the variable type
+    /// Cannot fail.
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
+    /// # use coreminer::feedback::Feedback;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
     /// # let debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
     /// #
-    /// // Set the value of a variable named "count" to 42
-    /// debugger.write_variable(&"count".to_string(), 42).unwrap();
+    /// if let Ok(Feedback::Processes(processes)) = debugger.list_processes() {
+    ///     for process in processes {
+    ///         println!("{} {:?}", process.pid, process.executable);
+    ///     }
+    /// }
     ///
     /// # }}
     /// ```
-    pub fn write_variable(
-        &self,
-        expression: &VariableExpression,
-        value: impl Into<VariableValue>,
-    ) -> Result<Feedback> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-
-        let (_, var, frame_info) = self.prepare_variable_access(expression)?;
-
-        dbge.var_write(&var, &frame_info, &value.into())?;
-
-        Ok(Feedback::Ok)
+    pub fn list_processes(&self) -> Result<Feedback> {
+        Ok(Feedback::Processes(
+            self.debuggee
+                .iter()
+                .map(|d| ProcessInfo::new(d.pid, true))
+                .chain(
+                    self.held_processes
+                        .iter()
+                        .map(|d| ProcessInfo::new(d.pid, false)),
+                )
+                .collect(),
+        ))
     }
 
-    /// Reads a single [Word] from memory at the specified address
+    /// Selects a held process as the debuggee
+    ///
+    /// The previous debuggee is held stopped in its place, together with its breakpoints and a
+    /// signal that is still to be delivered.
     ///
     /// # Parameters
     ///
-    /// * `addr` - The address to read from
+    /// * `pid` - The process ID of the held process
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Word)` - The value read from memory
-    /// * `Err(DebuggerError)` - If the memory could not be read
+    /// * `Ok(Feedback::Ok)` - If the process was selected
+    /// * `Err(DebuggerError)` - If the process could not be selected
     ///
     /// # Errors
     ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    /// - The memory address is invalid
-    /// - The memory cannot be accessed
+    /// This function fails if no process with that process ID is held by the debugger.
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use coreminer::addr::Addr;
-    /// # use coreminer::feedback::Feedback;
+    /// # use nix::unistd::Pid;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
-    /// # let debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume the debuggee forked with FollowForkMode::Both
     /// #
-    /// // Read a word from memory at address 0x1000
-    /// if let Ok(Feedback::Word(value)) = debugger.read_mem(Addr::from(0x1000usize)) {
-    ///     println!("Memory at 0x1000: {:#x}", value);
-    /// }
+    /// debugger.select_process(Pid::from_raw(1338)).unwrap();
+    ///
+    /// // now the child runs, the parent stays stopped
+    /// debugger.cont().unwrap();
     ///
     /// # }}
     /// ```
-    pub fn read_mem(&self, addr: Addr) -> Result<Feedback> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
+    pub fn select_process(&mut self, pid: Pid) -> Result<Feedback> {
+        if self.debuggee.as_ref().is_some_and(|d| d.pid == pid) {
+            return Ok(Feedback::Ok);
+        }
+        let Some(idx) = self.held_processes.iter().position(|d| d.pid == pid) else {
+            return Err(DebuggerError::NoSuchProcess(pid.as_raw()));
+        };
 
-        let w = mem_read_word(dbge.pid, addr)?;
+        let mut new = self.held_processes.remove(idx);
+        let selected = new.selected_thread;
+        let sig = new
+            .threads
+            .get_mut(&selected)
+            .and_then(|t| t.pending_signal.take());
 
-        Ok(Feedback::Word(w))
+        if let Some(mut old) = self.debuggee.replace(new) {
+            let selected = old.selected_thread;
+            if let Some(thread) = old.threads.get_mut(&selected) {
+                thread.pending_signal = self.last_signal.take();
+            }
+            self.held_processes.push(old);
+        }
+        self.last_signal = sig;
+        info!("selected process {pid}");
+
+        Ok(Feedback::Ok)
     }
 
-    /// Writes a [Word] to memory at the specified address
+    /// Enables or disables stopping at every syscall of the debuggee
+    ///
+    /// While syscall tracing is enabled, the debuggee stops at the entry and at the exit of each
+    /// syscall, and [`Feedback::Syscall`] is returned from continuing.
     ///
     /// # Parameters
     ///
-    /// * `addr` - The address to write to
-    /// * `value` - The value to write
+    /// * `enable` - Whether to stop at every syscall
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Ok)` - If the write was successful
-    /// * `Err(DebuggerError)` - If the memory could not be written
+    /// * `Ok(Feedback::Ok)` - If the setting was changed
     ///
     /// # Errors
     ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    /// - The memory address is invalid
-    /// - The memory cannot be accessed
+    /// Cannot fail.
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use coreminer::addr::Addr;
+    /// # use coreminer::feedback::Feedback;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
-    /// # let debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is running
     /// #
-    /// // Write the value 0x42 to memory at address 0x1000
-    /// debugger.write_mem(Addr::from(0x1000usize), 0x42).unwrap();
+    /// debugger.trace_syscalls(true).unwrap();
+    ///
+    /// if let Ok(Feedback::Syscall(event)) = debugger.cont() {
+    ///     println!("{event}");
+    /// }
     ///
     /// # }}
     /// ```
-    pub fn write_mem(&self, addr: Addr, value: Word) -> Result<Feedback> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-
-        mem_write_word(dbge.pid, addr, value)?;
-
+    pub fn trace_syscalls(&mut self, enable: bool) -> Result<Feedback> {
+        info!("tracing all syscalls: {enable}");
+        self.syscall_catcher.set_trace_all(enable);
         Ok(Feedback::Ok)
     }
 
-    /// Gets the value of a register
+    /// Adds a catchpoint for a syscall
+    ///
+    /// The debuggee stops at the entry and at the exit of that syscall, and
+    /// [`Feedback::Syscall`] is returned from continuing.
     ///
     /// # Parameters
     ///
-    /// * `r` - The register to get
+    /// * `syscall` - The name or the number of the syscall, like `write` or `1`
     ///
     /// # Returns
     ///
-    /// * `Ok(u64)` - The register value
-    /// * `Err(DebuggerError)` - If the register could not be read
+    /// * `Ok(Feedback::Ok)` - If the catchpoint was added
+    /// * `Err(DebuggerError)` - If the syscall is not known
     ///
     /// # Errors
     ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    /// - Register access fails
+    /// This function fails with [`DebuggerError::UnknownSyscall`] if `syscall` is neither the
+    /// name nor the number of a syscall.
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use coreminer::Register;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
-    /// # let debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
+    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # // Assume debuggee is running
     /// #
-    /// // Get the value of the rax register
-    /// let rax = debugger.get_reg(Register::rax).unwrap();
-    /// println!("RAX: {:#x}", rax);
+    /// debugger.catch_syscall("openat").unwrap();
+    ///
+    /// // stops when the debuggee opens a file
+    /// debugger.cont().unwrap();
     ///
     /// # }}
     /// ```
-    pub fn get_reg(&self, r: Register) -> Result<u64> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-
-        crate::get_reg(dbge.pid, r)
+    pub fn catch_syscall(&mut self, syscall: &str) -> Result<Feedback> {
+        let number = parse_syscall(syscall)?;
+        info!("catching syscall {syscall} ({number})");
+        self.syscall_catcher.catch(number);
+        Ok(Feedback::Ok)
     }
 
-    /// Sets the value of a register
+    /// Removes a catchpoint for a syscall
     ///
     /// # Parameters
     ///
-    /// * `r` - The register to set
-    /// * `v` - The value to set
+    /// * `syscall` - The name or the number of the syscall, like `write` or `1`
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Ok)` - If the register was set successfully
-    /// * `Err(DebuggerError)` - If the register could not be set
+    /// * `Ok(Feedback::Ok)` - If the catchpoint was removed
+    /// * `Err(DebuggerError)` - If there was no such catchpoint
     ///
     /// # Errors
     ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    /// - Register access fails
+    /// This function fails with [`DebuggerError::UnknownSyscall`] if `syscall` is not a known
+    /// syscall or if it was not caught.
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use coreminer::Register;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
-    /// # let debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
+    /// # let mut debugger = Debugger::build(ui).unwrap();
     /// #
-    /// // Set the value of the rax register to 0x42
-    /// debugger.set_reg(Register::rax, 0x42).unwrap();
+    /// debugger.catch_syscall("write").unwrap();
+    /// debugger.del_catch_syscall("write").unwrap();
     ///
     /// # }}
     /// ```
-    pub fn set_reg(&self, r: Register, v: u64) -> Result<Feedback> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-        crate::set_reg(dbge.pid, r, v)?;
+    pub fn del_catch_syscall(&mut self, syscall: &str) -> Result<Feedback> {
+        let number = parse_syscall(syscall)?;
+        if !self.syscall_catcher.uncatch(number) {
+            return Err(DebuggerError::UnknownSyscall(syscall.to_string()));
+        }
+        info!("no longer catching syscall {syscall} ({number})");
         Ok(Feedback::Ok)
     }
 
-    /// Gets the current stack of the debugged process
+    /// Sets what happens when the debuggee receives a signal
+    ///
+    /// See [`SignalPolicy`] for the meaning of the settings. The policy of `SIGTRAP` is only
+    /// consulted for printing, as the debugger needs it for breakpoints and stepping.
+    ///
+    /// # Parameters
+    ///
+    /// * `sig` - The signal number
+    /// * `policy` - The new policy for that signal
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Stack)` - The stack
-    /// * `Err(DebuggerError)` - If the stack could not be retrieved
+    /// * `Ok(Feedback::Ok)` - If the policy was set
+    /// * `Err(DebuggerError)` - If the signal number is not a valid signal
     ///
     /// # Errors
     ///
     /// This function can fail if:
-    /// - The debuggee is not running
-    /// - Stack memory cannot be accessed
+    /// - The signal number cannot be converted to a valid [`Signal`] type
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use coreminer::feedback::Feedback;
+    /// # use coreminer::signal::SignalPolicy;
+    /// # use nix::sys::signal::Signal;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
-    /// # let debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
+    /// # let mut debugger = Debugger::build(ui).unwrap();
     /// #
-    /// // Get the current stack
-    /// if let Ok(Feedback::Stack(stack)) = debugger.get_stack() {
-    ///     println!("Stack: {}", stack);
-    /// }
+    /// // let the debuggee handle SIGUSR1 without stopping
+    /// debugger
+    ///     .set_signal_policy(Signal::SIGUSR1 as i32, SignalPolicy::new(false, true, true))
+    ///     .unwrap();
     ///
     /// # }}
     /// ```
-    pub fn get_stack(&self) -> Result<Feedback> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-
-        let stack = dbge.get_stack()?;
-        Ok(Feedback::Stack(stack))
+    pub fn set_signal_policy(&mut self, sig: i32, policy: SignalPolicy) -> Result<Feedback> {
+        let sig = Signal::try_from(sig)?;
+        info!("policy for {sig}: {policy:?}");
+        self.signal_policies.set(sig, policy);
+        Ok(Feedback::Ok)
     }
 
-    /// Gets the process memory map
+    /// Lists the [`SignalPolicy`] of every signal
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::ProcessMap)` - The process memory map
-    /// * `Err(DebuggerError)` - If the memory map could not be retrieved
+    /// * `Ok(Feedback::SignalPolicies)` - The signal policy table
     ///
     /// # Errors
     ///
-    /// This function can fail if:
-    /// - The debuggee is not running
-    /// - The memory map cannot be accessed
+    /// Cannot fail.
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
@@ -1881,43 +6189,32 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     /// #
     /// # let ui = CliUi::build(None).unwrap();
     /// # let debugger = Debugger::build(ui).unwrap();
-    /// # // Assume debuggee is already running
     /// #
-    /// // Get the process memory map
-    /// if let Ok(Feedback::ProcessMap(map)) = debugger.get_process_map() {
-    ///     println!("{:?}", map);
+    /// if let Ok(Feedback::SignalPolicies(table)) = debugger.list_signal_policies() {
+    ///     println!("{table}");
     /// }
     ///
     /// # }}
     /// ```
-    pub fn get_process_map(&self) -> Result<Feedback> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-
-        let pm = dbge.get_process_map()?;
-
-        Ok(Feedback::ProcessMap(pm))
+    pub fn list_signal_policies(&self) -> Result<Feedback> {
+        Ok(Feedback::SignalPolicies(self.signal_policies.clone()))
     }
 
-    /// Gets a [`Breakpoint`] at the specified address
+    /// Adds a rule for code that stepping does not stop in, see [`crate::skiplist`]
     ///
-    /// This method retrieves a [`Breakpoint`] object at the given address, if one exists.
-    /// It's primarily used by plugins to determine whether a [`Breakpoint`] exists at a
-    /// specific location.
+    /// The rule is kept when the debuggee is [restarted](Self::restart).
     ///
     /// # Parameters
     ///
-    /// * `addr` - The memory [`Addr`] to check for a [`Breakpoint`]
+    /// * `rule` - The new rule
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Breakpoint)` - Feedback containing either `Some(Breakpoint)` if a
-    ///   [`Breakpoint`] exists at the address, or [`None`] if there is no [`Breakpoint`]
-    /// * `Err(DebuggerError)` - If there was an error accessing the debuggee
+    /// * `Ok(Feedback::Ok)` - If the rule was added, or was already there
     ///
     /// # Errors
     ///
-    /// This function can fail if:
-    /// - The debuggee is not running (`DebuggerError::NoDebugee`)
+    /// Cannot fail.
     ///
     /// # Examples
     ///
@@ -1926,52 +6223,41 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use coreminer::addr::Addr;
-    /// # use coreminer::feedback::Feedback;
+    /// # use coreminer::skiplist::SkipRule;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
     /// # let mut debugger = Debugger::build(ui).unwrap();
-    /// # // Set a breakpoint first
-    /// # debugger.set_bp(Addr::from(0x1000usize)).unwrap();
+    /// # // Assume debuggee is running
+    /// #
+    /// debugger.add_skip(SkipRule::Library("libc.so.6".into())).unwrap();
     ///
-    /// // Check if a breakpoint exists at address 0x1000
-    /// if let Ok(Feedback::Breakpoint(maybe_bp)) = debugger.get_bp(Addr::from(0x1000usize)) {
-    ///     match maybe_bp {
-    ///         Some(bp) => println!("Found breakpoint at address 0x1000"),
-    ///         None => println!("No breakpoint at address 0x1000"),
-    ///     }
-    /// }
+    /// // steps over printf instead of into it
+    /// debugger.step_line().unwrap();
     /// # }}
     /// ```
-    pub fn get_bp(&self, addr: Addr) -> Result<Feedback> {
-        let dbge = self.debuggee.as_ref().ok_or(DebuggerError::NoDebugee)?;
-
-        let bp = dbge.breakpoints.get(&addr);
-
-        Ok(Feedback::Breakpoint(bp.cloned()))
+    pub fn add_skip(&mut self, rule: SkipRule) -> Result<Feedback> {
+        info!("skipping {rule} when stepping");
+        if !self.skip_list.add(rule) {
+            debug!("the skip rule was already there");
+        }
+        Ok(Feedback::Ok)
     }
 
-    /// Sets the last signal received from the [`Debuggee`]
-    ///
-    /// This method allows the [`Debugger`] or a plugin to set or modify the `last_signal`
-    /// that will be delivered to the [`Debuggee`].
-    ///
-    /// The set signal will be used the next time the debugger continues execution with
-    /// [`Self::cont()`] or one of the stepping methods.
+    /// Removes a rule of the skip list
     ///
     /// # Parameters
     ///
-    /// * `sig` - The signal number to set as the last signal
+    /// * `rule` - The rule to remove
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Ok)` - If the signal was successfully set
-    /// * `Err(DebuggerError)` - If the signal number could not be converted to a valid signal
+    /// * `Ok(Feedback::Ok)` - If the rule was removed
+    /// * `Err(DebuggerError)` - If there was no such rule
     ///
     /// # Errors
     ///
-    /// This function can fail if:
-    /// - The signal number cannot be converted to a valid [`Signal`] type
+    /// This function fails with [`DebuggerError::NoSkipRule`] if the skip list does not have
+    /// `rule`.
     ///
     /// # Examples
     ///
@@ -1980,119 +6266,53 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use nix::sys::signal::Signal;
+    /// # use coreminer::skiplist::SkipRule;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
     /// # let mut debugger = Debugger::build(ui).unwrap();
-    ///
-    /// // Set SIGTRAP as the last signal
-    /// debugger.set_last_signal(Signal::SIGTRAP as i32).unwrap();
-    ///
-    /// // When continuing execution, this signal will be passed to the debuggee
-    /// debugger.cont().unwrap();
+    /// #
+    /// let rule = SkipRule::Function("*printf".to_string());
+    /// debugger.add_skip(rule.clone()).unwrap();
+    /// debugger.del_skip(&rule).unwrap();
     /// # }}
     /// ```
-    pub fn set_last_signal(&mut self, sig: i32) -> Result<Feedback> {
-        let sig = Signal::try_from(sig)?;
-
-        info!("set last signal to {sig}");
-        self.last_signal = Some(sig);
-
+    pub fn del_skip(&mut self, rule: &SkipRule) -> Result<Feedback> {
+        if !self.skip_list.remove(rule) {
+            return Err(DebuggerError::NoSkipRule(rule.to_string()));
+        }
+        info!("no longer skipping {rule} when stepping");
         Ok(Feedback::Ok)
     }
 
-    /// Runs a program for debugging
-    ///
-    /// This function loads an executable, parses its debug information, and
-    /// launches it under debugger control.
-    ///
-    /// # Parameters
-    ///
-    /// * `executable_path` - Path to the executable
-    /// * `arguments` - Command-line arguments for the executable
+    /// Lists the rules of the skip list
     ///
     /// # Returns
     ///
-    /// * `Ok(Feedback::Ok)` - If the program was launched successfully
-    /// * `Err(DebuggerError)` - If the program could not be launched
+    /// * `Ok(Feedback::SkipList)` - The skip list
     ///
     /// # Errors
     ///
-    /// This function can fail if:
-    /// - A debuggee is already running
-    /// - The executable does not exist
-    /// - The executable is not a valid file
-    /// - Debug information cannot be parsed
-    /// - The process cannot be forked
+    /// Cannot fail.
     ///
     /// # Examples
     ///
-    ///
     /// ```no_run
     /// #[cfg(feature = "cli")]
     /// # mod featguard { fn _do_thing() {
     /// # use coreminer::debugger::Debugger;
     /// # use coreminer::ui::cli::CliUi;
-    /// # use std::path::Path;
-    /// # use std::ffi::CString;
+    /// # use coreminer::feedback::Feedback;
     /// #
     /// # let ui = CliUi::build(None).unwrap();
-    /// # let mut debugger = Debugger::build(ui).unwrap();
+    /// # let debugger = Debugger::build(ui).unwrap();
     /// #
-    /// // Run a program with arguments
-    /// let program = Path::new("./target/debug/my_program");
-    /// let args = vec![
-    ///     CString::new("my_program").unwrap(),
-    ///     CString::new("--arg1").unwrap(),
-    ///     CString::new("value1").unwrap()
-    /// ];
-    ///
-    /// debugger.run(program, &args).unwrap();
-    ///
+    /// if let Ok(Feedback::SkipList(list)) = debugger.list_skips() {
+    ///     println!("{list}");
+    /// }
     /// # }}
     /// ```
-    pub fn run(
-        &mut self,
-        executable_path: impl AsRef<Path>,
-        arguments: &[CString],
-    ) -> Result<Feedback> {
-        if self.debuggee.is_some() {
-            return Err(DebuggerError::AlreadyRunning);
-        }
-
-        debug!(
-            "exe to run are: {}",
-            executable_path.as_ref().to_string_lossy()
-        );
-        debug!("arguments to run are: {arguments:?}");
-
-        // NOTE: the lifetimes of the raw object data have given us many problems. It would be
-        // possible to read the object data out in the main function and passing it to the
-        // constructor of Debugger, but that would mean that we cannot debug a different program in
-        // the same session.
-        let exe: &Path = executable_path.as_ref();
-        let exe: PathBuf = which(exe).unwrap_or(exe.into());
-        info!("using executable path '{}'", exe.to_string_lossy());
-
-        // First, read the file data
-        self.stored_obj_data_raw = std::fs::read(&exe)?;
-
-        // Create a new scope to handle the borrow checker
-        {
-            // Create a reference to the raw data that matches the 'executable lifetime
-            let raw_data: &'executable [u8] = unsafe {
-                std::mem::transmute::<&[u8], &'executable [u8]>(&self.stored_obj_data_raw)
-            };
-
-            // Parse the object file
-            let obj_data = object::File::parse(raw_data)?;
-            self.stored_obj_data = Some(obj_data);
-        }
-
-        // Now launch the debuggee
-        self.launch_debuggee(&exe, arguments)?;
-
-        Ok(Feedback::Ok)
+    pub fn list_skips(&self) -> Result<Feedback> {
+        Ok(Feedback::SkipList(self.skip_list.clone()))
     }
 
     /// Runs a feedback loop for plugin hooks
@@ -2368,8 +6588,48 @@ impl<'executable, UI: DebuggerUI> Debugger<'executable, UI> {
         ))
     }
 
+    /// The ptrace options set for every traced thread of the debuggee
+    fn ptrace_options() -> ptrace::Options {
+        ptrace::Options::PTRACE_O_TRACECLONE
+            | ptrace::Options::PTRACE_O_TRACEFORK
+            | ptrace::Options::PTRACE_O_TRACEVFORK
+            | ptrace::Options::PTRACE_O_TRACEVFORKDONE
+            | ptrace::Options::PTRACE_O_TRACEEXEC
+            | ptrace::Options::PTRACE_O_TRACESYSGOOD
+    }
+
     /// Take the `last_signal` field of the debugger, leaving `None` in it's place
     fn take_last_status(&mut self) -> Option<Signal> {
         self.last_signal.take()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A UI that never asks for anything, the tests call the debugger directly
+    struct QuietUi;
+
+    impl DebuggerUI for QuietUi {
+        fn process(&mut self, _feedback: Feedback) -> Result<Status> {
+            Ok(Status::DebuggerQuit)
+        }
+    }
+
+    #[test]
+    fn test_bp_commands_cannot_control_execution() {
+        let mut debugger = Debugger::build(QuietUi).unwrap();
+        let addr = Addr::from(0x5555_5555_5139usize);
+        for command in [
+            Status::SelectThread(1337),
+            Status::SetFollowFork(FollowForkMode::Child),
+            Status::Continue,
+        ] {
+            assert!(matches!(
+                debugger.set_bp_commands(addr, vec![Status::Backtrace, command]),
+                Err(DebuggerError::NotABreakpointCommand(_))
+            ));
+        }
+    }
+}
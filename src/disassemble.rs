@@ -21,8 +21,8 @@ use crate::Addr;
 const CODE_BITNESS: u32 = 64;
 
 use iced_x86::{
-    Decoder, DecoderOptions, Formatter, FormatterOutput, FormatterTextKind, Instruction,
-    NasmFormatter,
+    Decoder, DecoderOptions, FlowControl, Formatter, FormatterOutput, FormatterTextKind,
+    Instruction, Mnemonic, NasmFormatter, OpKind, Register,
 };
 use serde::{Serialize, Serializer};
 use tracing::warn;
@@ -394,10 +394,205 @@ where
     serializable_data.serialize(serializer)
 }
 
+/// Finds the end of the prologue of a function
+///
+/// The prologue sets up the stack frame and stores the parameters of the function. A
+/// breakpoint at the start of a function would stop before that, so that the frame base and the
+/// parameters cannot be read yet. The prologue is recognized by its typical instructions:
+///
+/// - `endbr64`
+/// - `push` of the frame pointer or a callee-saved register
+/// - `mov rbp, rsp`
+/// - `sub rsp, imm`
+/// - Storing a parameter register on the stack
+///
+/// # Parameters
+///
+/// * `code` - The machine code at the start of the function
+/// * `first_addr` - The address of the function
+///
+/// # Returns
+///
+/// The address of the first instruction after the prologue, `first_addr` if the function has
+/// no recognized prologue.
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::addr::Addr;
+/// use coreminer::disassemble::prologue_end;
+///
+/// // push rbp; mov rbp, rsp; mov [rbp-0x14], edi; mov eax, 0
+/// let code = [0x55, 0x48, 0x89, 0xe5, 0x89, 0x7d, 0xec, 0xb8, 0, 0, 0, 0];
+/// let addr = Addr::from(0x1000usize);
+/// assert_eq!(prologue_end(&code, addr), addr + 7);
+/// ```
+#[must_use]
+pub fn prologue_end(code: &[u8], first_addr: Addr) -> Addr {
+    let mut decoder = Decoder::with_ip(CODE_BITNESS, code, first_addr.into(), DecoderOptions::NONE);
+    let mut instruction = Instruction::default();
+    let mut end = first_addr;
+    while decoder.can_decode() {
+        decoder.decode_out(&mut instruction);
+        if instruction.is_invalid() || !is_prologue_instruction(&instruction) {
+            break;
+        }
+        end = instruction.next_ip().into();
+    }
+    end
+}
+
+/// How an instruction changes the control flow, as far as stepping by source lines is concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InstructionFlow {
+    /// A direct or indirect `call`, returning to the address after it
+    Call {
+        /// Address of the instruction after the `call`
+        return_addr: Addr,
+    },
+    /// A `ret`, leaving the current function
+    Return,
+    /// Any other instruction, including jumps
+    Other,
+}
+
+/// Decodes the first instruction of some machine code and tells how it changes the control flow
+///
+/// # Parameters
+///
+/// * `code` - The machine code, starting with the instruction
+/// * `addr` - The address of the instruction
+///
+/// # Returns
+///
+/// The [`InstructionFlow`] of the instruction, [`InstructionFlow::Other`] if it cannot be
+/// decoded.
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::addr::Addr;
+/// use coreminer::disassemble::{instruction_flow, InstructionFlow};
+///
+/// // call rel32
+/// let code = [0xe8, 0x10, 0x00, 0x00, 0x00];
+/// let addr = Addr::from(0x1000usize);
+/// assert_eq!(
+///     instruction_flow(&code, addr),
+///     InstructionFlow::Call { return_addr: addr + 5 }
+/// );
+/// assert_eq!(instruction_flow(&[0xc3], addr), InstructionFlow::Return);
+/// ```
+#[must_use]
+pub fn instruction_flow(code: &[u8], addr: Addr) -> InstructionFlow {
+    let instruction =
+        Decoder::with_ip(CODE_BITNESS, code, addr.into(), DecoderOptions::NONE).decode();
+    if instruction.is_invalid() {
+        return InstructionFlow::Other;
+    }
+    match instruction.flow_control() {
+        FlowControl::Call | FlowControl::IndirectCall => InstructionFlow::Call {
+            return_addr: instruction.next_ip().into(),
+        },
+        FlowControl::Return => InstructionFlow::Return,
+        _ => InstructionFlow::Other,
+    }
+}
+
+/// Finds the memory slot that an indirect jump at the start of some machine code jumps through
+///
+/// This is how the stubs in the procedure linkage table (PLT) look: an `endbr64` if the code
+/// was compiled for indirect branch tracking, then a `jmp` through the slot of the function in
+/// the global offset table (GOT).
+///
+/// # Parameters
+///
+/// * `code` - The machine code, starting with the stub
+/// * `addr` - The address of the stub
+///
+/// # Returns
+///
+/// The address of the slot, [`None`] if the code does not start with a `rip` relative
+/// indirect jump.
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::addr::Addr;
+/// use coreminer::disassemble::jump_slot;
+///
+/// // jmp [rip+0x2fca]
+/// let code = [0xff, 0x25, 0xca, 0x2f, 0x00, 0x00];
+/// let addr = Addr::from(0x1030usize);
+/// assert_eq!(jump_slot(&code, addr), Some(Addr::from(0x4000usize)));
+/// assert_eq!(jump_slot(&[0xc3], addr), None);
+/// ```
+#[must_use]
+pub fn jump_slot(code: &[u8], addr: Addr) -> Option<Addr> {
+    let mut decoder = Decoder::with_ip(CODE_BITNESS, code, addr.into(), DecoderOptions::NONE);
+    let mut instruction = decoder.decode();
+    if instruction.mnemonic() == Mnemonic::Endbr64 {
+        instruction = decoder.decode();
+    }
+    (instruction.flow_control() == FlowControl::IndirectBranch
+        && instruction.is_ip_rel_memory_operand())
+    .then(|| instruction.ip_rel_memory_address().into())
+}
+
+/// Checks if an instruction is typical for the prologue of a function, see [`prologue_end`]
+fn is_prologue_instruction(instruction: &Instruction) -> bool {
+    const CALLEE_SAVED: &[Register] = &[
+        Register::RBP,
+        Register::RBX,
+        Register::R12,
+        Register::R13,
+        Register::R14,
+        Register::R15,
+    ];
+    const PARAMETERS: &[Register] = &[
+        Register::RDI,
+        Register::RSI,
+        Register::RDX,
+        Register::RCX,
+        Register::R8,
+        Register::R9,
+        Register::XMM0,
+        Register::XMM1,
+        Register::XMM2,
+        Register::XMM3,
+        Register::XMM4,
+        Register::XMM5,
+        Register::XMM6,
+        Register::XMM7,
+    ];
+
+    match instruction.mnemonic() {
+        Mnemonic::Endbr64 => true,
+        Mnemonic::Push => CALLEE_SAVED.contains(&instruction.op0_register()),
+        Mnemonic::Mov if instruction.op0_kind() == OpKind::Register => {
+            instruction.op0_register() == Register::RBP
+                && instruction.op1_register() == Register::RSP
+        }
+        Mnemonic::Sub => {
+            instruction.op0_register() == Register::RSP
+                && matches!(
+                    instruction.op1_kind(),
+                    OpKind::Immediate8to64 | OpKind::Immediate32to64
+                )
+        }
+        Mnemonic::Mov | Mnemonic::Movss | Mnemonic::Movsd | Mnemonic::Movaps => {
+            instruction.op0_kind() == OpKind::Memory
+                && matches!(instruction.memory_base(), Register::RBP | Register::RSP)
+                && PARAMETERS.contains(&instruction.op1_register().full_register())
+        }
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::addr::Addr;
-    use crate::disassemble::Disassembly;
+    use crate::disassemble::{instruction_flow, prologue_end, Disassembly, InstructionFlow};
 
     const SOME_MACHINE_CODE: &[u8] = &[
         0x48, 0x83, 0xec, 0x08, 0x48, 0x8b, 0x05, 0xbd, 0x1f, 0x02, 0x00, 0x48, 0x85, 0xc0, 0x74,
@@ -421,4 +616,39 @@ mod test {
         let json = serde_json::to_string(&dis).unwrap();
         println!("{json}");
     }
+
+    #[test]
+    fn test_prologue_end() {
+        // push rbp; mov rbp,rsp; sub rsp,0x20; mov [rbp-0x14],edi; mov edx,[rbp-0x14]
+        let code = [
+            0x55, 0x48, 0x89, 0xe5, 0x48, 0x83, 0xec, 0x20, 0x89, 0x7d, 0xec, 0x8b, 0x55, 0xec,
+        ];
+        let addr = Addr::from(SOME_ADDR);
+        assert_eq!(prologue_end(&code, addr), addr + 11);
+
+        // only sub rsp,8
+        assert_eq!(prologue_end(SOME_MACHINE_CODE, addr), addr + 4);
+    }
+
+    #[test]
+    fn test_instruction_flow() {
+        let addr = Addr::from(SOME_ADDR);
+        // call rax
+        assert_eq!(
+            instruction_flow(&SOME_MACHINE_CODE[16..], addr),
+            InstructionFlow::Call {
+                return_addr: addr + 2
+            }
+        );
+        assert_eq!(
+            instruction_flow(&SOME_MACHINE_CODE[22..], addr),
+            InstructionFlow::Return
+        );
+        // je
+        assert_eq!(
+            instruction_flow(&SOME_MACHINE_CODE[14..], addr),
+            InstructionFlow::Other
+        );
+        assert_eq!(instruction_flow(&[], addr), InstructionFlow::Other);
+    }
 }
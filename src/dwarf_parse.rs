@@ -23,7 +23,7 @@ use tracing::{trace, warn};
 use crate::dbginfo::GimliLocation;
 use crate::debuggee::Debuggee;
 use crate::errors::{DebuggerError, Result};
-use crate::{mem_read, Addr};
+use crate::Addr;
 
 /// Type alias for the Gimli reader used throughout the module
 ///
@@ -245,6 +245,76 @@ impl Debuggee {
         }
     }
 
+    /// Parses a DWARF byte size attribute (`DW_AT_byte_size`)
+    ///
+    /// # Parameters
+    ///
+    /// * `attribute` - The attribute to parse
+    ///
+    /// # Returns
+    ///
+    /// * `Some(usize)` - The size in bytes
+    /// * `None` - If the attribute is not present or not a constant
+    pub(crate) fn parse_byte_size(
+        attribute: Option<gimli::Attribute<GimliReaderThing>>,
+    ) -> Option<usize> {
+        let a = attribute?;
+        let size = a.udata_value();
+        if size.is_none() {
+            warn!(
+                "tried to parse a byte size DWARF attribute that was not a constant: {:?}",
+                a.value()
+            );
+        }
+        size.map(|s| s as usize)
+    }
+
+    /// Parses a DWARF member offset attribute (`DW_AT_data_member_location`)
+    ///
+    /// # Parameters
+    ///
+    /// * `attribute` - The attribute to parse
+    ///
+    /// # Returns
+    ///
+    /// * `Some(usize)` - The offset of the member in bytes
+    /// * `None` - If the attribute is not present or not a constant
+    pub(crate) fn parse_member_offset(
+        attribute: Option<gimli::Attribute<GimliReaderThing>>,
+    ) -> Option<usize> {
+        let a = attribute?;
+        let offset = a.udata_value();
+        if offset.is_none() {
+            warn!(
+                "tried to parse a member offset DWARF attribute that was not a constant: {:?}",
+                a.value()
+            );
+        }
+        offset.map(|o| o as usize)
+    }
+
+    /// Parses a DWARF base type encoding attribute (`DW_AT_encoding`)
+    ///
+    /// # Parameters
+    ///
+    /// * `attribute` - The attribute to parse
+    ///
+    /// # Returns
+    ///
+    /// * `Some(DwAte)` - How the values of the base type are encoded
+    /// * `None` - If the attribute is not present or of the wrong type
+    pub(crate) fn parse_base_encoding(
+        attribute: Option<gimli::Attribute<GimliReaderThing>>,
+    ) -> Option<gimli::DwAte> {
+        match attribute?.value() {
+            gimli::AttributeValue::Encoding(encoding) => Some(encoding),
+            other => {
+                warn!("tried to parse a base type encoding DWARF attribute that was a {other:?}");
+                None
+            }
+        }
+    }
+
     /// Parses a DWARF location attribute
     ///
     /// Location attributes describe where a variable or parameter is stored,
@@ -332,14 +402,14 @@ impl Debuggee {
                 } => {
                     let mut buff = vec![0; size as usize];
                     let addr: Addr = address.into(); // NOTE: may be relative?
-                    let read_this_many_bytes = mem_read(&mut buff, self.pid, addr)?;
+                    let read_this_many_bytes = self.read_memory(&mut buff, addr)?;
                     assert_eq!(size as usize, read_this_many_bytes);
                     let value = to_value(size, &buff);
                     res = evaluation.resume_with_memory(value)?;
                 }
                 gimli::EvaluationResult::RequiresRegister { register, .. /* ignore the actual type and give as word */ } => {
                     let reg_kind= crate::Register::try_from(register)?;
-                    let reg_value = crate::get_reg(self.pid, reg_kind)?;
+                    let reg_value = self.get_reg(self.selected_thread, reg_kind)?;
                     res = evaluation.resume_with_register(gimli::Value::from_u64(gimli::ValueType::Generic, reg_value)?)?;
                 }
                 gimli::EvaluationResult::RequiresFrameBase =>{
@@ -351,7 +421,9 @@ impl Debuggee {
                     )?;
                 }
                 gimli::EvaluationResult::RequiresCallFrameCfa => {
-                    let cfa: Addr = frame_info.canonical_frame_address.expect("no cfa was given");
+                    let cfa: Addr = frame_info
+                        .canonical_frame_address
+                        .ok_or(DebuggerError::NoFrameInfo)?;
                     trace!("cfa: {cfa}");
                     res = evaluation.resume_with_call_frame_cfa(cfa.into())?;
                 }
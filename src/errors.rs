@@ -76,6 +76,24 @@ pub enum DebuggerError {
     BreakpointIsAlreadyEnabled,
     #[error("Tried to disable breakpoint again")]
     BreakpointIsAlreadyDisabled,
+    #[error("There is no breakpoint at {0}")]
+    NoSuchBreakpoint(Addr),
+    #[error("Conditions cannot compare floating point values")]
+    FloatInCondition,
+    #[error("Not allowed as a command of a breakpoint: {0}")]
+    NotABreakpointCommand(String),
+    #[error("All debug registers are in use")]
+    NoFreeDebugRegister,
+    #[error("Cannot watch {1} bytes at {0}, the memory is empty or not mapped")]
+    InvalidWatchpoint(Addr, usize),
+    #[error("There already is a watchpoint at {0}")]
+    WatchpointExists(Addr),
+    #[error("There is no watchpoint at {0}")]
+    NoSuchWatchpoint(Addr),
+    #[error("Cannot watch reads of the page of code at {0}, use a write watchpoint")]
+    WatchpointOnCode(Addr),
+    #[error("The variable is not stored in memory: {0}")]
+    VariableNotInMemory(String),
     #[error("Could not parse integer: {0}")]
     ParseInt(
         #[serde(serialize_with = "ser_err")]
@@ -115,6 +133,12 @@ pub enum DebuggerError {
     DwTagNotImplemented(#[serde(serialize_with = "ser_dwtag")] DwTag),
     #[error("Tried stepping out of main function, this makes no sense")]
     StepOutMain,
+    #[error("The function at {0} has no return address, it is the outermost frame")]
+    NoReturnAddress(Addr),
+    #[error("The call frame information uses a rule that is not supported: {0}")]
+    UnsupportedUnwindRule(String),
+    #[error("Cannot decode a return value of this type: {0}")]
+    UnsupportedReturnType(String),
     #[error("Unwind Error: {0}")]
     Unwind(
         #[serde(serialize_with = "ser_err")]
@@ -141,12 +165,42 @@ pub enum DebuggerError {
     NoDatatypeFound,
     #[error("The debuggee is currently not in a known function")]
     NotInFunction,
+    #[error("No function with an address found for the name: {0}")]
+    FunctionNotFound(String),
+    #[error("No code found for line {1} or after it in {0:?}")]
+    LineNotFound(std::path::PathBuf, u64),
+    #[error("No source line information for the code at {0}")]
+    NoLineInfo(Addr),
     #[error("A required attribute did not exist: {0:?}")]
     AttributeDoesNotExist(#[serde(serialize_with = "ser_dwat")] gimli::DwAt),
     #[error("While parsing a DWARF location: no frame information was provided")]
     NoFrameInfo,
     #[error("Tried to run a program while one was already running")]
     AlreadyRunning,
+    #[error("The debuggee exited with code {0} before it could be debugged")]
+    LaunchFailed(i32),
+    #[error("There is nothing to restart, no program was run before")]
+    NoPreviousRun,
+    #[error("The debuggee is running, interrupt it first")]
+    DebuggeeRunning,
+    #[error("The debuggee is not running")]
+    DebuggeeNotRunning,
+    #[error("The debuggee has no thread with the thread ID {0}")]
+    NoSuchThread(i32),
+    #[error("No process with the process ID {0} is under the control of the debugger")]
+    NoSuchProcess(i32),
+    #[error("Invalid core file: {0}")]
+    InvalidCoreDump(String),
+    #[error("The memory at {0} is not contained in the core file")]
+    NotInCoreDump(Addr),
+    #[error("The debuggee is a core file, this needs a live process")]
+    CoreDumpNotLive,
+    #[error("Thread {0} did not stop after a single step: {1}")]
+    StepFailed(i32, String),
+    #[error("Unknown syscall: {0}")]
+    UnknownSyscall(String),
+    #[error("There is no skip rule '{0}'")]
+    NoSkipRule(String),
     #[error("Found multiple DWARF entries for an operation that was supposed to only find one")]
     MultipleDwarfEntries,
     #[error("Working with JSON failed: {0}")]
@@ -25,13 +25,24 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 #[cfg(feature = "plugins")]
 use steckrs::PluginIDOwned;
 
-use crate::breakpoint::Breakpoint;
+use crate::breakpoint::{Breakpoint, BreakpointInfo};
+use crate::condition::Condition;
 use crate::dbginfo::OwnedSymbol;
 use crate::disassemble::Disassembly;
 use crate::errors::DebuggerError;
+use crate::launch::LaunchOptions;
+use crate::location::{Location, UntilStop};
 use crate::memorymap::ProcessMemoryMap;
+use crate::process::{FollowForkMode, ForkEvent, ProcessInfo};
+use crate::returnvalue::ReturnValue;
+use crate::sharedlib::{LibraryEvent, LibraryInfo};
+use crate::signal::{SignalPolicy, SignalPolicyTable};
+use crate::skiplist::{SkipList, SkipRule};
+use crate::syscall::SyscallEvent;
+use crate::thread::ThreadInfo;
 use crate::unwind::Backtrace;
-use crate::variable::VariableValue;
+use crate::variable::{VariableExpression, VariableValue};
+use crate::watchpoint::{WatchKind, Watchpoint, WatchpointHit};
 use crate::{Addr, Register, Word};
 
 /// Represents a command from the UI to the debugger
@@ -45,6 +56,7 @@ use crate::{Addr, Register, Word};
 /// ```
 /// use coreminer::feedback::Status;
 /// use coreminer::addr::Addr;
+/// use coreminer::launch::LaunchOptions;
 /// use coreminer::Register;
 /// use std::path::Path;
 ///
@@ -58,10 +70,10 @@ use crate::{Addr, Register, Word};
 /// let status = Status::SetRegister(Register::rax, 0x42);
 ///
 /// // Command to run a executable in the debugger
-/// let status = Status::Run(Path::new("/bin/ls").into(), vec![]);
+/// let status = Status::Run(Path::new("/bin/ls").into(), vec![], LaunchOptions::default());
 /// ```
 #[non_exhaustive]
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "PascalCase")]
 pub enum Status {
     /// Generate a backtrace of the call stack
@@ -76,9 +88,24 @@ pub enum Status {
     /// Step out of the current function
     StepOut,
 
+    /// Step out of the current function and show what it returned
+    Finish,
+
     /// Step a single instruction
     StepSingle,
 
+    /// Step to the next source line, into called functions that have line information
+    StepLine,
+
+    /// Step to the next source line, over called functions
+    NextLine,
+
+    /// Continue until the debuggee reaches a [`Location`]
+    ///
+    /// No breakpoint is left at the location, also if something else stops the debuggee first.
+    /// The debugger answers with [`Feedback::RunUntil`].
+    RunUntil(Location),
+
     /// Look up symbols by name
     GetSymbolsByName(String),
 
@@ -94,15 +121,88 @@ pub enum Status {
     /// Continue execution
     Continue,
 
+    /// Continue execution in the background
+    ///
+    /// The debugger answers with [`Feedback::Running`] right away and accepts
+    /// [`Status::Interrupt`] while the debuggee runs. When the debuggee stops, the stop is
+    /// reported like for [`Status::Continue`].
+    ContinueAsync,
+
+    /// Stop a debuggee that runs in the background
+    Interrupt,
+
     /// Set a breakpoint at the specified address
     SetBreakpoint(Addr),
 
+    /// Set a breakpoint at the function with the given name, after its prologue
+    ///
+    /// The resolved address is returned as [`Feedback::Addr`]. If no loaded object has the
+    /// function, the breakpoint is pending until a shared library that has it is loaded, which
+    /// is returned as [`Feedback::PendingBreakpoint`].
+    SetBreakpointAtSymbol(String),
+
+    /// Set breakpoints at all addresses of a line in a source file
+    ///
+    /// If the line has no code, the next line with code is used. The line and the addresses
+    /// are returned as [`Feedback::LineAddrs`].
+    SetBreakpointAtLine(PathBuf, u64),
+
+    /// Set or remove the condition of the breakpoint at the specified address
+    ///
+    /// The debuggee only stops at the breakpoint if the condition is true.
+    SetBreakpointCondition(Addr, Option<Condition>),
+
+    /// Ignore the next hits of the breakpoint at the specified address
+    ///
+    /// Only hits where the condition of the breakpoint is true are counted.
+    SetBreakpointIgnoreCount(Addr, u64),
+
+    /// Set the commands that run when the breakpoint at the specified address is hit
+    ///
+    /// If a breakpoint has commands, the debuggee does not stop at it. The commands run and
+    /// their feedback is reported as one [`Feedback::Logpoint`], then the debuggee continues.
+    /// An empty list removes the commands. Commands that control the execution, like
+    /// [`Status::Continue`], are not allowed.
+    SetBreakpointCommands(Addr, Vec<Status>),
+
+    /// Set a breakpoint at the specified address that is removed after it was hit once
+    SetTemporaryBreakpoint(Addr),
+
+    /// Set a breakpoint at the specified address that uses a debug register instead of `INT3`
+    ///
+    /// The code of the debuggee is not changed. At most four hardware breakpoints can be set.
+    SetHardwareBreakpoint(Addr),
+
+    /// Watch a region in memory: an address, a length in bytes and which accesses to stop at
+    ///
+    /// When the region is accessed, the debuggee stops and [`Feedback::Watchpoint`] is
+    /// reported. Watchpoints share the four debug registers with hardware breakpoints, regions
+    /// that do not fit into one or that find none free are watched in software.
+    SetWatchpoint(Addr, usize, WatchKind),
+
+    /// Watch the location of a variable in the current function, like [`Status::SetWatchpoint`]
+    ///
+    /// The address of the variable is returned as [`Feedback::Addr`].
+    SetWatchpointAtVariable(VariableExpression, WatchKind),
+
+    /// Remove the watchpoint at the specified address
+    DelWatchpoint(Addr),
+
+    /// List all watchpoints with their hit counts and last values
+    ListWatchpoints,
+
+    /// List all breakpoints with their state, hit counts and settings
+    ListBreakpoints,
+
     /// Get a breakpoint at the specified address
     GetBreakpoint(Addr),
 
     /// Remove a breakpoint at the specified address
     DelBreakpoint(Addr),
 
+    /// List the shared libraries that are loaded into the debuggee
+    ListLibraries,
+
     /// Get all register values
     DumpRegisters,
 
@@ -115,9 +215,6 @@ pub enum Status {
     /// Read a value from memory
     ReadMem(Addr),
 
-    /// Show debugger information
-    Infos,
-
     /// Read a variable's value
     ReadVariable(String),
 
@@ -130,17 +227,85 @@ pub enum Status {
     /// Show the process memory map
     ProcMap,
 
-    /// Run a new program
+    /// Run a new program with arguments
+    ///
+    /// The [`LaunchOptions`] may be left out in JSON, the debuggee then inherits everything
+    /// from the debugger.
     Run(
         PathBuf,
         #[serde(serialize_with = "serialize_cstring_vec")]
         #[serde(deserialize_with = "deserialize_cstring_vec")]
         Vec<CString>,
+        #[serde(default)] LaunchOptions,
     ),
 
+    /// Kill the debuggee and run the last program again, keeping the breakpoints
+    Restart,
+
+    /// Attach to an already running process with the given process ID
+    Attach(i32),
+
+    /// Detach from the debuggee, removing all breakpoints and letting it continue
+    Detach,
+
+    /// Load the core file of a crashed process to inspect it
+    ///
+    /// The debug symbols are read from the executable, if it is left out, the executable
+    /// recorded in the core file is used.
+    LoadCore(PathBuf, #[serde(default)] Option<PathBuf>),
+
+    /// Write a core file of the live debuggee to the given path, like `gcore`
+    GenerateCore(PathBuf),
+
+    /// Write the program, breakpoints, watchpoints, plugin states and signal policies to a
+    /// [session file](crate::session) at the given path
+    SaveSession(PathBuf),
+
+    /// Restore a [session file](crate::session) and launch its program again
+    LoadSession(PathBuf),
+
     /// Set the last signal with the number of the signal
     SetLastSignal(i32),
 
+    /// List all threads of the debuggee
+    ListThreads,
+
+    /// Select the thread with the given thread ID for register, backtrace and stepping operations
+    SelectThread(i32),
+
+    /// Set which process is debugged after the debuggee created a new process
+    SetFollowFork(FollowForkMode),
+
+    /// List all processes under the control of the debugger
+    ListProcesses,
+
+    /// Select the held process with the given process ID as the debuggee
+    SelectProcess(i32),
+
+    /// Enable or disable stopping at every syscall of the debuggee
+    TraceSyscalls(bool),
+
+    /// Stop at the entry and exit of the syscall with the given name or number
+    CatchSyscall(String),
+
+    /// Remove the catchpoint for the syscall with the given name or number
+    DelCatchSyscall(String),
+
+    /// Set if the debuggee stops at, prints and gets the signal with the given number
+    SetSignalPolicy(i32, SignalPolicy),
+
+    /// List the policies of all signals
+    ListSignalPolicies,
+
+    /// Add a rule for code that stepping does not stop in, see [`crate::skiplist`]
+    AddSkip(SkipRule),
+
+    /// Remove a rule of the skip list
+    DelSkip(SkipRule),
+
+    /// List the rules of the skip list
+    ListSkips,
+
     /// To be used by plugin hooks if the hook is done
     #[serde(skip)]
     #[cfg(feature = "plugins")]
@@ -159,6 +324,83 @@ pub enum Status {
     PluginGetList,
 }
 
+impl Status {
+    /// Checks if the status needs a live debuggee, so that it cannot be used with a core file
+    pub(crate) fn needs_live_debuggee(&self) -> bool {
+        matches!(
+            self,
+            Status::Continue
+                | Status::ContinueAsync
+                | Status::Interrupt
+                | Status::SetBreakpoint(_)
+                | Status::SetBreakpointAtSymbol(_)
+                | Status::SetBreakpointAtLine(..)
+                | Status::SetBreakpointCondition(..)
+                | Status::SetBreakpointIgnoreCount(..)
+                | Status::SetTemporaryBreakpoint(_)
+                | Status::SetHardwareBreakpoint(_)
+                | Status::SetWatchpoint(..)
+                | Status::SetWatchpointAtVariable(..)
+                | Status::DelWatchpoint(_)
+                | Status::ListWatchpoints
+                | Status::SetBreakpointCommands(..)
+                | Status::DelBreakpoint(_)
+                | Status::SetRegister(..)
+                | Status::WriteMem(..)
+                | Status::StepSingle
+                | Status::StepOut
+                | Status::Finish
+                | Status::StepInto
+                | Status::StepOver
+                | Status::StepLine
+                | Status::NextLine
+                | Status::RunUntil(_)
+                | Status::WriteVariable(..)
+                | Status::Restart
+                | Status::Detach
+                | Status::SetLastSignal(_)
+                | Status::ListProcesses
+                | Status::SelectProcess(_)
+                | Status::TraceSyscalls(_)
+                | Status::CatchSyscall(_)
+                | Status::DelCatchSyscall(_)
+                | Status::GenerateCore(_)
+                | Status::ListLibraries
+        )
+    }
+
+    /// Checks if the status controls the execution of the debuggee, so that it cannot be a
+    /// command of a breakpoint
+    pub(crate) fn controls_execution(&self) -> bool {
+        match self {
+            Status::Continue
+            | Status::ContinueAsync
+            | Status::Interrupt
+            | Status::StepSingle
+            | Status::StepOut
+            | Status::Finish
+            | Status::StepInto
+            | Status::StepOver
+            | Status::StepLine
+            | Status::NextLine
+            | Status::RunUntil(_)
+            | Status::Run(..)
+            | Status::Restart
+            | Status::Attach(_)
+            | Status::Detach
+            | Status::LoadCore(..)
+            | Status::LoadSession(_)
+            | Status::SelectProcess(_)
+            | Status::SelectThread(_)
+            | Status::SetFollowFork(_)
+            | Status::DebuggerQuit => true,
+            #[cfg(feature = "plugins")]
+            Status::PluginContinue => true,
+            _ => false,
+        }
+    }
+}
+
 /// Represents the result of a debugging operation
 ///
 /// [`Feedback`] is used to communicate the results of debugging operations
@@ -201,6 +443,13 @@ pub enum Feedback {
     /// Memory address
     Addr(Addr),
 
+    /// The breakpoint for the function with this name is set when a shared library that has it
+    /// is loaded
+    PendingBreakpoint(String),
+
+    /// A source line and the addresses of its code
+    LineAddrs(u64, Vec<Addr>),
+
     /// Register values
     Registers(UserRegs),
 
@@ -222,6 +471,12 @@ pub enum Feedback {
     /// Variable value
     Variable(VariableValue),
 
+    /// The value a function returned after [`Status::Finish`]
+    ReturnValue(ReturnValue),
+
+    /// Why the debuggee stopped after [`Status::RunUntil`]
+    RunUntil(UntilStop),
+
     /// Stack contents
     Stack(crate::stack::Stack),
 
@@ -234,6 +489,53 @@ pub enum Feedback {
     /// Returns a requested [`Breakpoint`]
     Breakpoint(Option<Breakpoint>),
 
+    /// All breakpoints, sorted by address
+    Breakpoints(Vec<BreakpointInfo>),
+
+    /// The debuggee hit the breakpoint at the address and ran its commands, this is the
+    /// feedback of each command
+    ///
+    /// The debuggee continues right after it.
+    Logpoint(Addr, Vec<Feedback>),
+
+    /// The debuggee accessed a watched location
+    Watchpoint(WatchpointHit),
+
+    /// All watchpoints, sorted by address
+    Watchpoints(Vec<Watchpoint>),
+
+    /// Threads of the debuggee
+    Threads(Vec<ThreadInfo>),
+
+    /// The debuggee created a new process with `fork` or `vfork`
+    Fork(ForkEvent),
+
+    /// The debuggee executed a new program, the debug information was reloaded for it
+    Exec(PathBuf),
+
+    /// Processes under the control of the debugger
+    Processes(Vec<ProcessInfo>),
+
+    /// The debuggee stopped at the entry or exit of a syscall
+    Syscall(SyscallEvent),
+
+    /// The shared libraries that are loaded into the debuggee
+    Libraries(Vec<LibraryInfo>),
+
+    /// The dynamic loader loaded or unloaded shared libraries
+    ///
+    /// The debuggee continues right after it.
+    Library(LibraryEvent),
+
+    /// The policies of all signals
+    SignalPolicies(SignalPolicyTable),
+
+    /// The rules of the skip list
+    SkipList(SkipList),
+
+    /// The debuggee runs in the background
+    Running,
+
     #[cfg(feature = "plugins")]
     /// Information on if a plugin is enabled
     ///
@@ -267,14 +569,87 @@ impl Display for Feedback {
             Feedback::Registers(regs) => write!(f, "Registers: {regs:#x?}")?,
             Feedback::Word(w) => write!(f, "Word: {w:#018x?}")?,
             Feedback::Addr(w) => write!(f, "Address: {w}")?,
+            Feedback::PendingBreakpoint(name) => write!(f, "Pending breakpoint for {name}")?,
+            Feedback::LineAddrs(line, addrs) => {
+                write!(f, "Line {line}:")?;
+                addrs.iter().try_for_each(|addr| write!(f, " {addr}"))?;
+            }
             Feedback::Disassembly(t) => write!(f, "{t:#?}")?,
             Feedback::Symbols(t) => write!(f, "Symbols: {t:#?}")?,
             Feedback::Backtrace(t) => write!(f, "Backtrace: {t:#?}")?,
             Feedback::Variable(t) => write!(f, "Variable: {t:#?}")?,
+            Feedback::ReturnValue(rv) => write!(f, "{rv}")?,
+            Feedback::RunUntil(stop) => write!(f, "{stop}")?,
             Feedback::Stack(t) => write!(f, "Stack:\n{t}")?,
             Feedback::ProcessMap(pm) => write!(f, "Process Map:\n{pm:#x?}")?,
             Feedback::Exit(code) => write!(f, "Debugee exited with code {code}")?,
             Feedback::Breakpoint(bp) => write!(f, "Breakpoint: {bp:?}")?,
+            Feedback::Breakpoints(bps) => {
+                write!(f, "Breakpoints:")?;
+                for bp in bps {
+                    write!(f, "\n  {bp}")?;
+                }
+            }
+            Feedback::Watchpoint(hit) => write!(f, "{hit}")?,
+            Feedback::Watchpoints(wps) => {
+                write!(f, "Watchpoints:")?;
+                for wp in wps {
+                    write!(f, "\n  {wp}")?;
+                }
+            }
+            Feedback::Logpoint(addr, feedbacks) => {
+                write!(f, "Logpoint at {addr}:")?;
+                for feedback in feedbacks {
+                    write!(f, "\n  {feedback}")?;
+                }
+            }
+            Feedback::Threads(threads) => {
+                write!(f, "Threads:")?;
+                for t in threads {
+                    write!(
+                        f,
+                        "\n{} {:<8} {:<16} {}",
+                        if t.selected { "*" } else { " " },
+                        t.tid,
+                        t.name.as_deref().unwrap_or("??"),
+                        t.rip.map_or("??".to_string(), |a| a.to_string())
+                    )?;
+                }
+            }
+            Feedback::Fork(fe) => write!(
+                f,
+                "Process {} created process {} with {}, following {}",
+                fe.parent,
+                fe.child,
+                if fe.vfork { "vfork" } else { "fork" },
+                fe.mode
+            )?,
+            Feedback::Exec(path) => write!(f, "Debuggee executed '{}'", path.to_string_lossy())?,
+            Feedback::Processes(processes) => {
+                write!(f, "Processes:")?;
+                for p in processes {
+                    write!(
+                        f,
+                        "\n{} {:<8} {}",
+                        if p.selected { "*" } else { " " },
+                        p.pid,
+                        p.executable
+                            .as_ref()
+                            .map_or("??".into(), |e| e.to_string_lossy())
+                    )?;
+                }
+            }
+            Feedback::Syscall(event) => write!(f, "Syscall: {event}")?,
+            Feedback::Libraries(libraries) => {
+                write!(f, "Libraries:")?;
+                for library in libraries {
+                    write!(f, "\n  {library}")?;
+                }
+            }
+            Feedback::Library(event) => write!(f, "{event}")?,
+            Feedback::SignalPolicies(table) => write!(f, "Signal Policies:\n{table}")?,
+            Feedback::SkipList(list) => write!(f, "{list}")?,
+            Feedback::Running => write!(f, "Running in the background")?,
             Feedback::Internal(_) => write!(f, "Internal Feedback")?,
             #[cfg(feature = "plugins")]
             Feedback::PluginStatus(ps) => write!(f, "Plugin Status: {ps:?}")?,
@@ -370,7 +745,7 @@ impl From<user_regs_struct> for UserRegs {
     }
 }
 
-fn serialize_cstring_vec<S>(items: &[CString], serializer: S) -> Result<S::Ok, S::Error>
+pub(crate) fn serialize_cstring_vec<S>(items: &[CString], serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -381,7 +756,7 @@ where
     strings.serialize(serializer)
 }
 
-fn deserialize_cstring_vec<'de, D>(deserializer: D) -> Result<Vec<CString>, D::Error>
+pub(crate) fn deserialize_cstring_vec<'de, D>(deserializer: D) -> Result<Vec<CString>, D::Error>
 where
     D: Deserializer<'de>,
 {
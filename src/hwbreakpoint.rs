@@ -0,0 +1,426 @@
+//! # Hardware Breakpoint Module
+//!
+//! Provides breakpoints that use the debug registers of x86-64 instead of changing the code of
+//! the debuggee.
+//!
+//! A software [`Breakpoint`](crate::breakpoint::Breakpoint) writes `INT3` into the text of the
+//! debuggee, which code that checksums itself notices. A [`HardwareBreakpoint`] leaves the memory
+//! alone: the address is written into one of the debug registers `DR0` to `DR3`, and enabled in
+//! the control register `DR7`. The CPU then stops the thread before it executes the instruction
+//! at that address, and the kernel reports a `SIGTRAP` with
+//! [`TRAP_HWBKPT`](crate::consts::TRAP_HWBKPT).
+//!
+//! The debug registers can also break on writes or reads of memory, which is used for the
+//! [watchpoints](crate::watchpoint). There are only four debug registers, so at most four hardware
+//! breakpoints and watchpoints can be set together. The debug registers belong to each thread,
+//! they are written with `PTRACE_POKEUSER` before a thread is resumed, see [`DebugRegisters`].
+
+use nix::sys::ptrace;
+use nix::unistd::Pid;
+use tracing::{debug, trace};
+
+use crate::breakpoint::{count_hit, BreakpointInfo, BreakpointKind, BreakpointSettings};
+use crate::debuggee::Debuggee;
+use crate::errors::{DebuggerError, Result};
+use crate::thread::Thread;
+use crate::{Addr, Register};
+
+/// Number of debug registers that can hold an address
+pub const DEBUG_REGISTER_COUNT: usize = 4;
+/// Index of the debug status register `DR6`
+const DR_STATUS: usize = 6;
+/// Index of the debug control register `DR7`
+const DR_CONTROL: usize = 7;
+/// The resume flag in `eflags`, which makes the CPU ignore instruction breakpoints for the next
+/// instruction
+pub(crate) const EFLAGS_RF: u64 = 1 << 16;
+
+/// A breakpoint that uses a debug register instead of `INT3`
+///
+/// Unlike a [`Breakpoint`](crate::breakpoint::Breakpoint), the debuggee stops before the
+/// instruction at the address is executed, so the instruction pointer is the address of the
+/// breakpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HardwareBreakpoint {
+    addr: Addr,
+    slot: usize,
+    hits: u64,
+    settings: BreakpointSettings,
+}
+
+/// What the CPU watches the address in a debug register for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Trigger {
+    /// Executing the instruction at the address
+    Execute,
+    /// Writing to the given number of bytes at the address
+    Write(usize),
+    /// Reading or writing the given number of bytes at the address
+    ReadWrite(usize),
+}
+
+/// The debug registers the threads of a debuggee should have
+///
+/// Every change increases the generation. Each [`Thread`] remembers the generation that was
+/// last written into its debug registers, so that only outdated threads are written, and new
+/// threads get the debug registers before they run for the first time.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DebugRegisters {
+    slots: [Option<(Addr, Trigger)>; DEBUG_REGISTER_COUNT],
+    generation: u64,
+}
+
+impl HardwareBreakpoint {
+    /// Creates a hardware breakpoint that uses the debug register `slot`
+    #[must_use]
+    pub(crate) fn new(addr: Addr, slot: usize, settings: BreakpointSettings) -> Self {
+        Self {
+            addr,
+            slot,
+            hits: 0,
+            settings,
+        }
+    }
+
+    /// Returns the address of the breakpoint
+    #[must_use]
+    pub fn addr(&self) -> Addr {
+        self.addr
+    }
+
+    /// Returns the index of the debug register that holds the address, 0 for `DR0`
+    #[must_use]
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    /// Returns how often the breakpoint was hit while its condition was true
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the settings of the breakpoint
+    #[must_use]
+    pub fn settings(&self) -> &BreakpointSettings {
+        &self.settings
+    }
+
+    /// Returns the settings of the breakpoint to change them
+    pub(crate) fn settings_mut(&mut self) -> &mut BreakpointSettings {
+        &mut self.settings
+    }
+
+    /// Counts a hit of the breakpoint where its condition is true, like [`Breakpoint::hit`]
+    ///
+    /// [`Breakpoint::hit`]: crate::breakpoint::Breakpoint::hit
+    pub fn hit(&mut self) -> bool {
+        count_hit(&mut self.hits, &mut self.settings)
+    }
+
+    /// Returns the information on this breakpoint that is listed to the user
+    #[must_use]
+    pub fn info(&self) -> BreakpointInfo {
+        BreakpointInfo {
+            addr: self.addr,
+            kind: BreakpointKind::Hardware,
+            enabled: true,
+            hits: self.hits,
+            settings: self.settings.clone(),
+        }
+    }
+}
+
+impl Trigger {
+    /// Returns the condition and length bits of this trigger for `DR7`, condition first
+    ///
+    /// The length of an execution breakpoint must be 1, which is encoded as 0.
+    #[must_use]
+    fn control_bits(self) -> u64 {
+        let (condition, len) = match self {
+            Self::Execute => (0b00, 1),
+            Self::Write(len) => (0b01, len),
+            Self::ReadWrite(len) => (0b11, len),
+        };
+        let len = match len {
+            2 => 0b01,
+            8 => 0b10,
+            4 => 0b11,
+            _ => 0b00,
+        };
+        condition | len << 2
+    }
+}
+
+impl DebugRegisters {
+    /// Takes a free debug register for an address
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`DebuggerError::NoFreeDebugRegister`] if all debug registers are in use.
+    fn alloc(&mut self, addr: Addr, trigger: Trigger) -> Result<usize> {
+        let slot = self
+            .slots
+            .iter()
+            .position(Option::is_none)
+            .ok_or(DebuggerError::NoFreeDebugRegister)?;
+        self.slots[slot] = Some((addr, trigger));
+        self.generation += 1;
+        Ok(slot)
+    }
+
+    /// Frees a debug register
+    fn free(&mut self, slot: usize) {
+        self.slots[slot] = None;
+        self.generation += 1;
+    }
+
+    /// Frees all debug registers
+    pub(crate) fn clear(&mut self) {
+        self.slots = [None; DEBUG_REGISTER_COUNT];
+        self.generation += 1;
+    }
+
+    /// Computes the value of the control register `DR7`
+    ///
+    /// Each used debug register is enabled locally (bit `2 * slot`), and the condition and
+    /// length of its [`Trigger`] are set in the four bits at `16 + 4 * slot`.
+    #[must_use]
+    pub fn control(&self) -> u64 {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, used)| used.map(|(_, trigger)| (slot, trigger)))
+            .fold(0, |dr7, (slot, trigger)| {
+                dr7 | 1 << (2 * slot) | trigger.control_bits() << (16 + 4 * slot)
+            })
+    }
+
+    /// Writes the debug registers of a thread, unless they are up to date already
+    ///
+    /// # Errors
+    ///
+    /// This function fails if `PTRACE_POKEUSER` fails, for example because an address is not in
+    /// user space.
+    pub(crate) fn apply(&self, thread: &mut Thread) -> Result<()> {
+        if thread.debug_registers_generation == self.generation {
+            return Ok(());
+        }
+        trace!(
+            "writing debug registers of thread {}: {:x?}",
+            thread.tid,
+            self.slots
+        );
+        // disable everything first, the kernel checks each address when it is enabled
+        write_debug_register(thread.tid, DR_CONTROL, 0)?;
+        for (slot, used) in self.slots.iter().enumerate() {
+            if let Some((addr, _)) = used {
+                write_debug_register(thread.tid, slot, addr.u64())?;
+            }
+        }
+        write_debug_register(thread.tid, DR_CONTROL, self.control())?;
+        thread.debug_registers_generation = self.generation;
+        Ok(())
+    }
+}
+
+impl Debuggee {
+    /// Sets a hardware breakpoint, or replaces the settings of an existing one
+    ///
+    /// The debug registers of all stopped threads are written right away, so that an invalid
+    /// address is noticed here.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if all debug registers are in use or cannot be written.
+    pub(crate) fn set_hw_breakpoint(
+        &mut self,
+        addr: Addr,
+        settings: BreakpointSettings,
+    ) -> Result<()> {
+        if let Some(bp) = self.hw_breakpoints.get_mut(&addr) {
+            debug!("replacing the settings of the hardware breakpoint at {addr}");
+            *bp.settings_mut() = settings;
+            return Ok(());
+        }
+
+        let slot = self.take_debug_register(addr, Trigger::Execute)?;
+        debug!("hardware breakpoint at {addr} uses DR{slot}");
+        self.hw_breakpoints
+            .insert(addr, HardwareBreakpoint::new(addr, slot, settings));
+        Ok(())
+    }
+
+    /// Removes a hardware breakpoint
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(true)` - If the breakpoint was removed
+    /// * `Ok(false)` - If there is no hardware breakpoint at `addr`
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the debug registers cannot be written.
+    pub(crate) fn del_hw_breakpoint(&mut self, addr: Addr) -> Result<bool> {
+        let Some(bp) = self.hw_breakpoints.remove(&addr) else {
+            return Ok(false);
+        };
+        self.release_debug_register(bp.slot())?;
+        Ok(true)
+    }
+
+    /// Removes all hardware breakpoints and watchpoints from the threads, for example before
+    /// detaching
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the debug registers cannot be written.
+    pub(crate) fn clear_hw_breakpoints(&mut self) -> Result<()> {
+        self.hw_breakpoints.clear();
+        self.watchpoints.clear();
+        self.debug_registers.clear();
+        self.apply_debug_registers()
+    }
+
+    /// Takes a free debug register and writes it into the stopped threads
+    ///
+    /// # Returns
+    ///
+    /// The index of the debug register, 0 for `DR0`
+    ///
+    /// # Errors
+    ///
+    /// This function fails if all debug registers are in use or the kernel refuses the address,
+    /// the debug register is free again then.
+    pub(crate) fn take_debug_register(&mut self, addr: Addr, trigger: Trigger) -> Result<usize> {
+        let slot = self.debug_registers.alloc(addr, trigger)?;
+        if let Err(e) = self.apply_debug_registers() {
+            self.debug_registers.free(slot);
+            self.apply_debug_registers()?;
+            return Err(e);
+        }
+        Ok(slot)
+    }
+
+    /// Frees a debug register and clears it in the stopped threads
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the debug registers cannot be written.
+    pub(crate) fn release_debug_register(&mut self, slot: usize) -> Result<()> {
+        self.debug_registers.free(slot);
+        self.apply_debug_registers()
+    }
+
+    /// Writes the debug registers of all stopped threads that are outdated
+    ///
+    /// Running threads get them before they are resumed the next time.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the debug registers of a thread cannot be written.
+    pub(crate) fn apply_debug_registers(&mut self) -> Result<()> {
+        for thread in self.threads.values_mut().filter(|t| !t.is_running()) {
+            self.debug_registers.apply(thread)?;
+        }
+        Ok(())
+    }
+
+    /// Returns all hardware breakpoints
+    pub fn hw_breakpoints(&self) -> impl Iterator<Item = &HardwareBreakpoint> {
+        self.hw_breakpoints.values()
+    }
+
+    /// Sets or clears the resume flag of a thread that is at a hardware breakpoint
+    ///
+    /// With the flag set, the thread executes the instruction at the breakpoint when it is
+    /// resumed, instead of stopping at it again. The kernel sets the flag after a hit, but not
+    /// when the thread got to the address in another way, like by single stepping. A thread that
+    /// hit the breakpoint while the debugger was stopping all threads needs the flag cleared, so
+    /// that it hits the breakpoint again and the hit is reported.
+    ///
+    /// Threads that are not at a hardware breakpoint are left alone.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the registers of the thread cannot be read or written.
+    pub(crate) fn set_resume_flag(&self, tid: Pid, resume: bool) -> Result<()> {
+        let rip = Addr::from(crate::get_reg(tid, Register::rip)?);
+        if !self.hw_breakpoints.contains_key(&rip) {
+            return Ok(());
+        }
+        let eflags = crate::get_reg(tid, Register::eflags)?;
+        let eflags = if resume {
+            eflags | EFLAGS_RF
+        } else {
+            eflags & !EFLAGS_RF
+        };
+        trace!("thread {tid} is at the hardware breakpoint at {rip}, resume flag: {resume}");
+        crate::set_reg(tid, Register::eflags, eflags)
+    }
+}
+
+/// Reads and clears the debug status register `DR6` of a thread
+///
+/// Bit `n` of the status is set if the condition of the debug register `DRn` was met at the
+/// last debug exception.
+///
+/// # Errors
+///
+/// This function fails if `PTRACE_PEEKUSER` or `PTRACE_POKEUSER` fails.
+pub(crate) fn take_debug_status(tid: Pid) -> Result<u64> {
+    let offset = std::mem::offset_of!(nix::libc::user, u_debugreg) + DR_STATUS * 8;
+    let status = ptrace::read_user(tid, offset as ptrace::AddressType)? as u64;
+    write_debug_register(tid, DR_STATUS, 0)?;
+    Ok(status)
+}
+
+/// Writes a debug register of a thread with `PTRACE_POKEUSER`
+///
+/// # Errors
+///
+/// This function fails if the kernel refuses the value.
+fn write_debug_register(tid: Pid, idx: usize, value: u64) -> Result<()> {
+    let offset = std::mem::offset_of!(nix::libc::user, u_debugreg) + idx * 8;
+    ptrace::write_user(
+        tid,
+        offset as ptrace::AddressType,
+        value as nix::libc::c_long,
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_debug_control() {
+        let mut regs = DebugRegisters::default();
+        assert_eq!(regs.control(), 0);
+
+        let exec = Trigger::Execute;
+        assert_eq!(regs.alloc(Addr::from(0x1000usize), exec).unwrap(), 0);
+        assert_eq!(regs.alloc(Addr::from(0x2000usize), exec).unwrap(), 1);
+        assert_eq!(regs.control(), 0b101);
+
+        regs.free(0);
+        assert_eq!(regs.control(), 0b100);
+        assert_eq!(
+            regs.alloc(Addr::from(0x3000usize), Trigger::Write(4))
+                .unwrap(),
+            0
+        );
+        // DR0 breaks on 4 byte writes: condition 01, length 11
+        assert_eq!(regs.control(), 0b1101 << 16 | 0b101);
+        regs.alloc(Addr::from(0x4000usize), Trigger::ReadWrite(8))
+            .unwrap();
+        assert_eq!(regs.control() >> 24 & 0b1111, 0b1011);
+        regs.alloc(Addr::from(0x5000usize), exec).unwrap();
+        assert!(matches!(
+            regs.alloc(Addr::from(0x6000usize), exec),
+            Err(DebuggerError::NoFreeDebugRegister)
+        ));
+        assert_eq!(regs.generation, 6);
+    }
+}
@@ -0,0 +1,212 @@
+//! # Launch Module
+//!
+//! Provides the [`LaunchOptions`] used to configure how a new debuggee is started with
+//! [`Status::Run`](crate::feedback::Status::Run).
+//!
+//! By default, the debuggee inherits the environment, the working directory and the terminal of
+//! the debugger, just like a program started from a shell. The options allow to change that,
+//! so that a debugging session can be reproduced exactly:
+//!
+//! - The environment can be cleared, and single variables can be set or removed
+//! - The working directory can be changed
+//! - Standard input, output and error can be redirected to files
+//! - Address space layout randomization (ASLR) can be disabled with
+//!   `personality(ADDR_NO_RANDOMIZE)`, so that addresses are the same for every run
+//!
+//! The options are applied in the forked child process, right before it executes the program.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::{CString, OsStr, OsString};
+use std::fs::{File, OpenOptions};
+use std::os::fd::{AsRawFd, RawFd};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
+
+use nix::sys::personality::{self, Persona};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Result;
+
+/// Options for launching a new debuggee
+///
+/// The [`Default`] options launch the debuggee like a shell would, inheriting everything from
+/// the debugger.
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::launch::LaunchOptions;
+/// use std::path::PathBuf;
+///
+/// let mut options = LaunchOptions::default();
+/// options.clear_env();
+/// options.set_env("LANG", "C");
+/// options.cwd = Some(PathBuf::from("/tmp"));
+/// options.stdout = Some(PathBuf::from("/tmp/out.txt"));
+/// options.disable_aslr = true;
+///
+/// let env = options.environment();
+/// assert_eq!(env.len(), 1);
+/// assert_eq!(env[0].to_str().unwrap(), "LANG=C");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LaunchOptions {
+    /// Start with an empty environment instead of the environment of the debugger
+    pub clear_env: bool,
+    /// Environment variables to set, overriding inherited ones
+    pub set_env: BTreeMap<String, String>,
+    /// Inherited environment variables to remove
+    pub unset_env: BTreeSet<String>,
+    /// Working directory of the debuggee, [`None`] to inherit it
+    pub cwd: Option<PathBuf>,
+    /// File to read standard input from, [`None`] to inherit it
+    pub stdin: Option<PathBuf>,
+    /// File to write standard output to, [`None`] to inherit it
+    pub stdout: Option<PathBuf>,
+    /// File to write standard error to, [`None`] to inherit it
+    pub stderr: Option<PathBuf>,
+    /// Disable address space layout randomization for the debuggee
+    pub disable_aslr: bool,
+}
+
+/// Everything needed to launch a program again, as it was given to
+/// [`Status::Run`](crate::feedback::Status::Run)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LaunchConfig {
+    /// Path to the executable
+    pub executable: PathBuf,
+    /// Command-line arguments for the executable
+    #[serde(
+        serialize_with = "crate::feedback::serialize_cstring_vec",
+        deserialize_with = "crate::feedback::deserialize_cstring_vec"
+    )]
+    pub arguments: Vec<CString>,
+    /// Environment, working directory, redirections and ASLR of the debuggee
+    #[serde(default)]
+    pub options: LaunchOptions,
+}
+
+impl LaunchOptions {
+    /// Sets an environment variable for the debuggee
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - Name of the variable
+    /// * `value` - Value of the variable
+    pub fn set_env(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        self.unset_env.remove(&name);
+        self.set_env.insert(name, value.into());
+    }
+
+    /// Removes an environment variable from the environment of the debuggee
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - Name of the variable
+    pub fn unset_env(&mut self, name: impl Into<String>) {
+        let name = name.into();
+        self.set_env.remove(&name);
+        self.unset_env.insert(name);
+    }
+
+    /// Starts the debuggee with an empty environment, forgetting all variables set before
+    pub fn clear_env(&mut self) {
+        self.clear_env = true;
+        self.set_env.clear();
+        self.unset_env.clear();
+    }
+
+    /// Builds the environment of the debuggee, as `NAME=VALUE` strings for `execve`
+    ///
+    /// Inherited variables come from the environment of the debugger.
+    #[must_use]
+    pub fn environment(&self) -> Vec<CString> {
+        let mut env: BTreeMap<OsString, OsString> = if self.clear_env {
+            BTreeMap::new()
+        } else {
+            std::env::vars_os().collect()
+        };
+        for name in &self.unset_env {
+            env.remove(OsStr::new(name));
+        }
+        for (name, value) in &self.set_env {
+            env.insert(name.into(), value.into());
+        }
+
+        env.into_iter()
+            .filter_map(|(name, value)| {
+                let mut entry = name.into_vec();
+                entry.push(b'=');
+                entry.extend_from_slice(value.as_bytes());
+                CString::new(entry).ok()
+            })
+            .collect()
+    }
+
+    /// Applies the options to the current process
+    ///
+    /// This is meant to be called in the forked child process, right before it executes the
+    /// debuggee. The environment is not applied here, it is given to `execve` instead.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The working directory cannot be changed
+    /// - A file for redirection cannot be opened
+    /// - The personality of the process cannot be changed
+    pub(crate) fn apply(&self) -> Result<()> {
+        if let Some(stdin) = &self.stdin {
+            redirect(&File::open(stdin)?, nix::libc::STDIN_FILENO)?;
+        }
+        if let Some(stdout) = &self.stdout {
+            redirect(&create(stdout)?, nix::libc::STDOUT_FILENO)?;
+        }
+        if let Some(stderr) = &self.stderr {
+            redirect(&create(stderr)?, nix::libc::STDERR_FILENO)?;
+        }
+        if let Some(cwd) = &self.cwd {
+            std::env::set_current_dir(cwd)?;
+        }
+        if self.disable_aslr {
+            personality::set(personality::get()? | Persona::ADDR_NO_RANDOMIZE)?;
+        }
+        Ok(())
+    }
+}
+
+/// Opens a file for output redirection, creating or truncating it
+fn create(path: &Path) -> Result<File> {
+    Ok(OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)?)
+}
+
+/// Replaces a standard file descriptor with the given file
+fn redirect(file: &File, fd: RawFd) -> Result<()> {
+    // SAFETY: both file descriptors are valid, dup2 does not touch any memory
+    nix::errno::Errno::result(unsafe { nix::libc::dup2(file.as_raw_fd(), fd) })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_environment_set_and_unset() {
+        let mut options = LaunchOptions::default();
+        options.clear_env();
+        options.set_env("A", "1");
+        options.set_env("B", "2");
+        options.unset_env("A");
+        assert_eq!(options.environment(), vec![CString::new("B=2").unwrap()]);
+
+        options.set_env("A", "3");
+        assert!(options.unset_env.is_empty());
+        assert_eq!(options.environment().len(), 2);
+    }
+}
@@ -10,10 +10,21 @@
 //! - **Register Control**: Access and modify CPU registers
 //! - **Breakpoint Management**: Set, enable, disable, and remove breakpoints
 //! - **Execution Control**: Step by step execution, continue execution, step in/out/over functions
+//! - **Run Until**: Run to an address, function or source line, or until the current function
+//!   returns, without leaving a breakpoint behind
 //! - **Symbol Resolution**: Parse and use DWARF debug information for symbol lookup
+//! - **Shared Libraries**: Follow libraries as the dynamic loader loads them, with their symbols
+//! - **Skip Lists**: Step over functions, compilation units, source directories and libraries that
+//!   are not interesting
+//! - **Source Lines**: Map addresses to source lines and back with the DWARF line table
 //! - **Variable Inspection**: Access application variables through debug information
+//! - **Return Values**: Decode what a function returned by its return type and the System V ABI
 //! - **Stack Analysis**: Generate and inspect backtraces and stack frames
+//! - **Call Frames**: Find stack frames and return addresses with the call frame information,
+//!   also in code without frame pointers
 //! - **Disassembly**: Disassemble machine code to human readable assembly
+//! - **Core Files**: Inspect registers, memory, backtraces and variables of a crashed process
+//! - **Sessions**: Save breakpoints, watchpoints and settings to a file and restore them later
 //!
 //! ## Architecture
 //!
@@ -65,6 +76,7 @@ use std::array::TryFromSliceError;
 use std::io::{Read, Seek, Write};
 use std::str::FromStr;
 
+use nix::libc::user_regs_struct;
 use nix::sys::ptrace;
 use nix::unistd::Pid;
 use serde::{Deserialize, Serialize};
@@ -76,7 +88,10 @@ use self::errors::DebuggerError;
 
 pub mod addr;
 pub mod breakpoint;
+pub mod callframe;
+pub mod condition;
 pub mod consts;
+pub mod coredump;
 pub mod dbginfo;
 pub mod debuggee;
 pub mod debugger;
@@ -84,11 +99,24 @@ pub mod disassemble;
 pub mod dwarf_parse;
 pub mod errors;
 pub mod feedback;
+pub mod hwbreakpoint;
+pub mod launch;
+pub mod linetable;
+pub mod location;
 pub mod memorymap;
+pub mod process;
+pub mod returnvalue;
+pub mod session;
+pub mod sharedlib;
+pub mod signal;
+pub mod skiplist;
 pub mod stack;
+pub mod syscall;
+pub mod thread;
 pub mod ui;
 pub mod unwind;
 pub mod variable;
+pub mod watchpoint;
 
 #[cfg(feature = "plugins")]
 pub mod plugins;
@@ -97,9 +125,11 @@ pub mod plugins;
 pub type Word = usize;
 /// Number of bytes in a [Word] (8 bytes on a 64-bit system)
 pub const WORD_BYTES: usize = Word::BITS as usize / 8;
+/// Size of a memory page on `x86_64`
+pub const PAGE_SIZE: usize = 0x1000;
 
 /// CPU register names for `x86_64` architecture
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[allow(non_camel_case_types)]
 #[allow(missing_docs)] // just register names, self explanatory
 pub enum Register {
@@ -258,9 +288,12 @@ pub(crate) fn mem_write(data_raw: &[u8], pid: Pid, addr: Addr) -> Result<usize>
 ///
 /// This function will return an error if [`ptrace::getregs`] fails.
 pub fn get_reg(pid: Pid, r: Register) -> Result<u64> {
-    let regs = ptrace::getregs(pid)?;
+    Ok(reg_value(&ptrace::getregs(pid)?, r))
+}
 
-    let v = match r {
+/// Gets the value of a specified register out of a set of registers
+pub(crate) fn reg_value(regs: &user_regs_struct, r: Register) -> u64 {
+    match r {
         Register::r9 => regs.r9,
         Register::r8 => regs.r8,
         Register::r10 => regs.r10,
@@ -288,9 +321,7 @@ pub fn get_reg(pid: Pid, r: Register) -> Result<u64> {
         Register::gs_base => regs.gs_base,
         Register::gs => regs.gs,
         Register::ds => regs.ds,
-    };
-
-    Ok(v)
+    }
 }
 
 /// Sets the value of a specified register for the target process
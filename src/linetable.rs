@@ -0,0 +1,248 @@
+//! # Line Table Module
+//!
+//! Maps addresses of the debuggee to source locations and back, using the line programs in the
+//! `.debug_line` section of the DWARF debug information.
+//!
+//! Every compilation unit has a line program, which [gimli] runs to produce rows of
+//! (address, file, line, column). The [`LineTable`] collects the rows of all compilation units,
+//! relocated by the load address of the executable, and sorts them by address. A row describes
+//! the code from its address up to the address of the next row, unless it ends a sequence of
+//! contiguous code.
+//!
+//! Rows that are marked with `is_stmt` are the recommended places for breakpoints on a line.
+//! A single line can have several of them, for example when a function is inlined or a template
+//! is instantiated more than once.
+
+use std::num::NonZeroU64;
+use std::path::{Path, PathBuf};
+
+use gimli::Reader;
+use serde::Serialize;
+
+use crate::dbginfo::CMDebugInfo;
+use crate::dwarf_parse::GimliReaderThing;
+use crate::errors::Result;
+use crate::Addr;
+
+/// A row of the line table
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct LineRow {
+    /// Address of the first instruction of this row
+    pub addr: Addr,
+    /// Path of the source file
+    pub file: PathBuf,
+    /// Line in the source file, 0 if the code has no line
+    pub line: u64,
+    /// Column in the source line, 0 for the whole line
+    pub column: u64,
+    /// Whether this is a recommended place for a breakpoint on this line
+    pub is_stmt: bool,
+    /// Whether this row only marks the end of a sequence of contiguous code
+    pub end_sequence: bool,
+}
+
+/// The line table of an executable, sorted by address
+///
+/// # Examples
+///
+/// ```no_run
+/// # use coreminer::linetable::LineTable;
+/// # use coreminer::dbginfo::CMDebugInfo;
+/// # use coreminer::addr::Addr;
+/// # fn example(dbginfo: &CMDebugInfo) -> coreminer::errors::Result<()> {
+/// let table = LineTable::build(dbginfo, Addr::from(0usize))?;
+///
+/// if let Some((line, addrs)) = table.addrs_for_line("main.c", 12) {
+///     println!("line {line} starts at {addrs:?}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LineTable {
+    rows: Vec<LineRow>,
+}
+
+impl LineTable {
+    /// Builds the line table from the line programs of all compilation units
+    ///
+    /// # Parameters
+    ///
+    /// * `dbginfo` - Debug information extracted from the executable
+    /// * `load_bias` - Difference between the load address and the link address of the
+    ///   executable, see [`CMDebugInfo::load_bias`]
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if a line program cannot be parsed.
+    pub fn build(dbginfo: &CMDebugInfo<'_>, load_bias: Addr) -> Result<Self> {
+        let dwarf = &dbginfo.dwarf;
+        let mut rows = Vec::new();
+        let mut units = dwarf.units();
+
+        while let Some(header) = units.next()? {
+            let unit = dwarf.unit(header)?;
+            let Some(program) = unit.line_program.clone() else {
+                continue;
+            };
+            let comp_dir = match &unit.comp_dir {
+                Some(dir) => PathBuf::from(dir.to_string_lossy()?.as_ref()),
+                None => PathBuf::new(),
+            };
+
+            let mut program_rows = program.rows();
+            while let Some((header, row)) = program_rows.next_row()? {
+                let file = match row.file(header) {
+                    Some(file) => file_path(dwarf, &unit, header, file, &comp_dir)?,
+                    None => PathBuf::new(),
+                };
+                rows.push(LineRow {
+                    addr: load_bias + row.address() as usize,
+                    file,
+                    line: row.line().map_or(0, NonZeroU64::get),
+                    column: match row.column() {
+                        gimli::ColumnType::LeftEdge => 0,
+                        gimli::ColumnType::Column(c) => c.get(),
+                    },
+                    is_stmt: row.is_stmt(),
+                    end_sequence: row.end_sequence(),
+                });
+            }
+        }
+
+        // the end of a sequence comes before a sequence that starts at the same address
+        rows.sort_by_key(|r| (r.addr, !r.end_sequence));
+        Ok(Self { rows })
+    }
+
+    /// Returns all rows, sorted by address
+    #[must_use]
+    pub fn rows(&self) -> &[LineRow] {
+        &self.rows
+    }
+
+    /// Finds the row that describes the code at an address
+    ///
+    /// # Returns
+    ///
+    /// * `Some(&LineRow)` - The row with the code at `addr`
+    /// * `None` - If the address is not in any sequence of the line table
+    #[must_use]
+    pub fn row_for_addr(&self, addr: Addr) -> Option<&LineRow> {
+        let idx = self.rows.partition_point(|r| r.addr <= addr);
+        let row = self.rows.get(idx.checked_sub(1)?)?;
+        (!row.end_sequence).then_some(row)
+    }
+
+    /// Finds the addresses to break at for a source line
+    ///
+    /// The file matches if its path ends with `file`, so `main.c` matches `/src/main.c`. If the
+    /// line has no code, the next line after it that has code is used, like gdb does.
+    ///
+    /// Only `is_stmt` rows that start the line are used: a row where the code before it
+    /// belongs to a different line. A line can have several addresses, for example for the
+    /// condition of a loop, for inlined functions or for instantiated templates.
+    ///
+    /// # Parameters
+    ///
+    /// * `file` - The source file, or the end of its path
+    /// * `line` - The line in the source file
+    ///
+    /// # Returns
+    ///
+    /// * `Some((line, addrs))` - The line that was found and its addresses, sorted
+    /// * `None` - If no line at or after `line` in `file` has code
+    #[must_use]
+    pub fn addrs_for_line(&self, file: impl AsRef<Path>, line: u64) -> Option<(u64, Vec<Addr>)> {
+        let file = file.as_ref();
+        let candidates =
+            |r: &&LineRow| r.is_stmt && !r.end_sequence && r.line >= line && r.file.ends_with(file);
+        let found = self.rows.iter().filter(candidates).map(|r| r.line).min()?;
+
+        let mut addrs = Vec::new();
+        for (idx, row) in self.rows.iter().enumerate() {
+            if row.line != found || !candidates(&row) {
+                continue;
+            }
+            let continues_line = idx
+                .checked_sub(1)
+                .and_then(|i| self.rows.get(i))
+                .is_some_and(|prev| {
+                    !prev.end_sequence && prev.line == row.line && prev.file == row.file
+                });
+            if !continues_line {
+                addrs.push(row.addr);
+            }
+        }
+        addrs.dedup();
+
+        Some((found, addrs))
+    }
+}
+
+/// Builds the full path of a file in the line program header
+fn file_path(
+    dwarf: &gimli::Dwarf<GimliReaderThing>,
+    unit: &gimli::Unit<GimliReaderThing>,
+    header: &gimli::LineProgramHeader<GimliReaderThing>,
+    file: &gimli::FileEntry<GimliReaderThing>,
+    comp_dir: &Path,
+) -> Result<PathBuf> {
+    let mut path = comp_dir.to_path_buf();
+    if let Some(dir) = file.directory(header) {
+        path.push(dwarf.attr_string(unit, dir)?.to_string_lossy()?.as_ref());
+    }
+    path.push(
+        dwarf
+            .attr_string(unit, file.path_name())?
+            .to_string_lossy()?
+            .as_ref(),
+    );
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn row(addr: usize, line: u64, end_sequence: bool) -> LineRow {
+        LineRow {
+            addr: Addr::from(addr),
+            file: PathBuf::from("/src/main.c"),
+            line,
+            column: 0,
+            is_stmt: true,
+            end_sequence,
+        }
+    }
+
+    #[test]
+    fn test_addrs_for_line() {
+        let table = LineTable {
+            rows: vec![
+                row(0x1000, 3, false),
+                row(0x1008, 4, false),
+                row(0x1010, 4, false),
+                row(0x1018, 7, false),
+                row(0x1020, 4, false),
+                row(0x1028, 8, true),
+            ],
+        };
+
+        assert_eq!(
+            table.addrs_for_line("main.c", 4),
+            Some((4, vec![Addr::from(0x1008usize), Addr::from(0x1020usize)]))
+        );
+        // line 5 has no code, the next line with code is used
+        assert_eq!(
+            table.addrs_for_line("main.c", 5),
+            Some((7, vec![Addr::from(0x1018usize)]))
+        );
+        assert_eq!(table.addrs_for_line("other.c", 4), None);
+        assert_eq!(table.addrs_for_line("main.c", 9), None);
+
+        assert_eq!(table.row_for_addr(Addr::from(0x1014usize)).unwrap().line, 4);
+        assert!(table.row_for_addr(Addr::from(0x1028usize)).is_none());
+        assert!(table.row_for_addr(Addr::from(0x0fffusize)).is_none());
+    }
+}
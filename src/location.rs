@@ -0,0 +1,141 @@
+//! # Location Module
+//!
+//! Provides the [`Location`], a place in the code of the debuggee that
+//! [`Status::RunUntil`](crate::feedback::Status::RunUntil) runs to, and the [`UntilStop`] that
+//! reports why the debuggee stopped afterwards.
+//!
+//! The debugger sets internal one-shot breakpoints at the addresses of the location, so unlike
+//! with a [temporary breakpoint](crate::breakpoint::BreakpointSettings::temporary), nothing is
+//! left behind when another breakpoint, a watchpoint or a signal stops the debuggee first.
+
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use nix::sys::signal::Signal;
+use serde::{Deserialize, Serialize};
+
+use crate::feedback::Feedback;
+use crate::Addr;
+
+/// A place in the code of the debuggee to run to
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::location::Location;
+/// use std::path::PathBuf;
+///
+/// let location = Location::Line(PathBuf::from("main.c"), 12);
+/// assert_eq!(location.to_string(), "main.c:12");
+/// assert_eq!(Location::Caller.to_string(), "the caller");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum Location {
+    /// An address in the debuggee
+    Addr(Addr),
+    /// A function with this name, after its prologue
+    Function(String),
+    /// The code of a line in a source file, or of the next line after it that has code
+    Line(PathBuf, u64),
+    /// The return address of the current function, once the current function has returned
+    Caller,
+}
+
+/// Why the debuggee stopped after [`Status::RunUntil`](crate::feedback::Status::RunUntil)
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::location::{Location, StopReason, UntilStop};
+/// use coreminer::addr::Addr;
+///
+/// let stop = UntilStop {
+///     location: Location::Function("square".to_string()),
+///     reason: StopReason::Breakpoint(Addr::from(0x555555555139usize)),
+/// };
+/// println!("{stop}");
+/// ```
+#[derive(Debug, Serialize)]
+pub struct UntilStop {
+    /// The location the debuggee was supposed to run to
+    pub location: Location,
+    /// What stopped the debuggee
+    pub reason: StopReason,
+}
+
+/// The event that stopped the debuggee, see [`UntilStop`]
+#[derive(Debug, Serialize)]
+pub enum StopReason {
+    /// The debuggee reached the location, at this address
+    Reached(Addr),
+    /// The debuggee hit the breakpoint at this address first
+    Breakpoint(Addr),
+    /// The debuggee received the signal with this number first
+    Signal(i32),
+    /// Something else stopped the debuggee first, like a watchpoint or its exit
+    Other(Box<Feedback>),
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location::Addr(addr) => write!(f, "{addr}"),
+            Location::Function(name) => write!(f, "{name}"),
+            Location::Line(file, line) => write!(f, "{}:{line}", file.to_string_lossy()),
+            Location::Caller => write!(f, "the caller"),
+        }
+    }
+}
+
+impl Display for UntilStop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let location = &self.location;
+        match &self.reason {
+            StopReason::Reached(addr) => write!(f, "Reached {location} at {addr}"),
+            StopReason::Breakpoint(addr) => {
+                write!(
+                    f,
+                    "Stopped at the breakpoint at {addr} before reaching {location}"
+                )
+            }
+            StopReason::Signal(signum) => write!(
+                f,
+                "Stopped by {} before reaching {location}",
+                Signal::try_from(*signum).map_or("an unknown signal", Signal::as_str)
+            ),
+            StopReason::Other(feedback) => {
+                write!(f, "Stopped before reaching {location}: {feedback}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_location_json() {
+        let location = Location::Line(PathBuf::from("src/main.rs"), 7);
+        let json = serde_json::to_string(&location).unwrap();
+        assert_eq!(json, r#"{"Line":["src/main.rs",7]}"#);
+        assert_eq!(serde_json::from_str::<Location>(&json).unwrap(), location);
+        assert_eq!(
+            serde_json::from_str::<Location>(r#""Caller""#).unwrap(),
+            Location::Caller
+        );
+    }
+
+    #[test]
+    fn test_stop_display() {
+        let stop = UntilStop {
+            location: Location::Function("square".to_string()),
+            reason: StopReason::Signal(Signal::SIGSEGV as i32),
+        };
+        assert_eq!(
+            stop.to_string(),
+            "Stopped by SIGSEGV before reaching square"
+        );
+    }
+}
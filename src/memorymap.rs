@@ -9,10 +9,13 @@
 //!
 //! The memory map information is extracted from the `/proc/<pid>/maps` file using the
 //! [`proc_maps`] crate and provides a structured way to analyze process memory regions.
+//! For a core dump, the memory map is built from the segments of the core file instead, see
+//! [`CoreDump::memory_map`](crate::coredump::CoreDump::memory_map).
 
 use std::fmt::{self, Display};
+use std::path::{Path, PathBuf};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::addr::Addr;
 
@@ -151,6 +154,141 @@ pub struct ProcessMemoryMap {
     pub private_regions: usize,
 }
 
+/// An address as an offset into a mapped file, like the executable or a shared library
+///
+/// Unlike an absolute [Addr], this stays the same between runs of a program, even if the
+/// program is loaded at a different address because of ASLR. The offset is relative to the
+/// lowest address the file is mapped at.
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::memorymap::ModuleOffset;
+///
+/// let location = ModuleOffset::new("/usr/bin/ls", 0x1139);
+/// assert_eq!(location.to_string(), "/usr/bin/ls+0x1139");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ModuleOffset {
+    /// Path of the mapped file
+    pub module: PathBuf,
+    /// Offset from the lowest address the file is mapped at
+    pub offset: usize,
+}
+
+impl MemoryPermissions {
+    /// Returns the read, write and execute permissions as the `PROT_*` flags of `mprotect`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use coreminer::memorymap::MemoryPermissions;
+    ///
+    /// let permissions = MemoryPermissions {
+    ///     read: true,
+    ///     write: true,
+    ///     execute: false,
+    ///     shared: false,
+    ///     private: true,
+    /// };
+    /// assert_eq!(permissions.protection(), nix::libc::PROT_READ | nix::libc::PROT_WRITE);
+    /// ```
+    #[must_use]
+    pub fn protection(&self) -> i32 {
+        let mut protection = nix::libc::PROT_NONE;
+        if self.read {
+            protection |= nix::libc::PROT_READ;
+        }
+        if self.write {
+            protection |= nix::libc::PROT_WRITE;
+        }
+        if self.execute {
+            protection |= nix::libc::PROT_EXEC;
+        }
+        protection
+    }
+}
+
+impl ModuleOffset {
+    /// Creates a new [`ModuleOffset`]
+    ///
+    /// # Parameters
+    ///
+    /// * `module` - Path of the mapped file
+    /// * `offset` - Offset from the lowest address the file is mapped at
+    #[must_use]
+    pub fn new(module: impl Into<PathBuf>, offset: usize) -> Self {
+        Self {
+            module: module.into(),
+            offset,
+        }
+    }
+}
+
+impl Display for ModuleOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}+{:#x}", self.module.to_string_lossy(), self.offset)
+    }
+}
+
+impl ProcessMemoryMap {
+    /// Gets the lowest address a file is mapped at
+    ///
+    /// # Parameters
+    ///
+    /// * `module` - Path of the mapped file
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Addr)` - The lowest address of all regions of that file
+    /// * `None` - If the file is not mapped
+    #[must_use]
+    pub fn module_base(&self, module: &Path) -> Option<Addr> {
+        self.regions
+            .iter()
+            .filter(|r| r.path.as_deref().map(Path::new) == Some(module))
+            .map(|r| r.start_address)
+            .min()
+    }
+
+    /// Converts an absolute address into an offset into the mapped file that contains it
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The absolute address
+    ///
+    /// # Returns
+    ///
+    /// * `Some(ModuleOffset)` - The location of the address in its file
+    /// * `None` - If the address is not in a mapped file, for example on the heap
+    #[must_use]
+    pub fn module_offset(&self, addr: Addr) -> Option<ModuleOffset> {
+        let region = self
+            .regions
+            .iter()
+            .find(|r| r.start_address <= addr && addr < r.end_address)
+            .filter(|r| r.inode != 0)?;
+        let module = Path::new(region.path.as_deref()?);
+        let base = self.module_base(module)?;
+        Some(ModuleOffset::new(module, (addr - base).usize()))
+    }
+
+    /// Converts an offset into a mapped file into an absolute address
+    ///
+    /// # Parameters
+    ///
+    /// * `location` - The location in the file
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Addr)` - The absolute address in this process
+    /// * `None` - If the file is not mapped (yet)
+    #[must_use]
+    pub fn resolve(&self, location: &ModuleOffset) -> Option<Addr> {
+        Some(self.module_base(&location.module)? + location.offset)
+    }
+}
+
 impl From<Vec<proc_maps::MapRange>> for ProcessMemoryMap {
     fn from(ranges: Vec<proc_maps::MapRange>) -> Self {
         let regions: Vec<MemoryRegion> = ranges
@@ -183,6 +321,12 @@ impl From<Vec<proc_maps::MapRange>> for ProcessMemoryMap {
             })
             .collect();
 
+        regions.into()
+    }
+}
+
+impl From<Vec<MemoryRegion>> for ProcessMemoryMap {
+    fn from(regions: Vec<MemoryRegion>) -> Self {
         // Calculate summary statistics
         let total_mapped = regions.iter().map(|r| r.size).sum();
         let executable_regions = regions.iter().filter(|r| r.permissions.execute).count();
@@ -238,3 +382,20 @@ impl Display for ProcessMemoryMap {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_module_offset_roundtrip() {
+        let map = ProcessMemoryMap::from(
+            proc_maps::get_process_maps(std::process::id() as proc_maps::Pid).unwrap(),
+        );
+        let addr = Addr::from(test_module_offset_roundtrip as *const () as usize);
+
+        let location = map.module_offset(addr).unwrap();
+        assert_eq!(location.module, std::env::current_exe().unwrap());
+        assert_eq!(map.resolve(&location), Some(addr));
+    }
+}
@@ -21,7 +21,7 @@
 //!
 //! The module includes several built-in plugins:
 //! - [`SigtrapGuardPlugin`]: A plugin that prevents the detection of the coreminer debugger with
-//!                           a signal handler for SIGTRAP
+//!   a signal handler for SIGTRAP
 //!
 //! ## Usage
 //!
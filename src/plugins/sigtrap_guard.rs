@@ -74,6 +74,7 @@ use tracing::{info, trace, warn};
 
 use crate::addr::Addr;
 use crate::breakpoint::Breakpoint;
+use crate::consts::{SI_KERNEL, TRAP_BRKPT};
 use crate::feedback::{Feedback, Status};
 
 use super::extension_points::{EPreSigtrap, EPreSigtrapF};
@@ -145,7 +146,7 @@ impl EPreSigtrapF for SigtrapInjectionGuard {
     /// # State Machine
     ///
     /// This function implements a small state machine:
-    /// 1. If not a SIGTRAP, return immediately
+    /// 1. If not a SIGTRAP from an `INT3`, return immediately
     /// 2. If we don't have the instruction pointer, request it with `Status::DumpRegisters`
     /// 3. If we don't have breakpoint information, request it with `Status::GetBreakpoint`
     /// 4. If a breakpoint exists at the location, let the debugger handle it normally
@@ -156,7 +157,9 @@ impl EPreSigtrapF for SigtrapInjectionGuard {
         siginfo: &nix::libc::siginfo_t,
         sig: &nix::sys::signal::Signal,
     ) -> crate::errors::Result<(crate::feedback::Status, bool)> {
-        if *sig != SIGTRAP {
+        // only INT3 can be inserted by the debuggee, single steps and hardware breakpoints are
+        // always ours
+        if *sig != SIGTRAP || !matches!(siginfo.si_code, SI_KERNEL | TRAP_BRKPT) {
             return Ok((Status::PluginContinue, false));
         }
 
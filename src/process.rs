@@ -0,0 +1,134 @@
+//! # Process Module
+//!
+//! Provides the types used to follow the debuggee through `fork`, `vfork` and `execve`.
+//!
+//! The debugger traces new processes created by the debuggee with `PTRACE_O_TRACEFORK` and
+//! `PTRACE_O_TRACEVFORK`. Which process is debugged afterwards is decided by the
+//! [`FollowForkMode`]. Processes that are kept under the control of the debugger without being
+//! debugged right now are held stopped, and can be selected with
+//! [`Status::SelectProcess`](crate::feedback::Status::SelectProcess).
+//!
+//! When the debuggee executes a new program with `execve`, the debug information and symbols
+//! are reloaded for the new executable.
+
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use nix::unistd::Pid;
+use serde::{Deserialize, Serialize};
+
+use crate::breakpoint::ser_pid;
+use crate::errors::DebuggerError;
+
+/// Decides which process is debugged after the debuggee forked
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::process::FollowForkMode;
+/// use std::str::FromStr;
+///
+/// assert_eq!(FollowForkMode::default(), FollowForkMode::Parent);
+/// assert_eq!(FollowForkMode::from_str("child").unwrap(), FollowForkMode::Child);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum FollowForkMode {
+    /// Keep debugging the parent, the child is detached and runs on its own
+    #[default]
+    Parent,
+    /// Debug the child, the parent is detached and runs on its own
+    Child,
+    /// Keep debugging the parent, the child is held stopped and can be selected later
+    Both,
+}
+
+/// Information about a `fork` or `vfork` of the debuggee
+///
+/// Returned with [`Feedback::Fork`](crate::feedback::Feedback::Fork) when the debuggee
+/// creates a new process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct ForkEvent {
+    /// Process ID of the process that forked
+    #[serde(serialize_with = "ser_pid")]
+    pub parent: Pid,
+    /// Process ID of the new process
+    #[serde(serialize_with = "ser_pid")]
+    pub child: Pid,
+    /// Whether the new process was created with `vfork`, sharing the memory of the parent
+    pub vfork: bool,
+    /// The mode that was used to decide which process to follow
+    pub mode: FollowForkMode,
+}
+
+/// Information about a process under the control of the debugger, as presented to the user
+///
+/// Returned as a list with [`Feedback::Processes`](crate::feedback::Feedback::Processes).
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessInfo {
+    /// Process ID of the process
+    #[serde(serialize_with = "ser_pid")]
+    pub pid: Pid,
+    /// Executable of the process, as found in `/proc/<pid>/exe`
+    pub executable: Option<PathBuf>,
+    /// Whether this is the process that is currently debugged
+    pub selected: bool,
+}
+
+impl ProcessInfo {
+    /// Collects the information about a process
+    ///
+    /// # Parameters
+    ///
+    /// * `pid` - The process ID of the process
+    /// * `selected` - Whether this is the process that is currently debugged
+    #[must_use]
+    pub fn new(pid: Pid, selected: bool) -> Self {
+        Self {
+            pid,
+            executable: std::fs::read_link(format!("/proc/{pid}/exe")).ok(),
+            selected,
+        }
+    }
+}
+
+impl FromStr for FollowForkMode {
+    type Err = DebuggerError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        Ok(match s.as_str() {
+            "parent" => Self::Parent,
+            "child" => Self::Child,
+            "both" => Self::Both,
+            _ => return Err(DebuggerError::ParseStr(s)),
+        })
+    }
+}
+
+impl Display for FollowForkMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parent => write!(f, "parent"),
+            Self::Child => write!(f, "child"),
+            Self::Both => write!(f, "both"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_follow_fork_mode_roundtrip() {
+        for mode in [
+            FollowForkMode::Parent,
+            FollowForkMode::Child,
+            FollowForkMode::Both,
+        ] {
+            assert_eq!(FollowForkMode::from_str(&mode.to_string()).unwrap(), mode);
+        }
+        assert!(FollowForkMode::from_str("sibling").is_err());
+    }
+}
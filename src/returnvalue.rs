@@ -0,0 +1,393 @@
+//! # Return Value Module
+//!
+//! Decodes the value that a function returned, right after it returned to its caller, as it is
+//! reported by [`Status::Finish`](crate::feedback::Status::Finish).
+//!
+//! Where a return value is, is defined by the System V ABI for `x86_64`, depending on its
+//! DWARF type:
+//!
+//! - Integers, pointers and enums are in `rax`, 128 bit integers in `rax` and `rdx`
+//! - `float` and `double` are in `xmm0`, `long double` is in `st0`
+//! - Structures and unions of up to 16 bytes are split into two eightbytes. An eightbyte that
+//!   only holds floating point members is in the next free register of `xmm0` and `xmm1`, any
+//!   other one in the next free register of `rax` and `rdx`.
+//! - Larger values are in memory that the caller provides. Its address is passed in a hidden
+//!   argument, which the function returns in `rax`.
+
+use std::fmt::Display;
+
+use nix::libc::{user_fpregs_struct, user_regs_struct};
+use serde::Serialize;
+use tracing::trace;
+
+use crate::dbginfo::{OwnedSymbol, SymbolKind};
+use crate::debuggee::Debuggee;
+use crate::errors::{DebuggerError, Result};
+use crate::variable::VariableValue;
+use crate::{Addr, WORD_BYTES};
+
+/// Values larger than this are returned in memory
+const MAX_REGISTER_RETURN: usize = 2 * WORD_BYTES;
+/// Size of a `long double` in memory, only the first 10 bytes are used
+const LONG_DOUBLE_SIZE: usize = 16;
+/// Size of a value in an x87 register
+const X87_VALUE_SIZE: usize = 10;
+
+/// The value a function returned, see [`Status::Finish`](crate::feedback::Status::Finish)
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::returnvalue::ReturnValue;
+/// use coreminer::variable::VariableValue;
+///
+/// let returned = ReturnValue {
+///     function: Some("square".to_string()),
+///     value: Some(VariableValue::Bytes(vec![0x19, 0, 0, 0])),
+/// };
+/// println!("{returned}");
+/// ```
+#[derive(Debug, Clone, Serialize)]
+pub struct ReturnValue {
+    /// Name of the function that returned, if it is known
+    pub function: Option<String>,
+    /// The bytes of the returned value, [`None`] if the function returns nothing or its return
+    /// type is not known
+    pub value: Option<VariableValue>,
+}
+
+/// Where an eightbyte of a value is passed, as classified by the System V ABI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueClass {
+    /// Padding, nothing is passed
+    NoClass,
+    /// In a general purpose register
+    Integer,
+    /// In an SSE register
+    Sse,
+    /// In memory
+    Memory,
+}
+
+impl ValueClass {
+    /// Merges the classes of two fields in the same eightbyte
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::NoClass, c) | (c, Self::NoClass) => c,
+            (Self::Memory, _) | (_, Self::Memory) => Self::Memory,
+            (Self::Integer, _) | (_, Self::Integer) => Self::Integer,
+            _ => Self::Sse,
+        }
+    }
+}
+
+impl Display for ReturnValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let function = self.function.as_deref().unwrap_or("??");
+        match &self.value {
+            Some(value) => write!(f, "{function} returned: {value:#?}"),
+            None => write!(f, "{function} returned"),
+        }
+    }
+}
+
+impl Debuggee {
+    /// Decodes the value a function returned, right after its `ret`
+    ///
+    /// The registers of the selected thread must be the ones the function returned with.
+    ///
+    /// # Parameters
+    ///
+    /// * `function` - The function that returned
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(None)` - If the function returns nothing
+    /// * `Ok(Some(VariableValue::Bytes))` - The bytes of the returned value
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the return type has no size or cannot be classified, or the
+    /// registers or memory of the thread cannot be read.
+    pub fn return_value(&self, function: &OwnedSymbol) -> Result<Option<VariableValue>> {
+        let Some(datatype) = self.get_type_for_symbol(function)? else {
+            return Ok(None);
+        };
+        let datatype = self.strip_typedefs(datatype)?;
+        let size = datatype
+            .byte_size()
+            .ok_or(DebuggerError::SymbolHasNoByteSize)?;
+        let tid = self.selected_thread();
+        let regs = self.get_regs(tid)?;
+
+        let x87_registers = x87_registers(&datatype);
+        if x87_registers > 0 {
+            let fpregs = self.get_fpregs(tid)?;
+            let bytes = (0..x87_registers)
+                .flat_map(|index| x87_register(&fpregs, index))
+                .collect();
+            return Ok(Some(VariableValue::Bytes(bytes)));
+        }
+
+        let classes = self.classify_return_value(&datatype, size)?;
+        trace!("return value of {size} bytes is in {classes:?}");
+        if classes.contains(&ValueClass::Memory) {
+            let mut bytes = vec![0; size];
+            self.read_memory(&mut bytes, Addr::from(regs.rax))?;
+            return Ok(Some(VariableValue::Bytes(bytes)));
+        }
+
+        let fpregs = if classes.contains(&ValueClass::Sse) {
+            Some(self.get_fpregs(tid)?)
+        } else {
+            None
+        };
+        Ok(Some(VariableValue::Bytes(assemble_registers(
+            &classes,
+            size,
+            &regs,
+            fpregs.as_ref(),
+        ))))
+    }
+
+    /// Finds where the eightbytes of a return value are
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the type contains types that cannot be classified.
+    fn classify_return_value(
+        &self,
+        datatype: &OwnedSymbol,
+        size: usize,
+    ) -> Result<Vec<ValueClass>> {
+        if size > MAX_REGISTER_RETURN {
+            return Ok(vec![ValueClass::Memory]);
+        }
+        let mut classes = vec![ValueClass::NoClass; size.div_ceil(WORD_BYTES)];
+        self.classify(datatype, 0, size, &mut classes)?;
+        Ok(classes)
+    }
+
+    /// Classifies a value of a type at an offset in the return value and merges it into the
+    /// classes of the eightbytes
+    ///
+    /// # Parameters
+    ///
+    /// * `datatype` - The type of the value
+    /// * `offset` - Where the value is in the return value
+    /// * `size` - The size of the value, which can be larger than the type for arrays
+    /// * `classes` - The classes of the eightbytes of the return value
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the type contains types that cannot be classified.
+    fn classify(
+        &self,
+        datatype: &OwnedSymbol,
+        offset: usize,
+        size: usize,
+        classes: &mut [ValueClass],
+    ) -> Result<()> {
+        match datatype.kind() {
+            SymbolKind::Typedef => {
+                let inner = self.strip_typedefs(datatype.clone())?;
+                self.classify(&inner, offset, size, classes)?;
+            }
+            SymbolKind::BaseType
+                if matches!(
+                    datatype.base_encoding(),
+                    Some(gimli::DW_ATE_float | gimli::DW_ATE_complex_float)
+                ) =>
+            {
+                // a long double in a structure is returned in memory
+                if x87_registers(datatype) > 0 {
+                    merge_range(classes, offset, size, ValueClass::Memory);
+                } else {
+                    merge_range(classes, offset, size, ValueClass::Sse);
+                }
+            }
+            SymbolKind::BaseType | SymbolKind::Pointer | SymbolKind::Enum => {
+                merge_range(classes, offset, size, ValueClass::Integer);
+            }
+            SymbolKind::Struct => {
+                for member in datatype.children() {
+                    if member.kind() != SymbolKind::Member {
+                        continue;
+                    }
+                    let member_offset = member.member_offset().unwrap_or(0);
+                    let member_type = self
+                        .get_type_for_symbol(member)?
+                        .ok_or(DebuggerError::NoDatatypeFound)?;
+                    let member_type = self.strip_typedefs(member_type)?;
+                    // arrays often have no size of their own, they fill the space up to the
+                    // next member
+                    let member_size = member_type.byte_size().unwrap_or_else(|| {
+                        datatype
+                            .children()
+                            .iter()
+                            .filter_map(OwnedSymbol::member_offset)
+                            .filter(|o| *o > member_offset)
+                            .min()
+                            .unwrap_or(size)
+                            - member_offset
+                    });
+                    self.classify(&member_type, offset + member_offset, member_size, classes)?;
+                }
+            }
+            SymbolKind::Array => {
+                let element = self
+                    .get_type_for_symbol(datatype)?
+                    .ok_or(DebuggerError::NoDatatypeFound)?;
+                let element = self.strip_typedefs(element)?;
+                let element_size = element
+                    .byte_size()
+                    .ok_or(DebuggerError::SymbolHasNoByteSize)?;
+                for index in 0..size / element_size.max(1) {
+                    self.classify(
+                        &element,
+                        offset + index * element_size,
+                        element_size,
+                        classes,
+                    )?;
+                }
+            }
+            other => {
+                return Err(DebuggerError::UnsupportedReturnType(format!(
+                    "{other:?} {}",
+                    datatype.name().unwrap_or("??")
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    /// Follows typedefs and qualified types to the type they name
+    ///
+    /// # Errors
+    ///
+    /// This function fails if a typedef has no datatype.
+    pub(crate) fn strip_typedefs(&self, mut datatype: OwnedSymbol) -> Result<OwnedSymbol> {
+        while datatype.kind() == SymbolKind::Typedef {
+            datatype = self
+                .get_type_for_symbol(&datatype)?
+                .ok_or(DebuggerError::NoDatatypeFound)?;
+        }
+        Ok(datatype)
+    }
+}
+
+/// Counts the x87 registers a value of a base type is returned in: a `long double` is in
+/// `st0`, a complex `long double` in `st0` and `st1`
+fn x87_registers(datatype: &OwnedSymbol) -> usize {
+    match (datatype.base_encoding(), datatype.byte_size()) {
+        (Some(gimli::DW_ATE_float), Some(LONG_DOUBLE_SIZE)) => 1,
+        (Some(gimli::DW_ATE_complex_float), Some(size)) if size == 2 * LONG_DOUBLE_SIZE => 2,
+        _ => 0,
+    }
+}
+
+/// Merges a class into the classes of the eightbytes that a value covers
+fn merge_range(classes: &mut [ValueClass], offset: usize, size: usize, class: ValueClass) {
+    let first = offset / WORD_BYTES;
+    let last = (offset + size.max(1) - 1) / WORD_BYTES;
+    for eightbyte in classes.iter_mut().take(last + 1).skip(first) {
+        *eightbyte = eightbyte.merge(class);
+    }
+}
+
+/// Puts the eightbytes of a return value together from the registers they were returned in
+///
+/// # Parameters
+///
+/// * `classes` - The classes of the eightbytes, none of them is [`ValueClass::Memory`]
+/// * `size` - The size of the return value
+/// * `regs` - The general purpose registers
+/// * `fpregs` - The floating point registers, needed if a class is [`ValueClass::Sse`]
+fn assemble_registers(
+    classes: &[ValueClass],
+    size: usize,
+    regs: &user_regs_struct,
+    fpregs: Option<&user_fpregs_struct>,
+) -> Vec<u8> {
+    let mut integer = [regs.rax, regs.rdx].into_iter();
+    let mut sse = (0..2).map(|index| fpregs.map_or(0, |fpregs| xmm_low(fpregs, index)));
+    let mut bytes = Vec::with_capacity(classes.len() * WORD_BYTES);
+    for class in classes {
+        match class {
+            ValueClass::Integer => bytes.extend(integer.next().unwrap_or(0).to_le_bytes()),
+            ValueClass::Sse => bytes.extend(sse.next().unwrap_or(0).to_le_bytes()),
+            ValueClass::NoClass | ValueClass::Memory => bytes.extend([0; WORD_BYTES]),
+        }
+    }
+    bytes.truncate(size);
+    bytes
+}
+
+/// Gets the low 8 bytes of an SSE register
+fn xmm_low(fpregs: &user_fpregs_struct, index: usize) -> u64 {
+    let low = u64::from(fpregs.xmm_space[index * 4]);
+    let high = u64::from(fpregs.xmm_space[index * 4 + 1]);
+    low | (high << 32)
+}
+
+/// Gets the value of an x87 register, padded to the size of a `long double`
+fn x87_register(fpregs: &user_fpregs_struct, index: usize) -> Vec<u8> {
+    let mut bytes: Vec<u8> = fpregs.st_space[index * 4..index * 4 + 4]
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .collect();
+    bytes[X87_VALUE_SIZE..].fill(0);
+    bytes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_merge_classes() {
+        assert_eq!(ValueClass::NoClass.merge(ValueClass::Sse), ValueClass::Sse);
+        assert_eq!(ValueClass::Sse.merge(ValueClass::Sse), ValueClass::Sse);
+        assert_eq!(
+            ValueClass::Sse.merge(ValueClass::Integer),
+            ValueClass::Integer
+        );
+        assert_eq!(
+            ValueClass::Integer.merge(ValueClass::NoClass),
+            ValueClass::Integer
+        );
+        assert_eq!(
+            ValueClass::Memory.merge(ValueClass::Integer),
+            ValueClass::Memory
+        );
+    }
+
+    #[test]
+    fn test_assemble_registers() {
+        // SAFETY: the register structs are plain data, all zero is valid
+        let mut regs: user_regs_struct = unsafe { std::mem::zeroed() };
+        let mut fpregs: user_fpregs_struct = unsafe { std::mem::zeroed() };
+        regs.rax = 0x1122_3344_5566_7788;
+        regs.rdx = 0x99;
+        fpregs.xmm_space[0] = 2.5f32.to_bits();
+
+        // struct { long a; int b; }
+        let bytes =
+            assemble_registers(&[ValueClass::Integer, ValueClass::Integer], 12, &regs, None);
+        assert_eq!(
+            bytes,
+            [0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11, 0x99, 0, 0, 0]
+        );
+
+        // struct { float x; float y; int z; }: the floats are in xmm0, z is in rax
+        let bytes = assemble_registers(
+            &[ValueClass::Sse, ValueClass::Integer],
+            12,
+            &regs,
+            Some(&fpregs),
+        );
+        assert_eq!(&bytes[..4], 2.5f32.to_le_bytes());
+        assert_eq!(&bytes[8..], [0x88, 0x77, 0x66, 0x55]);
+    }
+}
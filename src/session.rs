@@ -0,0 +1,178 @@
+//! # Session Module
+//!
+//! Provides the [`Session`], a snapshot of the setup of the debugger that can be written to a
+//! JSON file and loaded again later, with
+//! [`Status::SaveSession`](crate::feedback::Status::SaveSession) and
+//! [`Status::LoadSession`](crate::feedback::Status::LoadSession).
+//!
+//! A session contains everything that is needed to continue an investigation another day:
+//!
+//! - The program with its arguments and [launch options](crate::launch::LaunchOptions)
+//! - The breakpoints with their settings, and the pending breakpoints
+//! - The watchpoints
+//! - The enabled status of the plugins
+//! - The [signal policies](crate::signal::SignalPolicyTable)
+//! - The [skip list](crate::skiplist)
+//!
+//! Breakpoints and watchpoints are stored as [`ModuleOffset`]s, so that they are found again
+//! even if the program is loaded at a different address. Watchpoints on the stack or the heap
+//! are not part of a session, as their addresses are different in every run.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::breakpoint::{BreakpointKind, BreakpointSettings};
+use crate::errors::Result;
+use crate::launch::LaunchConfig;
+use crate::memorymap::ModuleOffset;
+use crate::signal::SignalPolicyTable;
+use crate::skiplist::SkipList;
+use crate::watchpoint::WatchKind;
+
+/// The setup of the debugger, as written to a session file
+///
+/// # Examples
+///
+/// ```no_run
+/// use coreminer::session::Session;
+/// use std::path::Path;
+///
+/// let session = Session::load(Path::new("/tmp/session.json")).unwrap();
+/// for bp in &session.breakpoints {
+///     println!("breakpoint at {}", bp.location);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// The program that is debugged, [`None`] if the debugger did not launch it
+    pub launch: Option<LaunchConfig>,
+    /// The breakpoints in mapped files
+    pub breakpoints: Vec<SessionBreakpoint>,
+    /// Names of functions with breakpoints that wait for a library to be loaded
+    pub pending_breakpoints: Vec<String>,
+    /// The watchpoints in mapped files
+    pub watchpoints: Vec<SessionWatchpoint>,
+    /// The plugins and if they are enabled
+    #[cfg(feature = "plugins")]
+    pub plugins: Vec<(steckrs::PluginIDOwned, bool)>,
+    /// What happens when the debuggee receives a signal
+    pub signal_policies: SignalPolicyTable,
+    /// The code that stepping does not stop in
+    pub skip_list: SkipList,
+}
+
+/// A breakpoint in a [`Session`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionBreakpoint {
+    /// Where the breakpoint is
+    pub location: ModuleOffset,
+    /// If the breakpoint is set in the code or in a debug register
+    pub kind: BreakpointKind,
+    /// Condition, ignore count, commands and if the breakpoint is temporary
+    pub settings: BreakpointSettings,
+}
+
+/// A watchpoint in a [`Session`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionWatchpoint {
+    /// Where the watched region starts
+    pub location: ModuleOffset,
+    /// Length of the watched region in bytes
+    pub len: usize,
+    /// Which accesses the watchpoint stops at
+    pub kind: WatchKind,
+}
+
+impl Session {
+    /// Reads a session from a JSON file
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - Path of the session file
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the file cannot be read or is not a valid session.
+    pub fn load(path: &Path) -> Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+
+    /// Writes the session to a JSON file, replacing the file if it exists
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - Path of the session file
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ffi::CString;
+    use std::path::PathBuf;
+
+    use nix::sys::signal::Signal;
+
+    use super::*;
+    use crate::launch::LaunchOptions;
+    use crate::signal::SignalPolicy;
+    use crate::skiplist::SkipRule;
+
+    #[test]
+    fn test_session_roundtrip() {
+        let mut session = Session {
+            launch: Some(LaunchConfig {
+                executable: PathBuf::from("/usr/bin/ls"),
+                arguments: vec![CString::new("/usr/bin/ls").unwrap(), c"-la".into()],
+                options: LaunchOptions::default(),
+            }),
+            breakpoints: vec![SessionBreakpoint {
+                location: ModuleOffset::new("/usr/bin/ls", 0x1139),
+                kind: BreakpointKind::Software,
+                settings: BreakpointSettings {
+                    condition: Some("rdi == 0x10".parse().unwrap()),
+                    ignore_count: 2,
+                    temporary: false,
+                    commands: vec![],
+                },
+            }],
+            pending_breakpoints: vec!["plugin_init".to_string()],
+            watchpoints: vec![SessionWatchpoint {
+                location: ModuleOffset::new("/usr/bin/ls", 0x4010),
+                len: 4,
+                kind: WatchKind::Write,
+            }],
+            ..Default::default()
+        };
+        session
+            .signal_policies
+            .set(Signal::SIGUSR1, SignalPolicy::new(false, true, true));
+        session
+            .skip_list
+            .add(SkipRule::SourcePrefix(PathBuf::from("/usr/include")));
+
+        let json = serde_json::to_string(&session).unwrap();
+        let loaded: Session = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, session);
+    }
+
+    #[test]
+    fn test_session_defaults() {
+        let session: Session = serde_json::from_str("{}").unwrap();
+        assert_eq!(session, Session::default());
+    }
+}
@@ -0,0 +1,891 @@
+//! # Shared Library Module
+//!
+//! Keeps track of the shared libraries that the dynamic loader maps into the debuggee, so that
+//! breakpoints can be set in them by name, even before they are loaded.
+//!
+//! The dynamic loader tells debuggers about the loaded objects with its rendezvous structure
+//! `r_debug`, which the `DT_DEBUG` entry in the dynamic section of the executable points to. It
+//! has the head of the `link_map` list, with the path and the load bias of each loaded object,
+//! and the address of `_dl_debug_state`, an empty function that the loader calls before and
+//! after it changes the list. The debugger sets an internal [`Breakpoint`] there and reads the
+//! list again whenever the loader is done with a change. The difference to the libraries known
+//! before is reported as a [`LibraryEvent`].
+//!
+//! The loader only fills in `r_debug` while the program starts, after the debugger got control
+//! of it. If the loader has `_dl_debug_state` in its symbol table, the breakpoint is set there
+//! right away. Most distributions strip the loader, so otherwise the debugger breaks at the entry
+//! point of the executable first. The libraries the executable was linked against are loaded by
+//! then, and the breakpoint is moved to `_dl_debug_state`. Breakpoints in the constructors of
+//! these libraries are set too late in that case.
+//!
+//! The symbols of a library are read from its DWARF debug information, if it has any, and from
+//! its ELF symbol tables, which most libraries have even if they are stripped. They are used to
+//! [resolve functions](Debuggee::resolve_function) that the executable does not have.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use nix::unistd::Pid;
+use object::{
+    Object, ObjectSection, ObjectSegment, ObjectSymbol, ObjectSymbolTable, RelocationFlags,
+    RelocationTarget,
+};
+use serde::Serialize;
+use tracing::{debug, info, trace, warn};
+
+use crate::breakpoint::Breakpoint;
+use crate::callframe::CallFrameTable;
+use crate::dbginfo::{self, search_through_symbols, CMDebugInfo, OwnedSymbol, SymbolKind};
+use crate::debuggee::Debuggee;
+use crate::disassemble::jump_slot;
+use crate::errors::{DebuggerError, Result};
+use crate::{Addr, Register};
+
+/// Offset of `r_map`, the head of the `link_map` list, in `struct r_debug`
+const R_DEBUG_MAP: usize = 8;
+/// Offset of `r_brk`, the address of `_dl_debug_state`, in `struct r_debug`
+const R_DEBUG_BRK: usize = 16;
+/// Offset of `r_state` in `struct r_debug`
+const R_DEBUG_STATE: usize = 24;
+/// `r_state` when the `link_map` list is not being changed
+const RT_CONSISTENT: usize = 0;
+/// Offset of `l_addr`, the load bias, in `struct link_map`
+const LINK_MAP_ADDR: usize = 0;
+/// Offset of `l_name`, the path of the object, in `struct link_map`
+const LINK_MAP_NAME: usize = 8;
+/// Offset of `l_next` in `struct link_map`
+const LINK_MAP_NEXT: usize = 24;
+/// Tag of the entry that ends the dynamic section
+const DT_NULL: usize = 0;
+/// Tag of the entry in the dynamic section that points to `r_debug`
+const DT_DEBUG: usize = 21;
+/// Size of an entry in the dynamic section
+const DYN_ENTRY_SIZE: usize = 16;
+/// At most this many entries of the dynamic section are searched for `DT_DEBUG`
+const MAX_DYN_ENTRIES: usize = 1024;
+/// At most this many entries of the `link_map` list are read, in case it is broken
+const MAX_LINK_MAP_ENTRIES: usize = 4096;
+/// At most this many bytes of the path of a library are read
+const MAX_PATH_LEN: usize = 4096;
+/// The name of the function the loader calls when it changes the `link_map` list
+const DEBUG_STATE_SYMBOL: &str = "_dl_debug_state";
+/// Enough bytes for the instructions of a PLT stub that jump to the function
+const PLT_STUB_LEN: usize = 16;
+
+/// A shared library that is loaded into the debuggee
+#[derive(Debug, Clone)]
+pub struct SharedLibrary {
+    /// Path of the library, as the loader found it
+    path: PathBuf,
+    /// Load bias of the library, `l_addr` in its `link_map` entry
+    base: Addr,
+    /// Address of the `link_map` entry of the library
+    link_map: Addr,
+    /// The lowest and highest address of the segments of the library, once it was read
+    range: Option<(Addr, Addr)>,
+    /// Debug symbols of the library, if it has DWARF debug information
+    symbols: Vec<OwnedSymbol>,
+    /// Functions from the ELF symbol tables of the library
+    functions: Vec<ElfFunction>,
+    /// Call frame information of the library
+    frames: CallFrameTable,
+}
+
+/// A function from an ELF symbol table
+#[derive(Debug, Clone)]
+struct ElfFunction {
+    name: String,
+    addr: Addr,
+    size: usize,
+    global: bool,
+}
+
+/// Information on a shared library, as listed by
+/// [`Status::ListLibraries`](crate::feedback::Status::ListLibraries)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct LibraryInfo {
+    /// Path of the library
+    pub path: PathBuf,
+    /// Load bias of the library: how far it was moved from where it was linked
+    pub base: Addr,
+}
+
+/// The dynamic loader changed which shared libraries are loaded
+///
+/// The debuggee continues right after it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LibraryEvent {
+    /// Libraries that were loaded
+    pub loaded: Vec<LibraryInfo>,
+    /// Libraries that were unloaded, their breakpoints are gone
+    pub unloaded: Vec<LibraryInfo>,
+    /// Pending breakpoints that were set in the loaded libraries, by function name
+    pub breakpoints: Vec<(String, Addr)>,
+}
+
+/// Where the debugger learns about changes to the loaded libraries
+#[derive(Debug, Clone)]
+pub(crate) struct Rendezvous {
+    /// Address of the dynamic section of the executable
+    dynamic: Addr,
+    /// Address of `r_debug`, once the loader filled in `DT_DEBUG`
+    r_debug: Option<Addr>,
+    /// Address of the internal breakpoint
+    breakpoint: Addr,
+    /// Whether the internal breakpoint is at the entry point of the executable, waiting for the
+    /// loader to fill in `r_debug`
+    at_entry: bool,
+    /// The slots of the global offset table that the PLT stubs of the executable jump through,
+    /// with the names of the functions they are bound to
+    jump_slots: BTreeMap<Addr, String>,
+}
+
+impl SharedLibrary {
+    /// Reads the symbols of a library that was just loaded
+    ///
+    /// A library whose file cannot be read is kept without symbols, the problem is logged.
+    ///
+    /// # Parameters
+    ///
+    /// * `pid` - The process the library was loaded into, a relative path is relative to its
+    ///   working directory
+    /// * `path` - The path of the library
+    /// * `base` - The load bias of the library
+    /// * `link_map` - The address of the `link_map` entry of the library
+    fn load(pid: Pid, path: PathBuf, base: Addr, link_map: Addr) -> Self {
+        let mut library = Self {
+            path,
+            base,
+            link_map,
+            range: None,
+            symbols: Vec::new(),
+            functions: Vec::new(),
+            frames: CallFrameTable::default(),
+        };
+        if let Err(e) = library.read_symbols(pid) {
+            warn!(
+                "could not read the symbols of '{}': {e}",
+                library.path.to_string_lossy()
+            );
+        }
+        library
+    }
+
+    /// Reads the segments, ELF symbols and DWARF symbols from the file of the library
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the file cannot be read or parsed.
+    fn read_symbols(&mut self, pid: Pid) -> Result<()> {
+        let file = if self.path.is_relative() {
+            PathBuf::from(format!("/proc/{pid}/cwd")).join(&self.path)
+        } else {
+            self.path.clone()
+        };
+        let data = std::fs::read(file)?;
+        let object = object::File::parse(data.as_slice())?;
+
+        self.range = object
+            .segments()
+            .map(|s| (s.address() as usize, (s.address() + s.size()) as usize))
+            .reduce(|(low, high), (l, h)| (low.min(l), high.max(h)))
+            .map(|(low, high)| (self.base + low, self.base + high));
+        self.functions = object
+            .symbols()
+            .chain(object.dynamic_symbols())
+            .filter(|s| {
+                s.kind() == object::SymbolKind::Text && s.is_definition() && s.address() != 0
+            })
+            .filter_map(|s| {
+                Some(ElfFunction {
+                    name: s.name().ok()?.to_string(),
+                    addr: self.base + s.address() as usize,
+                    size: s.size() as usize,
+                    global: s.is_global(),
+                })
+            })
+            .collect();
+
+        let dbginfo = CMDebugInfo::build(object)?;
+        self.symbols = Debuggee::parse_symbols(self.base, &dbginfo)?;
+        self.frames = CallFrameTable::build(&dbginfo, self.base)?;
+        debug!(
+            "read {} functions and {} debug symbols of '{}'",
+            self.functions.len(),
+            self.symbols.len(),
+            self.path.to_string_lossy()
+        );
+        Ok(())
+    }
+
+    /// Gets the path of the library
+    #[inline]
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the load bias of the library
+    #[inline]
+    #[must_use]
+    pub fn base(&self) -> Addr {
+        self.base
+    }
+
+    /// Gets the debug symbols of the library, empty if it has no DWARF debug information
+    #[inline]
+    #[must_use]
+    pub fn symbols(&self) -> &[OwnedSymbol] {
+        &self.symbols
+    }
+
+    /// Gets the call frame information of the library, empty if it could not be read
+    #[inline]
+    #[must_use]
+    pub fn frames(&self) -> &CallFrameTable {
+        &self.frames
+    }
+
+    /// Returns the information on this library that is listed to the user
+    #[must_use]
+    pub fn info(&self) -> LibraryInfo {
+        LibraryInfo {
+            path: self.path.clone(),
+            base: self.base,
+        }
+    }
+
+    /// Checks if an address is in one of the segments of the library
+    #[must_use]
+    pub fn contains(&self, addr: Addr) -> bool {
+        self.range
+            .is_some_and(|(low, high)| low <= addr && addr < high)
+    }
+
+    /// Finds the name of the function in the ELF symbol tables of the library that contains an
+    /// address, Rust names are demangled
+    #[must_use]
+    pub fn elf_function_name(&self, addr: Addr) -> Option<String> {
+        self.functions
+            .iter()
+            .find(|f| f.addr <= addr && addr < f.addr + f.size.max(1))
+            .map(|f| format!("{:#}", rustc_demangle::demangle(&f.name)))
+    }
+
+    /// Finds a function of the library by its name
+    ///
+    /// The debug symbols are searched first, then the ELF symbol tables. Of the ELF symbols,
+    /// global ones are preferred over local ones with the same name.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some((low, high)))` - The address of the function and the address after it, if its
+    ///   size is known
+    /// * `Ok(None)` - If the library has no function with this name
+    ///
+    /// # Errors
+    ///
+    /// This function fails if several functions with this name exist at different addresses.
+    fn function(&self, name: &str) -> Result<Option<(Addr, Option<Addr>)>> {
+        let mut functions: Vec<(Addr, Option<Addr>)> = search_through_symbols(&self.symbols, |s| {
+            s.kind() == SymbolKind::Function && s.name() == Some(name)
+        })
+        .iter()
+        .filter_map(|s| Some((s.low_addr()?, s.high_addr())))
+        .collect();
+
+        if functions.is_empty() {
+            let matching: Vec<&ElfFunction> =
+                self.functions.iter().filter(|f| f.name == name).collect();
+            let global = matching.iter().any(|f| f.global);
+            functions = matching
+                .into_iter()
+                .filter(|f| f.global || !global)
+                .map(|f| (f.addr, (f.size > 0).then(|| f.addr + f.size)))
+                .collect();
+        }
+        functions.sort_unstable();
+        functions.dedup();
+
+        match functions[..] {
+            [] => Ok(None),
+            [function] => Ok(Some(function)),
+            _ => Err(DebuggerError::MultipleDwarfEntries),
+        }
+    }
+
+    /// Checks if this is the library of a `link_map` entry
+    fn is(&self, link_map: Addr, base: Addr, path: &Path) -> bool {
+        self.link_map == link_map && self.base == base && self.path == path
+    }
+}
+
+impl LibraryEvent {
+    /// Checks if nothing changed
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.loaded.is_empty() && self.unloaded.is_empty() && self.breakpoints.is_empty()
+    }
+}
+
+impl Display for LibraryInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.base, self.path.to_string_lossy())
+    }
+}
+
+impl Display for LibraryEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lines = Vec::new();
+        for library in &self.loaded {
+            lines.push(format!(
+                "Loaded '{}' at {}",
+                library.path.to_string_lossy(),
+                library.base
+            ));
+        }
+        for library in &self.unloaded {
+            lines.push(format!("Unloaded '{}'", library.path.to_string_lossy()));
+        }
+        for (name, addr) in &self.breakpoints {
+            lines.push(format!("Breakpoint for {name} at {addr}"));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl Debuggee {
+    /// Starts to keep track of the shared libraries of a dynamically linked executable
+    ///
+    /// The internal breakpoint is set at `_dl_debug_state` if the loader already filled in
+    /// `r_debug` or has a symbol table, otherwise at the entry point of the executable. If the
+    /// loader is done already, like in a process that was attached to, the libraries are read
+    /// right away. Nothing is done for a statically linked executable.
+    ///
+    /// # Parameters
+    ///
+    /// * `dbginfo` - Debug information of the executable
+    /// * `load_bias` - The load bias of the executable, see [`CMDebugInfo::load_bias`]
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the memory or the auxiliary vector of the process cannot be read,
+    /// or the internal breakpoint cannot be set.
+    pub(crate) fn watch_loader(
+        &mut self,
+        dbginfo: &CMDebugInfo<'_>,
+        load_bias: Addr,
+    ) -> Result<()> {
+        let Some(dynamic) = object_dynamic(&dbginfo.object_info) else {
+            debug!("the executable is statically linked, it has no shared libraries");
+            return Ok(());
+        };
+        let dynamic = load_bias + dynamic;
+
+        let r_debug = self.r_debug(dynamic)?;
+        let brk = match r_debug {
+            Some(r_debug) => Some(Addr::from(self.read_word(r_debug + R_DEBUG_BRK)?)),
+            None => None,
+        };
+        let (breakpoint, at_entry) = if let Some(brk) = brk.filter(|b| *b != Addr::NULL) {
+            (brk, false)
+        } else if let Some(debug_state) = self.loader_debug_state()? {
+            (debug_state, false)
+        } else if let Some(entry) = auxv_entry(self.pid, nix::libc::AT_ENTRY)? {
+            (Addr::from(entry), true)
+        } else {
+            warn!("the process has no entry point, shared libraries are not tracked");
+            return Ok(());
+        };
+        debug!(
+            "watching the dynamic loader with a breakpoint at {breakpoint}{}",
+            if at_entry { ", the entry point" } else { "" }
+        );
+        self.set_loader_breakpoint(breakpoint)?;
+        self.rendezvous = Some(Rendezvous {
+            dynamic,
+            r_debug: r_debug.filter(|_| !at_entry),
+            breakpoint,
+            at_entry,
+            jump_slots: jump_slots(&dbginfo.object_info, load_bias),
+        });
+
+        if let (Some(r_debug), false) = (r_debug, at_entry) {
+            self.update_libraries(r_debug)?;
+        }
+        Ok(())
+    }
+
+    /// Checks if the debugger keeps track of the shared libraries of the debuggee
+    ///
+    /// This is not the case for statically linked executables and core files.
+    #[inline]
+    #[must_use]
+    pub fn tracks_libraries(&self) -> bool {
+        self.rendezvous.is_some()
+    }
+
+    /// Gets the address of the internal breakpoint for the dynamic loader, if there is one
+    #[must_use]
+    pub(crate) fn loader_breakpoint(&self) -> Option<Addr> {
+        self.rendezvous.as_ref().map(|r| r.breakpoint)
+    }
+
+    /// Gets the shared libraries that are loaded into the debuggee, in the order they were loaded
+    #[inline]
+    #[must_use]
+    pub fn libraries(&self) -> &[SharedLibrary] {
+        &self.libraries
+    }
+
+    /// Handles a hit of the internal breakpoint for the dynamic loader
+    ///
+    /// At the entry point of the executable, the breakpoint is moved to `_dl_debug_state` and
+    /// the thread is moved back onto the original instruction. If the loader is done with
+    /// changing the `link_map` list, the libraries are read again.
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread that hit the breakpoint
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(LibraryEvent))` - The libraries that were loaded and unloaded
+    /// * `Ok(None)` - If the `link_map` list is not consistent right now, or the debugger does
+    ///   not keep track of the shared libraries
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the memory or the registers of the debuggee cannot be read or
+    /// written, or the internal breakpoint cannot be moved.
+    pub(crate) fn library_event(&mut self, tid: Pid) -> Result<Option<LibraryEvent>> {
+        let Some(rendezvous) = self.rendezvous.clone() else {
+            return Ok(None);
+        };
+        let r_debug = match rendezvous.r_debug {
+            Some(r_debug) => Some(r_debug),
+            None => self.r_debug(rendezvous.dynamic)?,
+        };
+        if rendezvous.at_entry && self.remove_loader_breakpoint(rendezvous.breakpoint) {
+            crate::set_reg(tid, Register::rip, rendezvous.breakpoint.into())?;
+        }
+        let Some(r_debug) = r_debug else {
+            if rendezvous.at_entry {
+                warn!(
+                    "the dynamic loader did not fill in DT_DEBUG, shared libraries are not tracked"
+                );
+                self.rendezvous = None;
+            }
+            return Ok(None);
+        };
+
+        let mut breakpoint = rendezvous.breakpoint;
+        if rendezvous.at_entry {
+            breakpoint = Addr::from(self.read_word(r_debug + R_DEBUG_BRK)?);
+            debug!("moving the breakpoint for the dynamic loader to {breakpoint}");
+            self.set_loader_breakpoint(breakpoint)?;
+        }
+        self.rendezvous = Some(Rendezvous {
+            r_debug: Some(r_debug),
+            breakpoint,
+            at_entry: false,
+            ..rendezvous
+        });
+
+        let state = self.read_word(r_debug + R_DEBUG_STATE)? & 0xffff_ffff;
+        if state != RT_CONSISTENT {
+            trace!("the dynamic loader is changing the libraries, r_state is {state}");
+            return Ok(None);
+        }
+        self.update_libraries(r_debug).map(Some)
+    }
+
+    /// Sets the internal breakpoint for the dynamic loader again after the user removed their
+    /// breakpoint at the same address
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the breakpoint cannot be set.
+    pub(crate) fn restore_loader_breakpoint(&mut self, addr: Addr) -> Result<()> {
+        if self.loader_breakpoint() == Some(addr) {
+            self.set_loader_breakpoint(addr)?;
+        }
+        Ok(())
+    }
+
+    /// Finds a function in the shared libraries, see [`SharedLibrary::function`]
+    ///
+    /// The libraries are searched in the order they were loaded, like the dynamic loader
+    /// resolves symbols.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if a library has several functions with this name at different
+    /// addresses.
+    pub(crate) fn library_function(&self, name: &str) -> Result<Option<(Addr, Option<Addr>)>> {
+        for library in &self.libraries {
+            if let Some(function) = library.function(name)? {
+                debug!(
+                    "found function {name} in '{}'",
+                    library.path.to_string_lossy()
+                );
+                return Ok(Some(function));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Finds the function in a shared library that a PLT stub of the executable jumps to
+    ///
+    /// The stub jumps through a slot of the global offset table. With lazy binding, the slot
+    /// points back into the stub until the function is called the first time, so the function
+    /// is looked up by the name of the symbol the slot is bound to.
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - The address that may be a PLT stub
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some((String, Addr)))` - The name of the symbol and the address of the function the
+    ///   stub jumps to
+    /// * `Ok(None)` - If `addr` is not a PLT stub, or the function is not loaded
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the code at `addr` cannot be read, or a library has several
+    /// functions with the name of the symbol.
+    pub fn plt_target(&self, addr: Addr) -> Result<Option<(String, Addr)>> {
+        let Some(rendezvous) = &self.rendezvous else {
+            return Ok(None);
+        };
+        let mut code = [0; PLT_STUB_LEN];
+        self.read_memory(&mut code, addr)?;
+        self.hide_breakpoints(&mut code, addr);
+        let Some(name) = jump_slot(&code, addr).and_then(|slot| rendezvous.jump_slots.get(&slot))
+        else {
+            return Ok(None);
+        };
+        trace!("{addr} is the PLT stub of {name}");
+        Ok(self
+            .library_function(name)?
+            .map(|(low, _)| (name.clone(), low)))
+    }
+
+    /// Reads the `link_map` list and compares it to the known libraries
+    ///
+    /// The symbols of new libraries are read. The breakpoints in libraries that were unloaded
+    /// are forgotten, their memory is gone.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the `link_map` list cannot be read.
+    fn update_libraries(&mut self, r_debug: Addr) -> Result<LibraryEvent> {
+        let mut current = Vec::new();
+        let mut entry = Addr::from(self.read_word(r_debug + R_DEBUG_MAP)?);
+        while entry != Addr::NULL && current.len() < MAX_LINK_MAP_ENTRIES {
+            let base = Addr::from(self.read_word(entry + LINK_MAP_ADDR)?);
+            let name = self.read_c_string(Addr::from(self.read_word(entry + LINK_MAP_NAME)?))?;
+            // the executable has no name, the vDSO has no path
+            if name.contains('/') {
+                current.push((entry, base, PathBuf::from(name)));
+            }
+            entry = Addr::from(self.read_word(entry + LINK_MAP_NEXT)?);
+        }
+
+        let mut event = LibraryEvent::default();
+        let (kept, unloaded): (Vec<_>, Vec<_>) = std::mem::take(&mut self.libraries)
+            .into_iter()
+            .partition(|lib| current.iter().any(|(e, b, p)| lib.is(*e, *b, p)));
+        self.libraries = kept;
+        for library in unloaded {
+            info!("unloaded '{}'", library.path.to_string_lossy());
+            self.breakpoints.retain(|addr, bp| {
+                if library.contains(*addr) {
+                    bp.invalidate();
+                    false
+                } else {
+                    true
+                }
+            });
+            event.unloaded.push(library.info());
+        }
+        for (link_map, base, path) in current {
+            if self
+                .libraries
+                .iter()
+                .any(|lib| lib.is(link_map, base, &path))
+            {
+                continue;
+            }
+            info!("loaded '{}' at {base}", path.to_string_lossy());
+            let library = SharedLibrary::load(self.pid, path, base, link_map);
+            event.loaded.push(library.info());
+            self.libraries.push(library);
+        }
+        Ok(event)
+    }
+
+    /// Finds `r_debug` through the `DT_DEBUG` entry of the dynamic section of the executable
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Addr))` - The address of `r_debug`
+    /// * `Ok(None)` - If the loader did not fill in `DT_DEBUG` yet, or there is none
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the dynamic section cannot be read.
+    fn r_debug(&self, dynamic: Addr) -> Result<Option<Addr>> {
+        for idx in 0..MAX_DYN_ENTRIES {
+            let entry = dynamic + idx * DYN_ENTRY_SIZE;
+            match self.read_word(entry)? {
+                DT_NULL => break,
+                DT_DEBUG => {
+                    let r_debug = Addr::from(self.read_word(entry + 8usize)?);
+                    return Ok((r_debug != Addr::NULL).then_some(r_debug));
+                }
+                _ => (),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Finds `_dl_debug_state` in the symbol table of the dynamic loader
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Addr))` - The address of `_dl_debug_state`
+    /// * `Ok(None)` - If there is no loader or it has no symbol for it
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the auxiliary vector or the memory map of the process cannot be
+    /// read.
+    fn loader_debug_state(&self) -> Result<Option<Addr>> {
+        let Some(base) = auxv_entry(self.pid, nix::libc::AT_BASE)?.filter(|b| *b != 0) else {
+            return Ok(None);
+        };
+        let base = Addr::from(base);
+        let map = self.get_process_map()?;
+        let Some(path) = map
+            .regions
+            .iter()
+            .find(|r| r.start_address <= base && base < r.end_address)
+            .and_then(|r| r.path.clone())
+        else {
+            return Ok(None);
+        };
+
+        let data = match std::fs::read(&path) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("could not read the dynamic loader '{path}': {e}");
+                return Ok(None);
+            }
+        };
+        let object = object::File::parse(data.as_slice())?;
+        let addr = object
+            .symbols()
+            .chain(object.dynamic_symbols())
+            .find(|s| s.name() == Ok(DEBUG_STATE_SYMBOL))
+            .map(|s| dbginfo::load_bias(&object, base) + s.address() as usize);
+        if addr.is_none() {
+            debug!("the dynamic loader '{path}' has no symbol {DEBUG_STATE_SYMBOL}");
+        }
+        Ok(addr)
+    }
+
+    /// Sets the internal breakpoint for the dynamic loader, unless there already is a breakpoint
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the breakpoint cannot be enabled.
+    fn set_loader_breakpoint(&mut self, addr: Addr) -> Result<()> {
+        if !self.breakpoints.contains_key(&addr) {
+            let mut bp = Breakpoint::new_internal(self.pid, addr);
+            bp.enable()?;
+            self.breakpoints.insert(addr, bp);
+        }
+        Ok(())
+    }
+
+    /// Removes the internal breakpoint for the dynamic loader, a breakpoint of the user is kept
+    ///
+    /// # Returns
+    ///
+    /// Whether the breakpoint was removed.
+    fn remove_loader_breakpoint(&mut self, addr: Addr) -> bool {
+        if self
+            .breakpoints
+            .get(&addr)
+            .is_some_and(Breakpoint::is_internal)
+        {
+            // gets disabled on dropping
+            self.breakpoints.remove(&addr);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Reads a null terminated string from the memory of the debuggee
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the memory cannot be read.
+    fn read_c_string(&self, addr: Addr) -> Result<String> {
+        if addr == Addr::NULL {
+            return Ok(String::new());
+        }
+        let mut bytes = Vec::new();
+        let mut chunk = [0; 64];
+        while bytes.len() < MAX_PATH_LEN {
+            let read = self.read_memory(&mut chunk, addr + bytes.len())?;
+            if let Some(end) = chunk[..read].iter().position(|b| *b == 0) {
+                bytes.extend_from_slice(&chunk[..end]);
+                break;
+            }
+            if read == 0 {
+                break;
+            }
+            bytes.extend_from_slice(&chunk[..read]);
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+/// Reads the `JUMP_SLOT` relocations of an object file: the slots of the global offset table
+/// that its PLT stubs jump through, and the names of the symbols they are bound to
+///
+/// # Parameters
+///
+/// * `object_info` - The object file
+/// * `load_bias` - The load bias of the object file, added to the slot addresses
+fn jump_slots(object_info: &object::File<'_>, load_bias: Addr) -> BTreeMap<Addr, String> {
+    let (Some(relocations), Some(symbols)) = (
+        object_info.dynamic_relocations(),
+        object_info.dynamic_symbol_table(),
+    ) else {
+        return BTreeMap::new();
+    };
+    relocations
+        .filter(|(_, relocation)| {
+            relocation.flags()
+                == RelocationFlags::Elf {
+                    r_type: object::elf::R_X86_64_JUMP_SLOT,
+                }
+        })
+        .filter_map(|(offset, relocation)| {
+            let RelocationTarget::Symbol(index) = relocation.target() else {
+                return None;
+            };
+            let name = symbols.symbol_by_index(index).ok()?.name().ok()?;
+            Some((load_bias + offset as usize, name.to_string()))
+        })
+        .collect()
+}
+
+/// Gets the link address of the dynamic section of an object file, if it has one
+fn object_dynamic(object_info: &object::File<'_>) -> Option<usize> {
+    object_info
+        .section_by_name(".dynamic")
+        .map(|s| s.address() as usize)
+}
+
+/// Reads an entry of the auxiliary vector that the kernel gave the process
+///
+/// # Parameters
+///
+/// * `pid` - The process
+/// * `key` - The `AT_*` type of the entry
+///
+/// # Errors
+///
+/// This function fails if `/proc/<pid>/auxv` cannot be read.
+fn auxv_entry(pid: Pid, key: u64) -> Result<Option<usize>> {
+    let auxv = std::fs::read(format!("/proc/{pid}/auxv"))?;
+    Ok(auxv
+        .chunks_exact(16)
+        .map(|entry| {
+            let (key, value) = entry.split_at(8);
+            (
+                u64::from_ne_bytes(key.try_into().unwrap_or_default()),
+                u64::from_ne_bytes(value.try_into().unwrap_or_default()),
+            )
+        })
+        .find(|(k, _)| *k == key)
+        .map(|(_, value)| value as usize))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn library(functions: &[(&str, usize, bool)]) -> SharedLibrary {
+        SharedLibrary {
+            path: PathBuf::from("/usr/lib/libtest.so"),
+            base: Addr::from(0x7f00_0000_0000usize),
+            link_map: Addr::from(0x1000usize),
+            range: Some((
+                Addr::from(0x7f00_0000_0000usize),
+                Addr::from(0x7f00_0001_0000usize),
+            )),
+            symbols: Vec::new(),
+            functions: functions
+                .iter()
+                .map(|(name, addr, global)| ElfFunction {
+                    name: (*name).to_string(),
+                    addr: Addr::from(*addr),
+                    size: 0x10,
+                    global: *global,
+                })
+                .collect(),
+            frames: CallFrameTable::default(),
+        }
+    }
+
+    #[test]
+    fn test_library_function_prefers_global() {
+        let lib = library(&[
+            ("helper", 0x7f00_0000_1000, false),
+            ("helper", 0x7f00_0000_2000, true),
+            ("other", 0x7f00_0000_3000, true),
+        ]);
+        assert_eq!(
+            lib.function("helper").unwrap(),
+            Some((
+                Addr::from(0x7f00_0000_2000usize),
+                Some(Addr::from(0x7f00_0000_2010usize))
+            ))
+        );
+        assert_eq!(lib.function("missing").unwrap(), None);
+        assert_eq!(
+            lib.elf_function_name(Addr::from(0x7f00_0000_300cusize)),
+            Some("other".to_string())
+        );
+        assert_eq!(
+            lib.elf_function_name(Addr::from(0x7f00_0000_3010usize)),
+            None
+        );
+        assert!(lib.contains(Addr::from(0x7f00_0000_3000usize)));
+        assert!(!lib.contains(Addr::from(0x7f00_0001_0000usize)));
+    }
+
+    #[test]
+    fn test_library_event_display() {
+        let event = LibraryEvent {
+            loaded: vec![LibraryInfo {
+                path: PathBuf::from("/usr/lib/libtest.so"),
+                base: Addr::from(0x7f00_0000_0000usize),
+            }],
+            unloaded: Vec::new(),
+            breakpoints: vec![("helper".to_string(), Addr::from(0x7f00_0000_2004usize))],
+        };
+        assert_eq!(
+            event.to_string(),
+            "Loaded '/usr/lib/libtest.so' at 0x00007f0000000000\nBreakpoint for helper at 0x00007f0000002004"
+        );
+    }
+}
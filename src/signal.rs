@@ -0,0 +1,220 @@
+//! # Signal Module
+//!
+//! Provides the signal policy table, which decides what happens when the debuggee receives a
+//! signal.
+//!
+//! Every signal has a [`SignalPolicy`], similar to the `handle` command of gdb:
+//!
+//! - `stop`: all threads of the debuggee are stopped and control is given to the user
+//! - `print`: the signal is logged when it arrives
+//! - `pass`: the signal is delivered to the debuggee when it is resumed
+//!
+//! Signals with a policy that does not stop are passed on (or discarded) right away, without
+//! stopping the other threads. `SIGTRAP` is used by the debugger itself for breakpoints and
+//! single stepping, so its policy is only consulted for the `print` setting.
+//!
+//! The policies can be changed with
+//! [`Status::SetSignalPolicy`](crate::feedback::Status::SetSignalPolicy).
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use nix::sys::signal::Signal;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{DebuggerError, Result};
+
+/// Decides what happens when the debuggee receives a specific signal
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::signal::SignalPolicy;
+///
+/// let policy = SignalPolicy::default();
+/// assert!(policy.stop && policy.print && policy.pass);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SignalPolicy {
+    /// Stop the debuggee and give control to the user
+    pub stop: bool,
+    /// Log the signal when it arrives
+    pub print: bool,
+    /// Deliver the signal to the debuggee when it is resumed
+    pub pass: bool,
+}
+
+/// The [`SignalPolicy`] of every signal
+///
+/// The defaults are the same as in gdb: signals that are part of normal program operation like
+/// `SIGCHLD` or `SIGWINCH` are passed on silently, `SIGINT` and `SIGTRAP` stop but are not
+/// passed, and all other signals stop and are passed.
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::signal::{SignalPolicy, SignalPolicyTable};
+/// use nix::sys::signal::Signal;
+///
+/// let mut table = SignalPolicyTable::default();
+/// assert!(!table.get(Signal::SIGCHLD).stop);
+/// assert!(table.get(Signal::SIGTERM).pass);
+///
+/// table.set(Signal::SIGTERM, SignalPolicy { stop: false, print: true, pass: true });
+/// assert!(!table.get(Signal::SIGTERM).stop);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignalPolicyTable {
+    policies: BTreeMap<i32, SignalPolicy>,
+}
+
+impl SignalPolicy {
+    /// Creates a new [`SignalPolicy`]
+    ///
+    /// # Parameters
+    ///
+    /// * `stop` - Stop the debuggee and give control to the user
+    /// * `print` - Log the signal when it arrives
+    /// * `pass` - Deliver the signal to the debuggee when it is resumed
+    #[must_use]
+    pub const fn new(stop: bool, print: bool, pass: bool) -> Self {
+        Self { stop, print, pass }
+    }
+
+    /// The default policy of a signal, as in gdb
+    ///
+    /// # Parameters
+    ///
+    /// * `sig` - The signal
+    #[must_use]
+    pub const fn default_for(sig: Signal) -> Self {
+        match sig {
+            Signal::SIGINT | Signal::SIGTRAP => Self::new(true, true, false),
+            Signal::SIGALRM
+            | Signal::SIGURG
+            | Signal::SIGCHLD
+            | Signal::SIGWINCH
+            | Signal::SIGIO
+            | Signal::SIGVTALRM
+            | Signal::SIGPROF => Self::new(false, false, true),
+            _ => Self::new(true, true, true),
+        }
+    }
+}
+
+impl Default for SignalPolicy {
+    fn default() -> Self {
+        Self::new(true, true, true)
+    }
+}
+
+impl SignalPolicyTable {
+    /// Gets the policy for a signal
+    ///
+    /// # Parameters
+    ///
+    /// * `sig` - The signal
+    #[must_use]
+    pub fn get(&self, sig: Signal) -> SignalPolicy {
+        self.policies
+            .get(&(sig as i32))
+            .copied()
+            .unwrap_or_else(|| SignalPolicy::default_for(sig))
+    }
+
+    /// Sets the policy for a signal
+    ///
+    /// # Parameters
+    ///
+    /// * `sig` - The signal
+    /// * `policy` - The new policy of that signal
+    pub fn set(&mut self, sig: Signal, policy: SignalPolicy) {
+        self.policies.insert(sig as i32, policy);
+    }
+
+    /// Iterates over all signals and their policies, ordered by signal number
+    pub fn iter(&self) -> impl Iterator<Item = (Signal, SignalPolicy)> + '_ {
+        Signal::iterator().map(|sig| (sig, self.get(sig)))
+    }
+}
+
+impl Default for SignalPolicyTable {
+    fn default() -> Self {
+        Self {
+            policies: Signal::iterator()
+                .map(|sig| (sig as i32, SignalPolicy::default_for(sig)))
+                .collect(),
+        }
+    }
+}
+
+impl Display for SignalPolicyTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let yes_no = |b: bool| if b { "Yes" } else { "No" };
+        write!(f, "Signal      Stop  Print Pass")?;
+        for (sig, policy) in self.iter() {
+            write!(
+                f,
+                "\n{:<12}{:<6}{:<6}{}",
+                sig.as_str(),
+                yes_no(policy.stop),
+                yes_no(policy.print),
+                yes_no(policy.pass)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a signal from its number or name
+///
+/// Names are accepted with or without the `SIG` prefix and in any case.
+///
+/// # Parameters
+///
+/// * `s` - The number or name of the signal, like `15`, `SIGTERM` or `term`
+///
+/// # Errors
+///
+/// Fails with [`DebuggerError::ParseStr`] if `s` is not a known signal.
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::signal::parse_signal;
+/// use nix::sys::signal::Signal;
+///
+/// assert_eq!(parse_signal("15").unwrap(), Signal::SIGTERM);
+/// assert_eq!(parse_signal("SIGTERM").unwrap(), Signal::SIGTERM);
+/// assert_eq!(parse_signal("term").unwrap(), Signal::SIGTERM);
+/// assert!(parse_signal("SIGFOO").is_err());
+/// ```
+pub fn parse_signal(s: &str) -> Result<Signal> {
+    if let Ok(number) = s.parse::<i32>() {
+        return Signal::try_from(number).map_err(|_| DebuggerError::ParseStr(s.to_string()));
+    }
+    let name = s.to_uppercase();
+    let name = if name.starts_with("SIG") {
+        name
+    } else {
+        format!("SIG{name}")
+    };
+    Signal::from_str(&name).map_err(|_| DebuggerError::ParseStr(s.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_default_table_has_every_signal() {
+        let table = SignalPolicyTable::default();
+        assert_eq!(table.iter().count(), Signal::iterator().count());
+        assert_eq!(
+            table.get(Signal::SIGINT),
+            SignalPolicy::new(true, true, false)
+        );
+        assert_eq!(table.get(Signal::SIGTERM), SignalPolicy::default());
+    }
+}
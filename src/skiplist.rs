@@ -0,0 +1,306 @@
+//! # Skip List Module
+//!
+//! Provides the [`SkipList`]: rules for code that is not interesting to step through, like the C
+//! library or the internals of the Rust standard library. They are added with
+//! [`Status::AddSkip`](crate::feedback::Status::AddSkip).
+//!
+//! [`Status::StepInto`](crate::feedback::Status::StepInto) and
+//! [`Status::StepLine`](crate::feedback::Status::StepLine) do not stop in a called function
+//! that matches a rule, it runs until it returns instead. When line stepping returns into code
+//! that matches a rule, like a callback returning to `qsort`, it continues until the code of
+//! the user is reached again.
+//!
+//! A rule matches the [`CodeOrigin`] of an address:
+//!
+//! - [`SkipRule::Function`]: the name of the function, as a glob where `*` matches any text and
+//!   `?` a single character. These are the names in the debug information, for Rust without the
+//!   module path, or the demangled ELF symbols of a shared library.
+//! - [`SkipRule::CompileUnit`]: the name of the compilation unit, also as a glob
+//! - [`SkipRule::SourcePrefix`]: the source file of the line, like `/usr/include` or `/rustc`
+//! - [`SkipRule::Library`]: the shared library, by its file name or the end of its path
+//!
+//! Calls through the PLT of the executable are matched by the function in the shared library
+//! that they go to, with the name of the symbol that was called.
+
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dbginfo::{search_through_symbols, OwnedSymbol, SymbolKind};
+use crate::debuggee::Debuggee;
+use crate::Addr;
+
+/// A rule for code to skip when stepping, see the [module documentation](self)
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::skiplist::{CodeOrigin, SkipRule};
+///
+/// let rule = SkipRule::Function("*printf".to_string());
+/// let origin = CodeOrigin {
+///     function: Some("vfprintf".to_string()),
+///     ..Default::default()
+/// };
+/// assert!(rule.matches(&origin));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum SkipRule {
+    /// Functions whose name matches a glob
+    Function(String),
+    /// Code of compilation units whose name matches a glob
+    CompileUnit(String),
+    /// Code from source files whose path starts with a prefix
+    SourcePrefix(PathBuf),
+    /// Code of a shared library, by its file name or the end of its path
+    Library(PathBuf),
+}
+
+/// The rules for code to skip when stepping
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::skiplist::{SkipList, SkipRule};
+///
+/// let mut skip_list = SkipList::default();
+/// assert!(skip_list.add(SkipRule::Library("libc.so.6".into())));
+/// assert!(!skip_list.add(SkipRule::Library("libc.so.6".into())));
+/// assert_eq!(skip_list.rules().len(), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SkipList {
+    rules: Vec<SkipRule>,
+}
+
+/// Where the code at an address comes from, as far as the debugger knows
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodeOrigin {
+    /// Name of the function
+    pub function: Option<String>,
+    /// Name of the compilation unit of the function
+    pub compile_unit: Option<String>,
+    /// Source file of the line
+    pub file: Option<PathBuf>,
+    /// Path of the shared library, [`None`] for the executable
+    pub library: Option<PathBuf>,
+}
+
+impl SkipRule {
+    /// Checks if the rule matches code from an origin
+    #[must_use]
+    pub fn matches(&self, origin: &CodeOrigin) -> bool {
+        match self {
+            SkipRule::Function(glob) => origin
+                .function
+                .as_deref()
+                .is_some_and(|name| glob_match(glob, name)),
+            SkipRule::CompileUnit(glob) => origin
+                .compile_unit
+                .as_deref()
+                .is_some_and(|name| glob_match(glob, name)),
+            SkipRule::SourcePrefix(prefix) => origin
+                .file
+                .as_deref()
+                .is_some_and(|file| file.starts_with(prefix)),
+            SkipRule::Library(name) => origin
+                .library
+                .as_deref()
+                .is_some_and(|path| path.ends_with(name)),
+        }
+    }
+}
+
+impl SkipList {
+    /// Adds a rule
+    ///
+    /// # Returns
+    ///
+    /// `false` if the rule was already there
+    pub fn add(&mut self, rule: SkipRule) -> bool {
+        if self.rules.contains(&rule) {
+            return false;
+        }
+        self.rules.push(rule);
+        true
+    }
+
+    /// Removes a rule
+    ///
+    /// # Returns
+    ///
+    /// `false` if there was no such rule
+    pub fn remove(&mut self, rule: &SkipRule) -> bool {
+        let len = self.rules.len();
+        self.rules.retain(|r| r != rule);
+        self.rules.len() != len
+    }
+
+    /// Gets the rules, in the order they were added
+    #[inline]
+    #[must_use]
+    pub fn rules(&self) -> &[SkipRule] {
+        &self.rules
+    }
+
+    /// Checks if there are no rules
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Finds the first rule that matches code from an origin
+    #[must_use]
+    pub fn matching(&self, origin: &CodeOrigin) -> Option<&SkipRule> {
+        self.rules.iter().find(|rule| rule.matches(origin))
+    }
+}
+
+impl Display for SkipRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipRule::Function(glob) => write!(f, "function {glob}"),
+            SkipRule::CompileUnit(glob) => write!(f, "cu {glob}"),
+            SkipRule::SourcePrefix(prefix) => write!(f, "file {}", prefix.to_string_lossy()),
+            SkipRule::Library(name) => write!(f, "lib {}", name.to_string_lossy()),
+        }
+    }
+}
+
+impl Display for SkipList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Skip List:")?;
+        for rule in &self.rules {
+            write!(f, "\n  {rule}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Debuggee {
+    /// Finds out where the code at an address comes from, see [`CodeOrigin`]
+    ///
+    /// # Parameters
+    ///
+    /// * `addr` - An address in the code of the debuggee
+    #[must_use]
+    pub fn code_origin(&self, addr: Addr) -> CodeOrigin {
+        let library = self
+            .libraries()
+            .iter()
+            .find(|library| library.contains(addr));
+        let symbols = library.map_or(self.symbols(), |library| library.symbols());
+
+        let mut origin = CodeOrigin {
+            library: library.map(|library| library.path().to_path_buf()),
+            ..Default::default()
+        };
+        for unit in symbols {
+            let function = search_through_symbols(unit.children(), |s| {
+                s.kind() == SymbolKind::Function && contains(s, addr)
+            });
+            if let Some(function) = function.first() {
+                origin.function = function.name().map(str::to_string);
+                origin.compile_unit = unit
+                    .name()
+                    .filter(|_| unit.kind() == SymbolKind::CompileUnit)
+                    .map(str::to_string);
+                break;
+            }
+        }
+        if let Some(library) = library {
+            origin.function = origin.function.or_else(|| library.elf_function_name(addr));
+        } else {
+            origin.file = self
+                .lines()
+                .row_for_addr(addr)
+                .filter(|row| row.line != 0)
+                .map(|row| row.file.clone());
+        }
+        origin
+    }
+}
+
+/// Checks if the code of a symbol contains an address
+fn contains(symbol: &OwnedSymbol, addr: Addr) -> bool {
+    symbol.low_addr().is_some_and(|low| low <= addr)
+        && symbol.high_addr().is_some_and(|high| addr < high)
+}
+
+/// Matches a text against a glob, where `*` matches any text and `?` a single character
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::skiplist::glob_match;
+///
+/// assert!(glob_match("std::*", "std::rt::lang_start"));
+/// assert!(glob_match("*printf", "vfprintf"));
+/// assert!(!glob_match("print?", "printf_chk"));
+/// ```
+#[must_use]
+pub fn glob_match(glob: &str, text: &str) -> bool {
+    let glob: Vec<char> = glob.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut g, mut t) = (0, 0);
+    // where the last `*` was, and the text position it matched up to
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match glob.get(g) {
+            Some('*') => {
+                star = Some((g, t));
+                g += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                g += 1;
+                t += 1;
+            }
+            _ => match star {
+                // let the last `*` match one more character
+                Some((star_g, star_t)) => {
+                    star = Some((star_g, star_t + 1));
+                    g = star_g + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("core::*::fmt", "core::ptr::fmt"));
+        assert!(glob_match("a*b*c", "axxbyybzc"));
+        assert!(!glob_match("a*b*c", "axxbyyb"));
+        assert!(!glob_match("printf", "printf2"));
+    }
+
+    #[test]
+    fn test_rules_match_origin() {
+        let origin = CodeOrigin {
+            function: Some("write".to_string()),
+            compile_unit: Some("library/std/src/lib.rs/@/std.8a1f".to_string()),
+            file: Some(PathBuf::from("/rustc/4d91de4e/library/std/src/io/mod.rs")),
+            library: Some(PathBuf::from("/lib/x86_64-linux-gnu/libc.so.6")),
+        };
+        assert!(SkipRule::CompileUnit("library/std/*".to_string()).matches(&origin));
+        assert!(SkipRule::SourcePrefix("/rustc".into()).matches(&origin));
+        assert!(!SkipRule::SourcePrefix("/rust".into()).matches(&origin));
+        assert!(SkipRule::Library("libc.so.6".into()).matches(&origin));
+        assert!(!SkipRule::Library("c.so.6".into()).matches(&origin));
+        assert!(!SkipRule::Function("read".to_string()).matches(&origin));
+        assert!(!SkipRule::Function("write".to_string()).matches(&CodeOrigin::default()));
+    }
+}
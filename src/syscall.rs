@@ -0,0 +1,735 @@
+//! # Syscall Module
+//!
+//! Provides syscall tracing and syscall catchpoints for the debuggee.
+//!
+//! When syscall tracing or a syscall catchpoint is active, the debuggee is resumed with
+//! `PTRACE_SYSCALL` instead of `PTRACE_CONT`, so that it stops at the entry and exit of every
+//! syscall. The syscall number and arguments are decoded from the registers following the `x86_64`
+//! calling convention for syscalls:
+//!
+//! | Register   | Meaning                          |
+//! |------------|----------------------------------|
+//! | `orig_rax` | syscall number                   |
+//! | `rdi`      | first argument                   |
+//! | `rsi`      | second argument                  |
+//! | `rdx`      | third argument                   |
+//! | `r10`      | fourth argument                  |
+//! | `r8`       | fifth argument                   |
+//! | `r9`       | sixth argument                   |
+//! | `rax`      | return value (on syscall exit)   |
+//!
+//! Each stop is reported as a [`SyscallEvent`] with
+//! [`Feedback::Syscall`](crate::feedback::Feedback::Syscall).
+//!
+//! The debugger can also make a stopped thread execute a syscall of its choice, for example
+//! `mprotect` for software [watchpoints](crate::watchpoint). The `syscall` instruction is
+//! written over the code at the instruction pointer and stepped over, then the code and the
+//! registers are restored.
+
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+use nix::errno::Errno;
+use nix::libc::user_regs_struct;
+use nix::sys::ptrace;
+use nix::sys::signal::Signal;
+use nix::sys::wait::{waitpid, WaitPidFlag, WaitStatus};
+use nix::unistd::Pid;
+use serde::Serialize;
+use tracing::{debug, trace};
+
+use crate::breakpoint::ser_pid;
+use crate::debuggee::Debuggee;
+use crate::errors::{DebuggerError, Result};
+use crate::feedback::UserRegs;
+use crate::hwbreakpoint::EFLAGS_RF;
+use crate::{mem_read_word, mem_write_word, Addr, Word};
+
+/// The value of `rax` when a thread stops at a syscall entry (`-ENOSYS`)
+const SYSCALL_ENTRY_RAX: i64 = -(nix::libc::ENOSYS as i64);
+
+/// The `syscall` instruction
+const SYSCALL_INSTRUCTION: [u8; 2] = [0x0f, 0x05];
+
+/// Return values of syscalls from `-4095` to `-1` are negated error numbers
+const MAX_ERRNO: i64 = 4095;
+
+/// Syscall numbers and names of `x86_64` Linux, ordered by number
+///
+/// Source reference: `asm/unistd_64.h`
+pub const SYSCALLS: &[(u64, &str)] = &[
+    (0, "read"),
+    (1, "write"),
+    (2, "open"),
+    (3, "close"),
+    (4, "stat"),
+    (5, "fstat"),
+    (6, "lstat"),
+    (7, "poll"),
+    (8, "lseek"),
+    (9, "mmap"),
+    (10, "mprotect"),
+    (11, "munmap"),
+    (12, "brk"),
+    (13, "rt_sigaction"),
+    (14, "rt_sigprocmask"),
+    (15, "rt_sigreturn"),
+    (16, "ioctl"),
+    (17, "pread64"),
+    (18, "pwrite64"),
+    (19, "readv"),
+    (20, "writev"),
+    (21, "access"),
+    (22, "pipe"),
+    (23, "select"),
+    (24, "sched_yield"),
+    (25, "mremap"),
+    (26, "msync"),
+    (27, "mincore"),
+    (28, "madvise"),
+    (29, "shmget"),
+    (30, "shmat"),
+    (31, "shmctl"),
+    (32, "dup"),
+    (33, "dup2"),
+    (34, "pause"),
+    (35, "nanosleep"),
+    (36, "getitimer"),
+    (37, "alarm"),
+    (38, "setitimer"),
+    (39, "getpid"),
+    (40, "sendfile"),
+    (41, "socket"),
+    (42, "connect"),
+    (43, "accept"),
+    (44, "sendto"),
+    (45, "recvfrom"),
+    (46, "sendmsg"),
+    (47, "recvmsg"),
+    (48, "shutdown"),
+    (49, "bind"),
+    (50, "listen"),
+    (51, "getsockname"),
+    (52, "getpeername"),
+    (53, "socketpair"),
+    (54, "setsockopt"),
+    (55, "getsockopt"),
+    (56, "clone"),
+    (57, "fork"),
+    (58, "vfork"),
+    (59, "execve"),
+    (60, "exit"),
+    (61, "wait4"),
+    (62, "kill"),
+    (63, "uname"),
+    (64, "semget"),
+    (65, "semop"),
+    (66, "semctl"),
+    (67, "shmdt"),
+    (68, "msgget"),
+    (69, "msgsnd"),
+    (70, "msgrcv"),
+    (71, "msgctl"),
+    (72, "fcntl"),
+    (73, "flock"),
+    (74, "fsync"),
+    (75, "fdatasync"),
+    (76, "truncate"),
+    (77, "ftruncate"),
+    (78, "getdents"),
+    (79, "getcwd"),
+    (80, "chdir"),
+    (81, "fchdir"),
+    (82, "rename"),
+    (83, "mkdir"),
+    (84, "rmdir"),
+    (85, "creat"),
+    (86, "link"),
+    (87, "unlink"),
+    (88, "symlink"),
+    (89, "readlink"),
+    (90, "chmod"),
+    (91, "fchmod"),
+    (92, "chown"),
+    (93, "fchown"),
+    (94, "lchown"),
+    (95, "umask"),
+    (96, "gettimeofday"),
+    (97, "getrlimit"),
+    (98, "getrusage"),
+    (99, "sysinfo"),
+    (100, "times"),
+    (101, "ptrace"),
+    (102, "getuid"),
+    (103, "syslog"),
+    (104, "getgid"),
+    (105, "setuid"),
+    (106, "setgid"),
+    (107, "geteuid"),
+    (108, "getegid"),
+    (109, "setpgid"),
+    (110, "getppid"),
+    (111, "getpgrp"),
+    (112, "setsid"),
+    (113, "setreuid"),
+    (114, "setregid"),
+    (115, "getgroups"),
+    (116, "setgroups"),
+    (117, "setresuid"),
+    (118, "getresuid"),
+    (119, "setresgid"),
+    (120, "getresgid"),
+    (121, "getpgid"),
+    (122, "setfsuid"),
+    (123, "setfsgid"),
+    (124, "getsid"),
+    (125, "capget"),
+    (126, "capset"),
+    (127, "rt_sigpending"),
+    (128, "rt_sigtimedwait"),
+    (129, "rt_sigqueueinfo"),
+    (130, "rt_sigsuspend"),
+    (131, "sigaltstack"),
+    (132, "utime"),
+    (133, "mknod"),
+    (134, "uselib"),
+    (135, "personality"),
+    (136, "ustat"),
+    (137, "statfs"),
+    (138, "fstatfs"),
+    (139, "sysfs"),
+    (140, "getpriority"),
+    (141, "setpriority"),
+    (142, "sched_setparam"),
+    (143, "sched_getparam"),
+    (144, "sched_setscheduler"),
+    (145, "sched_getscheduler"),
+    (146, "sched_get_priority_max"),
+    (147, "sched_get_priority_min"),
+    (148, "sched_rr_get_interval"),
+    (149, "mlock"),
+    (150, "munlock"),
+    (151, "mlockall"),
+    (152, "munlockall"),
+    (153, "vhangup"),
+    (154, "modify_ldt"),
+    (155, "pivot_root"),
+    (156, "_sysctl"),
+    (157, "prctl"),
+    (158, "arch_prctl"),
+    (159, "adjtimex"),
+    (160, "setrlimit"),
+    (161, "chroot"),
+    (162, "sync"),
+    (163, "acct"),
+    (164, "settimeofday"),
+    (165, "mount"),
+    (166, "umount2"),
+    (167, "swapon"),
+    (168, "swapoff"),
+    (169, "reboot"),
+    (170, "sethostname"),
+    (171, "setdomainname"),
+    (172, "iopl"),
+    (173, "ioperm"),
+    (174, "create_module"),
+    (175, "init_module"),
+    (176, "delete_module"),
+    (177, "get_kernel_syms"),
+    (178, "query_module"),
+    (179, "quotactl"),
+    (180, "nfsservctl"),
+    (181, "getpmsg"),
+    (182, "putpmsg"),
+    (183, "afs_syscall"),
+    (184, "tuxcall"),
+    (185, "security"),
+    (186, "gettid"),
+    (187, "readahead"),
+    (188, "setxattr"),
+    (189, "lsetxattr"),
+    (190, "fsetxattr"),
+    (191, "getxattr"),
+    (192, "lgetxattr"),
+    (193, "fgetxattr"),
+    (194, "listxattr"),
+    (195, "llistxattr"),
+    (196, "flistxattr"),
+    (197, "removexattr"),
+    (198, "lremovexattr"),
+    (199, "fremovexattr"),
+    (200, "tkill"),
+    (201, "time"),
+    (202, "futex"),
+    (203, "sched_setaffinity"),
+    (204, "sched_getaffinity"),
+    (205, "set_thread_area"),
+    (206, "io_setup"),
+    (207, "io_destroy"),
+    (208, "io_getevents"),
+    (209, "io_submit"),
+    (210, "io_cancel"),
+    (211, "get_thread_area"),
+    (212, "lookup_dcookie"),
+    (213, "epoll_create"),
+    (214, "epoll_ctl_old"),
+    (215, "epoll_wait_old"),
+    (216, "remap_file_pages"),
+    (217, "getdents64"),
+    (218, "set_tid_address"),
+    (219, "restart_syscall"),
+    (220, "semtimedop"),
+    (221, "fadvise64"),
+    (222, "timer_create"),
+    (223, "timer_settime"),
+    (224, "timer_gettime"),
+    (225, "timer_getoverrun"),
+    (226, "timer_delete"),
+    (227, "clock_settime"),
+    (228, "clock_gettime"),
+    (229, "clock_getres"),
+    (230, "clock_nanosleep"),
+    (231, "exit_group"),
+    (232, "epoll_wait"),
+    (233, "epoll_ctl"),
+    (234, "tgkill"),
+    (235, "utimes"),
+    (236, "vserver"),
+    (237, "mbind"),
+    (238, "set_mempolicy"),
+    (239, "get_mempolicy"),
+    (240, "mq_open"),
+    (241, "mq_unlink"),
+    (242, "mq_timedsend"),
+    (243, "mq_timedreceive"),
+    (244, "mq_notify"),
+    (245, "mq_getsetattr"),
+    (246, "kexec_load"),
+    (247, "waitid"),
+    (248, "add_key"),
+    (249, "request_key"),
+    (250, "keyctl"),
+    (251, "ioprio_set"),
+    (252, "ioprio_get"),
+    (253, "inotify_init"),
+    (254, "inotify_add_watch"),
+    (255, "inotify_rm_watch"),
+    (256, "migrate_pages"),
+    (257, "openat"),
+    (258, "mkdirat"),
+    (259, "mknodat"),
+    (260, "fchownat"),
+    (261, "futimesat"),
+    (262, "newfstatat"),
+    (263, "unlinkat"),
+    (264, "renameat"),
+    (265, "linkat"),
+    (266, "symlinkat"),
+    (267, "readlinkat"),
+    (268, "fchmodat"),
+    (269, "faccessat"),
+    (270, "pselect6"),
+    (271, "ppoll"),
+    (272, "unshare"),
+    (273, "set_robust_list"),
+    (274, "get_robust_list"),
+    (275, "splice"),
+    (276, "tee"),
+    (277, "sync_file_range"),
+    (278, "vmsplice"),
+    (279, "move_pages"),
+    (280, "utimensat"),
+    (281, "epoll_pwait"),
+    (282, "signalfd"),
+    (283, "timerfd_create"),
+    (284, "eventfd"),
+    (285, "fallocate"),
+    (286, "timerfd_settime"),
+    (287, "timerfd_gettime"),
+    (288, "accept4"),
+    (289, "signalfd4"),
+    (290, "eventfd2"),
+    (291, "epoll_create1"),
+    (292, "dup3"),
+    (293, "pipe2"),
+    (294, "inotify_init1"),
+    (295, "preadv"),
+    (296, "pwritev"),
+    (297, "rt_tgsigqueueinfo"),
+    (298, "perf_event_open"),
+    (299, "recvmmsg"),
+    (300, "fanotify_init"),
+    (301, "fanotify_mark"),
+    (302, "prlimit64"),
+    (303, "name_to_handle_at"),
+    (304, "open_by_handle_at"),
+    (305, "clock_adjtime"),
+    (306, "syncfs"),
+    (307, "sendmmsg"),
+    (308, "setns"),
+    (309, "getcpu"),
+    (310, "process_vm_readv"),
+    (311, "process_vm_writev"),
+    (312, "kcmp"),
+    (313, "finit_module"),
+    (314, "sched_setattr"),
+    (315, "sched_getattr"),
+    (316, "renameat2"),
+    (317, "seccomp"),
+    (318, "getrandom"),
+    (319, "memfd_create"),
+    (320, "kexec_file_load"),
+    (321, "bpf"),
+    (322, "execveat"),
+    (323, "userfaultfd"),
+    (324, "membarrier"),
+    (325, "mlock2"),
+    (326, "copy_file_range"),
+    (327, "preadv2"),
+    (328, "pwritev2"),
+    (329, "pkey_mprotect"),
+    (330, "pkey_alloc"),
+    (331, "pkey_free"),
+    (332, "statx"),
+    (333, "io_pgetevents"),
+    (334, "rseq"),
+    (424, "pidfd_send_signal"),
+    (425, "io_uring_setup"),
+    (426, "io_uring_enter"),
+    (427, "io_uring_register"),
+    (428, "open_tree"),
+    (429, "move_mount"),
+    (430, "fsopen"),
+    (431, "fsconfig"),
+    (432, "fsmount"),
+    (433, "fspick"),
+    (434, "pidfd_open"),
+    (435, "clone3"),
+    (436, "close_range"),
+    (437, "openat2"),
+    (438, "pidfd_getfd"),
+    (439, "faccessat2"),
+    (440, "process_madvise"),
+    (441, "epoll_pwait2"),
+    (442, "mount_setattr"),
+    (443, "quotactl_fd"),
+    (444, "landlock_create_ruleset"),
+    (445, "landlock_add_rule"),
+    (446, "landlock_restrict_self"),
+    (447, "memfd_secret"),
+    (448, "process_mrelease"),
+    (449, "futex_waitv"),
+    (450, "set_mempolicy_home_node"),
+];
+
+/// A stop of the debuggee at the entry or exit of a syscall
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct SyscallEvent {
+    /// Thread ID of the thread that made the syscall
+    #[serde(serialize_with = "ser_pid")]
+    pub tid: Pid,
+    /// Number of the syscall
+    pub number: u64,
+    /// Name of the syscall, if the number is known
+    pub name: Option<&'static str>,
+    /// The six arguments of the syscall, unused arguments contain leftover register values
+    pub args: [u64; 6],
+    /// The return value of the syscall on syscall exit, [`None`] on syscall entry
+    pub ret: Option<i64>,
+}
+
+/// Decides at which syscalls the debuggee stops
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::syscall::SyscallCatcher;
+///
+/// let mut catcher = SyscallCatcher::default();
+/// assert!(!catcher.is_active());
+///
+/// catcher.catch(1); // write
+/// assert!(catcher.is_active());
+/// assert!(catcher.should_stop(1));
+/// assert!(!catcher.should_stop(0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyscallCatcher {
+    trace_all: bool,
+    caught: BTreeSet<u64>,
+}
+
+impl SyscallEvent {
+    /// Decodes a syscall stop from the registers of a thread
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID of the thread that stopped
+    /// * `regs` - The registers of that thread
+    /// * `entry` - Whether the thread stopped at the entry of the syscall
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)] // the return value in rax is signed
+    pub fn from_regs(tid: Pid, regs: &UserRegs, entry: bool) -> Self {
+        Self {
+            tid,
+            number: regs.orig_rax,
+            name: syscall_name(regs.orig_rax),
+            args: [regs.rdi, regs.rsi, regs.rdx, regs.r10, regs.r8, regs.r9],
+            ret: if entry { None } else { Some(regs.rax as i64) },
+        }
+    }
+
+    /// Checks if this is a stop at the entry of the syscall
+    #[inline]
+    #[must_use]
+    pub fn is_entry(&self) -> bool {
+        self.ret.is_none()
+    }
+}
+
+impl SyscallCatcher {
+    /// Checks if the debuggee needs to stop at syscalls at all
+    ///
+    /// If this is `false`, the debuggee can be resumed with `PTRACE_CONT`.
+    #[inline]
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.trace_all || !self.caught.is_empty()
+    }
+
+    /// Checks if the debuggee should stop at the syscall with this number
+    #[inline]
+    #[must_use]
+    pub fn should_stop(&self, number: u64) -> bool {
+        self.trace_all || self.caught.contains(&number)
+    }
+
+    /// Enables or disables stopping at every syscall
+    pub fn set_trace_all(&mut self, trace_all: bool) {
+        self.trace_all = trace_all;
+    }
+
+    /// Adds a catchpoint for the syscall with this number
+    pub fn catch(&mut self, number: u64) {
+        self.caught.insert(number);
+    }
+
+    /// Removes the catchpoint for the syscall with this number
+    ///
+    /// # Returns
+    ///
+    /// * `true` if there was a catchpoint for that syscall
+    /// * `false` otherwise
+    pub fn uncatch(&mut self, number: u64) -> bool {
+        self.caught.remove(&number)
+    }
+}
+
+impl Display for SyscallEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name {
+            Some(name) => write!(f, "[{}] {name}(", self.tid)?,
+            None => write!(f, "[{}] syscall_{}(", self.tid, self.number)?,
+        }
+        for (i, arg) in self.args.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{arg:#x}")?;
+        }
+        write!(f, ")")?;
+        match self.ret {
+            Some(ret) => write!(f, " = {ret}"),
+            None => write!(f, " ..."),
+        }
+    }
+}
+
+/// Checks if a thread stopped at a syscall entry, as opposed to a syscall exit
+///
+/// # Parameters
+///
+/// * `in_syscall` - Whether the last syscall stop of the thread was a syscall entry
+/// * `regs` - The registers of the thread
+#[must_use]
+#[allow(clippy::cast_possible_wrap)] // the return value in rax is signed
+pub fn is_syscall_entry(in_syscall: bool, regs: &user_regs_struct) -> bool {
+    // the kernel sets rax to -ENOSYS on every syscall entry. This alone is not enough, as a
+    // syscall can also return -ENOSYS
+    !in_syscall && regs.rax as i64 == SYSCALL_ENTRY_RAX
+}
+
+/// Gets the name of the syscall with this number
+#[must_use]
+pub fn syscall_name(number: u64) -> Option<&'static str> {
+    SYSCALLS
+        .binary_search_by_key(&number, |(nr, _)| *nr)
+        .ok()
+        .map(|idx| SYSCALLS[idx].1)
+}
+
+/// Gets the number of the syscall with this name
+#[must_use]
+pub fn syscall_number(name: &str) -> Option<u64> {
+    SYSCALLS.iter().find(|(_, n)| *n == name).map(|(nr, _)| *nr)
+}
+
+/// Parses a syscall given by its name or number
+///
+/// # Errors
+///
+/// Fails with [`DebuggerError::UnknownSyscall`] if the string is neither a number nor the name
+/// of a known syscall.
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::syscall::parse_syscall;
+///
+/// assert_eq!(parse_syscall("write").unwrap(), 1);
+/// assert_eq!(parse_syscall("59").unwrap(), 59);
+/// assert!(parse_syscall("not_a_syscall").is_err());
+/// ```
+pub fn parse_syscall(s: &str) -> Result<u64> {
+    let s = s.trim();
+    if let Ok(number) = s.parse::<u64>() {
+        return Ok(number);
+    }
+    syscall_number(&s.to_lowercase()).ok_or_else(|| DebuggerError::UnknownSyscall(s.to_string()))
+}
+
+impl Debuggee {
+    /// Makes a stopped thread of the debuggee execute a syscall
+    ///
+    /// The thread continues as if nothing happened afterwards, its code and registers are
+    /// restored.
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread that executes the syscall
+    /// * `number` - The number of the syscall, see [`SYSCALLS`]
+    /// * `args` - Up to six arguments of the syscall
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - The return value of the syscall
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the syscall returns an error number, or if the code or the
+    /// registers of the thread cannot be read or written.
+    #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)] // rax is signed
+    pub(crate) fn inject_syscall(&self, tid: Pid, number: u64, args: &[u64]) -> Result<u64> {
+        let saved = ptrace::getregs(tid)?;
+        let rip = Addr::from(saved.rip);
+        let code = mem_read_word(tid, rip)?;
+        let mut injected = code.to_le_bytes();
+        injected[..SYSCALL_INSTRUCTION.len()].copy_from_slice(&SYSCALL_INSTRUCTION);
+        mem_write_word(tid, rip, Word::from_le_bytes(injected))?;
+
+        let mut regs = saved;
+        regs.rax = number;
+        // the thread is not in a syscall, the kernel must not restart one
+        regs.orig_rax = u64::MAX;
+        // a hardware breakpoint at the instruction pointer must not trigger
+        regs.eflags |= EFLAGS_RF;
+        let arg_regs = [
+            &mut regs.rdi,
+            &mut regs.rsi,
+            &mut regs.rdx,
+            &mut regs.r10,
+            &mut regs.r8,
+            &mut regs.r9,
+        ];
+        for (reg, arg) in arg_regs.into_iter().zip(args) {
+            *reg = *arg;
+        }
+        trace!("thread {tid} executes syscall {number} with {args:x?}");
+        let ret = ptrace::setregs(tid, regs)
+            .map_err(DebuggerError::from)
+            .and_then(|()| self.step_silently(tid))
+            .and_then(|()| Ok(ptrace::getregs(tid)?.rax));
+
+        mem_write_word(tid, rip, code)?;
+        ptrace::setregs(tid, saved)?;
+        let ret = ret?;
+        if (-MAX_ERRNO..0).contains(&(ret as i64)) {
+            return Err(Errno::from_raw(-(ret as i64) as i32).into());
+        }
+        Ok(ret)
+    }
+
+    /// Steps a thread over one instruction without reporting the step
+    ///
+    /// Signals that arrive for the thread before the instruction is executed are sent to it
+    /// again, they are delivered when the thread is resumed.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the thread cannot be stepped, the instruction raises a `SIGSEGV`
+    /// or `SIGBUS`, or the thread does not stop after the step.
+    pub(crate) fn step_silently(&self, tid: Pid) -> Result<()> {
+        let mut signals = Vec::new();
+        let result = loop {
+            ptrace::step(tid, None)?;
+            match waitpid(tid, Some(WaitPidFlag::__WALL))? {
+                // a trap of the kernel, not a SIGTRAP sent by a process
+                WaitStatus::Stopped(_, Signal::SIGTRAP) if ptrace::getsiginfo(tid)?.si_code > 0 => {
+                    break Ok(());
+                }
+                // the instruction faulted and was not executed, stepping again faults again
+                WaitStatus::Stopped(_, sig @ (Signal::SIGSEGV | Signal::SIGBUS))
+                    if ptrace::getsiginfo(tid)?.si_code > 0 =>
+                {
+                    break Err(DebuggerError::StepFailed(
+                        tid.as_raw(),
+                        format!("the instruction raised {sig}"),
+                    ));
+                }
+                WaitStatus::Stopped(_, sig) => {
+                    debug!("thread {tid} received {sig} during a silent step");
+                    signals.push(sig);
+                }
+                status => {
+                    break Err(DebuggerError::StepFailed(
+                        tid.as_raw(),
+                        format!("{status:?}"),
+                    ))
+                }
+            }
+        };
+        for sig in signals {
+            // SAFETY: tgkill only sends a signal, it does not touch memory
+            Errno::result(unsafe {
+                nix::libc::syscall(
+                    nix::libc::SYS_tgkill,
+                    self.pid.as_raw(),
+                    tid.as_raw(),
+                    sig as i32,
+                )
+            })?;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_syscall_table_is_sorted() {
+        assert!(SYSCALLS.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_syscall_names() {
+        assert_eq!(syscall_name(0), Some("read"));
+        assert_eq!(syscall_name(59), Some("execve"));
+        assert_eq!(syscall_name(435), Some("clone3"));
+        assert_eq!(syscall_name(400), None);
+        assert_eq!(syscall_number("exit_group"), Some(231));
+        assert_eq!(syscall_number("EXIT_GROUP"), None);
+        assert_eq!(parse_syscall("EXIT_GROUP").unwrap(), 231);
+    }
+}
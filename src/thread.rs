@@ -0,0 +1,236 @@
+//! # Thread Module
+//!
+//! Provides the thread table used to debug multi-threaded processes.
+//!
+//! Every thread of the debuggee is traced on its own with [ptrace]. New threads are picked up
+//! automatically with `PTRACE_O_TRACECLONE`. The debugger uses all-stop semantics: whenever one
+//! thread stops for an event, all other threads are stopped too, and all of them are resumed
+//! together when execution continues.
+//!
+//! Register, backtrace and stepping operations act on the selected thread, which is
+//! the thread that caused the last stop unless another thread was selected with
+//! [`Status::SelectThread`](crate::feedback::Status::SelectThread).
+
+use std::path::PathBuf;
+
+use nix::errno::Errno;
+use nix::sys::ptrace;
+use nix::sys::signal::Signal;
+use nix::unistd::Pid;
+use serde::Serialize;
+
+use crate::breakpoint::ser_pid;
+use crate::errors::Result;
+#[cfg(doc)]
+use crate::hwbreakpoint::DebugRegisters;
+use crate::syscall::SyscallEvent;
+use crate::watchpoint::WatchpointHit;
+use crate::Addr;
+
+/// Execution state of a traced thread, as known by the debugger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThreadState {
+    /// The thread was resumed with `PTRACE_CONT`
+    Running,
+    /// The thread was resumed with `PTRACE_SINGLESTEP`
+    Stepping,
+    /// The thread is stopped and waits for the debugger
+    Stopped,
+}
+
+/// A single thread of the debuggee
+///
+/// This is an entry in the thread table of the [`Debuggee`](crate::debuggee::Debuggee).
+#[derive(Debug, Clone)]
+pub struct Thread {
+    /// Thread ID of this thread
+    pub(crate) tid: Pid,
+    /// What the thread is currently doing
+    pub(crate) state: ThreadState,
+    /// A `SIGSTOP` was sent to or is expected from this thread and has not been reported yet
+    pub(crate) expect_sigstop: bool,
+    /// A signal that was received while stopping all threads, delivered on the next resume
+    pub(crate) pending_signal: Option<Signal>,
+    /// A ptrace event (`PTRACE_EVENT_*`) that was reported while stopping all threads, handled
+    /// before the threads are resumed again
+    pub(crate) pending_event: Option<i32>,
+    /// The last syscall stop of this thread was a syscall entry, so the next one is the exit
+    pub(crate) in_syscall: bool,
+    /// A caught syscall stop that was reported while stopping all threads, reported before the
+    /// threads are resumed again
+    pub(crate) pending_syscall: Option<SyscallEvent>,
+    /// A watchpoint hit that was not reported yet, because it happened while stopping all
+    /// threads or while stepping over a breakpoint
+    pub(crate) pending_watchpoint: Option<WatchpointHit>,
+    /// The generation of the [`DebugRegisters`] that was last written into the debug registers
+    /// of this thread
+    pub(crate) debug_registers_generation: u64,
+}
+
+/// Information about a thread of the debuggee, as presented to the user
+///
+/// Returned as a list with [`Feedback::Threads`](crate::feedback::Feedback::Threads).
+#[derive(Debug, Clone, Serialize)]
+pub struct ThreadInfo {
+    /// Thread ID of the thread
+    #[serde(serialize_with = "ser_pid")]
+    pub tid: Pid,
+    /// Name of the thread, as found in `/proc/<pid>/task/<tid>/comm`
+    pub name: Option<String>,
+    /// Current instruction pointer of the thread, if it could be read
+    pub rip: Option<Addr>,
+    /// Whether this is the selected thread
+    pub selected: bool,
+}
+
+impl Thread {
+    /// Creates a new entry for the thread table
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread ID
+    /// * `state` - The state the thread is currently in
+    #[must_use]
+    pub fn new(tid: Pid, state: ThreadState) -> Self {
+        Self {
+            tid,
+            state,
+            expect_sigstop: false,
+            pending_signal: None,
+            pending_event: None,
+            in_syscall: false,
+            pending_syscall: None,
+            pending_watchpoint: None,
+            debug_registers_generation: 0,
+        }
+    }
+
+    /// Gets the thread ID of this thread
+    #[inline]
+    #[must_use]
+    pub fn tid(&self) -> Pid {
+        self.tid
+    }
+
+    /// Gets the state of this thread
+    #[inline]
+    #[must_use]
+    pub fn state(&self) -> ThreadState {
+        self.state
+    }
+
+    /// Checks if the thread was resumed and has not reported a stop yet
+    #[inline]
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.state != ThreadState::Stopped
+    }
+
+    /// Resumes this thread
+    ///
+    /// The thread is resumed with `PTRACE_SYSCALL` if it should stop at the next syscall entry or
+    /// exit, and with `PTRACE_CONT` otherwise.
+    ///
+    /// # Parameters
+    ///
+    /// * `sig` - A signal to deliver to the thread, if any
+    /// * `syscalls` - Whether the thread should stop at syscalls
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the thread cannot be resumed.
+    pub(crate) fn resume(&mut self, sig: Option<Signal>, syscalls: bool) -> Result<()> {
+        if syscalls {
+            ptrace::syscall(self.tid, sig)?;
+        } else {
+            ptrace::cont(self.tid, sig)?;
+            // the exit of a syscall that was already entered will not be reported
+            self.in_syscall = false;
+        }
+        self.state = ThreadState::Running;
+        Ok(())
+    }
+
+    /// Sends a `SIGSTOP` to this thread only, so that it enters a ptrace stop
+    ///
+    /// Does nothing if a `SIGSTOP` is already on the way.
+    ///
+    /// # Parameters
+    ///
+    /// * `pid` - The process ID of the thread group this thread belongs to
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the `tgkill` syscall fails, for example because the thread has
+    /// already exited.
+    pub(crate) fn request_stop(&mut self, pid: Pid) -> Result<()> {
+        if self.expect_sigstop {
+            return Ok(());
+        }
+        // SAFETY: tgkill only takes integers and does not touch our memory
+        let res = unsafe {
+            nix::libc::syscall(
+                nix::libc::SYS_tgkill,
+                pid.as_raw(),
+                self.tid.as_raw(),
+                Signal::SIGSTOP as nix::libc::c_int,
+            )
+        };
+        Errno::result(res)?;
+        self.expect_sigstop = true;
+        Ok(())
+    }
+}
+
+/// Gets the name of a thread from `/proc/<pid>/task/<tid>/comm`
+///
+/// Returns [`None`] if the name cannot be read.
+#[must_use]
+pub fn thread_name(pid: Pid, tid: Pid) -> Option<String> {
+    std::fs::read_to_string(format!("/proc/{pid}/task/{tid}/comm"))
+        .ok()
+        .map(|s| s.trim_end().to_string())
+}
+
+/// Checks if a thread belongs to a process, using `/proc/<pid>/task/<tid>`
+///
+/// This is used to tell new threads of the debuggee apart from new processes it created.
+#[must_use]
+pub fn is_thread_of(pid: Pid, tid: Pid) -> bool {
+    PathBuf::from(format!("/proc/{pid}/task/{tid}")).exists()
+}
+
+/// Lists the thread IDs of all threads of a process from `/proc/<pid>/task`
+///
+/// # Errors
+///
+/// This function fails if the directory cannot be read, for example because the process does not
+/// exist.
+pub fn list_tids(pid: Pid) -> Result<Vec<Pid>> {
+    let mut tids = Vec::new();
+    for entry in std::fs::read_dir(PathBuf::from(format!("/proc/{pid}/task")))? {
+        if let Some(tid) = entry?
+            .file_name()
+            .to_str()
+            .and_then(|s| s.parse::<i32>().ok())
+        {
+            tids.push(Pid::from_raw(tid));
+        }
+    }
+    tids.sort();
+    Ok(tids)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_list_own_threads() {
+        let me = Pid::this();
+        let tids = list_tids(me).expect("could not list threads of own process");
+        assert!(tids.contains(&me));
+        assert!(thread_name(me, me).is_some());
+        assert!(is_thread_of(me, me));
+    }
+}
@@ -74,4 +74,42 @@ pub trait DebuggerUI {
     /// This method can fail if there are issues with user input or other
     /// UI-specific errors.
     fn process(&mut self, feedback: Feedback) -> Result<Status>;
+
+    /// Shows feedback from the debugger without asking for a status command
+    ///
+    /// This is used while the debuggee runs in the background after
+    /// [`Status::ContinueAsync`], for example to show [`Feedback::Running`]. The default
+    /// implementation logs the feedback.
+    ///
+    /// # Parameters
+    ///
+    /// * `feedback` - The feedback from the debugger
+    ///
+    /// # Errors
+    ///
+    /// This method can fail if the feedback cannot be shown.
+    fn notify(&mut self, feedback: Feedback) -> Result<()> {
+        tracing::info!("{feedback}");
+        Ok(())
+    }
+
+    /// Checks for a status command without blocking
+    ///
+    /// This is called over and over while the debuggee runs in the background after
+    /// [`Status::ContinueAsync`], so that the user can send [`Status::Interrupt`]. The default
+    /// implementation never has a status command, so the debuggee runs until it stops by itself.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Status))` - The command to send to the debugger
+    /// * `Ok(None)` - If there is no command right now
+    /// * `Err(DebuggerError)` - If an error occurred during processing
+    ///
+    /// # Errors
+    ///
+    /// This method can fail if there are issues with user input or other
+    /// UI-specific errors.
+    fn poll(&mut self) -> Result<Option<Status>> {
+        Ok(None)
+    }
 }
@@ -20,11 +20,18 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use dialoguer::BasicHistory;
+use nix::sys::signal::{sigaction, SaFlags, SigAction, SigHandler, SigSet, Signal};
 use tracing::{error, info, trace, warn};
 
 use super::{DebuggerUI, Status};
 use crate::errors::Result;
 use crate::feedback::Feedback;
+use crate::launch::LaunchOptions;
+use crate::location::Location;
+use crate::process::FollowForkMode;
+use crate::signal::{parse_signal, SignalPolicy};
+use crate::skiplist::SkipRule;
+use crate::watchpoint::WatchKind;
 use crate::{Addr, Register, Word};
 
 /// Command-line interface for the debugger
@@ -52,11 +59,15 @@ pub struct CliUi {
     history: BasicHistory,
     stepper: usize,
     default_executable: Option<PathBuf>,
+    launch_options: LaunchOptions,
 }
 
 impl CliUi {
     /// Creates a new CLI UI instance
     ///
+    /// This also installs a handler for `SIGINT`, so that Ctrl-C interrupts the debuggee instead
+    /// of killing the debugger.
+    ///
     /// # Parameters
     ///
     /// * `default_executable` - Optional path to a default executable to run
@@ -73,6 +84,7 @@ impl CliUi {
     /// - The path of the executable does not exist
     /// - The path of the executable is not a file
     /// - The path of the executable is not executable
+    /// - The handler for `SIGINT` cannot be installed
     ///
     /// # Examples
     ///
@@ -100,12 +112,22 @@ impl CliUi {
                 return Err(crate::errors::DebuggerError::ExecutableIsNotExecutable);
             }
         }
+        // without SA_RESTART, so that waiting for the debuggee is interrupted
+        let action = SigAction::new(
+            SigHandler::Handler(on_sigint),
+            SaFlags::empty(),
+            SigSet::empty(),
+        );
+        // SAFETY: the handler does nothing, so it is async-signal-safe
+        unsafe { sigaction(Signal::SIGINT, &action) }?;
+
         let ui = CliUi {
             buf_preparsed: Vec::new(),
             buf: String::new(),
             history: BasicHistory::new(),
             stepper: 0,
             default_executable: default_executable.map(std::borrow::ToOwned::to_owned),
+            launch_options: LaunchOptions::default(),
         };
         Ok(ui)
     }
@@ -189,6 +211,35 @@ impl CliUi {
         }
     }
 
+    /// Parses a decimal number from the command line arguments
+    ///
+    /// Used for values that are conventionally given in decimal, like process IDs.
+    ///
+    /// # Parameters
+    ///
+    /// * `index` - The index of the argument to parse
+    ///
+    /// # Returns
+    ///
+    /// * `Some(u64)` - The parsed number
+    /// * `None` - If the number could not be parsed or the index is out of bounds
+    fn get_decimal(&self, index: usize) -> Option<u64> {
+        if index >= self.buf_preparsed.len() {
+            return None;
+        }
+
+        let raw = &self.buf_preparsed[index];
+        trace!("raw decimal number: {raw}");
+
+        match raw.parse::<u64>() {
+            Ok(val) => Some(val),
+            Err(e) => {
+                warn!("Failed to parse decimal number '{}': {}", raw, e);
+                None
+            }
+        }
+    }
+
     fn get_bool(&self, index: usize) -> Option<bool> {
         if index >= self.buf_preparsed.len() {
             return None;
@@ -225,282 +276,654 @@ impl CliUi {
         }
         true
     }
-}
-
-impl DebuggerUI for CliUi {
+    /// Parses the command in the input buffer into a [`Status`]
+    ///
+    /// Commands that only change the [`CliUi`], like `set stepper` or `help`, are handled here.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(Status)` - The status for the command
+    /// * `None` - If the command was invalid or there is no status for it
     #[allow(clippy::pedantic)] // TODO: refactor this function
-    fn process(&mut self, feedback: Feedback) -> crate::errors::Result<Status> {
-        if let Feedback::Error(e) = feedback {
-            error!("{e}");
-        } else if let Feedback::Disassembly(d) = feedback {
-            info!("\n{d}");
-        } else {
-            info!("{feedback}");
-        }
-
-        if self.stepper > 0 {
-            self.stepper -= 1;
-            return Ok(Status::StepSingle);
-        }
+    fn parse_command(&mut self) -> Option<Status> {
+        let cmd = &self.buf_preparsed[0].to_lowercase();
 
-        loop {
-            if let Err(e) = self.get_input() {
-                error!("Error getting input: {}", e);
-                continue;
+        if string_matches(cmd, &["cont", "c"]) {
+            return Some(Status::Continue);
+        } else if string_matches(cmd, &["delbreak", "dbp"]) {
+            if !self.ensure_args("delbreak", 1) {
+                return None;
             }
 
-            if self.buf_preparsed.is_empty() {
-                continue;
+            if let Some(addr_raw) = self.get_number(1) {
+                let addr: Addr = Addr::from(addr_raw as usize);
+                return Some(Status::DelBreakpoint(addr));
+            } else {
+                error!("Invalid address for delbreak");
+                return None;
+            }
+        } else if string_matches(cmd, &["d", "dis"]) {
+            if !self.ensure_args("disassemble", 2) {
+                return None;
             }
 
-            let cmd = &self.buf_preparsed[0].to_lowercase();
+            let addr_raw = if let Some(val) = self.get_number(1) {
+                val as usize
+            } else {
+                error!("Invalid address for disassemble");
+                return None;
+            };
 
-            if string_matches(cmd, &["cont", "c"]) {
-                return Ok(Status::Continue);
-            } else if string_matches(cmd, &["delbreak", "dbp"]) {
-                if !self.ensure_args("delbreak", 1) {
-                    continue;
-                }
+            let len = if let Some(val) = self.get_number(2) {
+                val as usize
+            } else {
+                error!("Invalid length for disassemble");
+                return None;
+            };
 
-                if let Some(addr_raw) = self.get_number(1) {
-                    let addr: Addr = Addr::from(addr_raw as usize);
-                    return Ok(Status::DelBreakpoint(addr));
-                } else {
-                    error!("Invalid address for delbreak");
-                    continue;
-                }
-            } else if string_matches(cmd, &["d", "dis"]) {
-                if !self.ensure_args("disassemble", 2) {
-                    continue;
+            let addr = Addr::from(addr_raw);
+            let literal = self.buf_preparsed.get(3).is_some_and(|s| s == "--literal");
+            return Some(Status::DisassembleAt(addr, len, literal));
+        } else if string_matches(cmd, &["break", "bp"]) {
+            if !self.ensure_args("break", 1) {
+                return None;
+            }
+
+            if let Some((file, line)) = self.buf_preparsed[1].rsplit_once(':') {
+                if let Ok(line) = line.parse() {
+                    return Some(Status::SetBreakpointAtLine(PathBuf::from(file), line));
                 }
+            }
+            // anything else that is not a number is the name of a function
+            if !is_number(&self.buf_preparsed[1]) {
+                return Some(Status::SetBreakpointAtSymbol(self.buf_preparsed[1].clone()));
+            }
+            if let Some(addr_raw) = self.get_number(1) {
+                let addr: Addr = Addr::from(addr_raw as usize);
+                return Some(Status::SetBreakpoint(addr));
+            } else {
+                error!("Invalid address for breakpoint");
+                return None;
+            }
+        } else if string_matches(cmd, &["tbreak", "tbp"]) {
+            if !self.ensure_args("tbreak", 1) {
+                return None;
+            }
 
-                let addr_raw = if let Some(val) = self.get_number(1) {
-                    val as usize
-                } else {
-                    error!("Invalid address for disassemble");
-                    continue;
-                };
+            if let Some(addr_raw) = self.get_number(1) {
+                let addr: Addr = Addr::from(addr_raw as usize);
+                return Some(Status::SetTemporaryBreakpoint(addr));
+            } else {
+                error!("Invalid address for temporary breakpoint");
+                return None;
+            }
+        } else if string_matches(cmd, &["hbreak", "hbp"]) {
+            if !self.ensure_args("hbreak", 1) {
+                return None;
+            }
 
-                let len = if let Some(val) = self.get_number(2) {
-                    val as usize
-                } else {
-                    error!("Invalid length for disassemble");
-                    continue;
-                };
+            if let Some(addr_raw) = self.get_number(1) {
+                let addr: Addr = Addr::from(addr_raw as usize);
+                return Some(Status::SetHardwareBreakpoint(addr));
+            } else {
+                error!("Invalid address for hardware breakpoint");
+                return None;
+            }
+        } else if string_matches(cmd, &["ignore"]) {
+            if !self.ensure_args("ignore", 2) {
+                return None;
+            }
 
-                let addr = Addr::from(addr_raw);
-                let literal = self.buf_preparsed.get(3).is_some_and(|s| s == "--literal");
-                return Ok(Status::DisassembleAt(addr, len, literal));
-            } else if string_matches(cmd, &["break", "bp"]) {
-                if !self.ensure_args("break", 1) {
-                    continue;
-                }
+            let Some(addr_raw) = self.get_number(1) else {
+                error!("Invalid address for ignore");
+                return None;
+            };
+            let Ok(count) = self.buf_preparsed[2].parse() else {
+                error!("Invalid count for ignore");
+                return None;
+            };
+            return Some(Status::SetBreakpointIgnoreCount(
+                Addr::from(addr_raw as usize),
+                count,
+            ));
+        } else if string_matches(cmd, &["watch", "awatch"]) {
+            if !self.ensure_args(cmd, 1) {
+                return None;
+            }
 
-                if let Some(addr_raw) = self.get_number(1) {
-                    let addr: Addr = Addr::from(addr_raw as usize);
-                    return Ok(Status::SetBreakpoint(addr));
-                } else {
-                    error!("Invalid address for breakpoint");
-                    continue;
-                }
-            } else if string_matches(cmd, &["set"]) {
-                if !self.ensure_args("set", 2) {
-                    continue;
-                }
+            let kind = if cmd == "awatch" {
+                WatchKind::ReadWrite
+            } else {
+                WatchKind::Write
+            };
+            if !is_number(&self.buf_preparsed[1]) {
+                return Some(Status::SetWatchpointAtVariable(
+                    self.buf_preparsed[1].clone(),
+                    kind,
+                ));
+            }
+            if !self.ensure_args(cmd, 2) {
+                return None;
+            }
+            let Some(addr_raw) = self.get_number(1) else {
+                error!("Invalid address for watchpoint");
+                return None;
+            };
+            let Ok(len) = self.buf_preparsed[2].parse() else {
+                error!("Invalid length for watchpoint");
+                return None;
+            };
+            return Some(Status::SetWatchpoint(
+                Addr::from(addr_raw as usize),
+                len,
+                kind,
+            ));
+        } else if string_matches(cmd, &["delwatch"]) {
+            if !self.ensure_args("delwatch", 1) {
+                return None;
+            }
 
-                if self.buf_preparsed[1] == "stepper" {
-                    match self.get_number(2) {
-                        Some(steps) => {
-                            self.stepper = steps as usize;
-                        }
-                        None => {
-                            error!("Invalid number for stepper");
+            let Some(addr_raw) = self.get_number(1) else {
+                error!("Invalid address for delwatch");
+                return None;
+            };
+            return Some(Status::DelWatchpoint(Addr::from(addr_raw as usize)));
+        } else if string_matches(cmd, &["commands"]) {
+            if !self.ensure_args("commands", 1) {
+                return None;
+            }
+
+            let Some(addr_raw) = self.get_number(1) else {
+                error!("Invalid address for commands");
+                return None;
+            };
+            info!("Enter one command per line, finish with 'end'");
+            let mut commands = Vec::new();
+            loop {
+                if let Err(e) = self.get_input() {
+                    error!("Error getting input: {}", e);
+                    return None;
+                }
+                match self.buf_preparsed.first().map(String::as_str) {
+                    None => continue,
+                    Some("end") => break,
+                    Some(_) => {
+                        if let Some(status) = self.parse_command() {
+                            commands.push(status);
                         }
                     }
-                } else {
-                    error!("Unknown subcommand for set");
-                }
-                continue;
-            } else if string_matches(cmd, &["sym", "gsym"]) {
-                if !self.ensure_args("symbol", 1) {
-                    continue;
                 }
+            }
+            return Some(Status::SetBreakpointCommands(
+                Addr::from(addr_raw as usize),
+                commands,
+            ));
+        } else if string_matches(cmd, &["cond", "condition"]) {
+            if !self.ensure_args("condition", 1) {
+                return None;
+            }
 
-                let symbol_name: String = self.buf_preparsed[1].to_string();
-                return Ok(Status::GetSymbolsByName(symbol_name));
-            } else if string_matches(cmd, &["var"]) {
-                if !self.ensure_args("var", 1) {
-                    continue;
+            let Some(addr_raw) = self.get_number(1) else {
+                error!("Invalid address for condition");
+                return None;
+            };
+            let addr = Addr::from(addr_raw as usize);
+            if self.buf_preparsed.len() == 2 {
+                return Some(Status::SetBreakpointCondition(addr, None));
+            }
+            match self.buf_preparsed[2..].join(" ").parse() {
+                Ok(condition) => {
+                    return Some(Status::SetBreakpointCondition(addr, Some(condition)));
                 }
-
-                let symbol_name: String = self.buf_preparsed[1].to_string();
-                return Ok(Status::ReadVariable(symbol_name));
-            } else if string_matches(cmd, &["vars"]) {
-                if !self.ensure_args("vars", 2) {
-                    continue;
+                Err(e) => {
+                    error!("Invalid condition: {e}");
+                    return None;
                 }
+            }
+        } else if string_matches(cmd, &["set"]) {
+            if !self.ensure_args("set", 2) {
+                return None;
+            }
 
-                let symbol_name: String = self.buf_preparsed[1].to_string();
-
-                if let Some(value) = self.get_number(2) {
-                    return Ok(Status::WriteVariable(symbol_name, value as usize));
-                } else {
-                    error!("Invalid value for variable");
-                    continue;
-                }
-            } else if string_matches(cmd, &["run"]) {
-                if self.buf_preparsed.len() == 1 && self.default_executable.is_some() {
-                    // safe because we just checked that its some
-                    let default_executable = self.default_executable.as_ref().unwrap();
-                    return Ok(Status::Run(
-                        default_executable.into(),
-                        vec![path_to_cstring_or_empty(default_executable)],
-                    ));
-                }
-                if !self.ensure_args("run", 1) {
-                    info!("For the run command, you can set a default executable when you launch the coreminer");
-                    continue;
+            if self.buf_preparsed[1] == "stepper" {
+                match self.get_number(2) {
+                    Some(steps) => {
+                        self.stepper = steps as usize;
+                    }
+                    None => {
+                        error!("Invalid number for stepper");
+                    }
                 }
-
-                let executable: PathBuf = PathBuf::from(self.buf_preparsed[1].clone());
-                let actual_args: Vec<CString> = if self.buf_preparsed.len() > 2 {
-                    let mut buf = Vec::new();
-
-                    for s in &self.buf_preparsed[2..] {
-                        buf.push(match CString::new(s.as_str()) {
-                            Ok(s) => s,
-                            Err(e) => {
-                                error!("could not make '{s}' into CString: {e}");
-                                continue;
-                            }
-                        })
+            } else if self.buf_preparsed[1] == "follow-fork" {
+                match FollowForkMode::from_str(&self.buf_preparsed[2]) {
+                    Ok(mode) => return Some(Status::SetFollowFork(mode)),
+                    Err(e) => {
+                        error!("Invalid follow-fork mode: {}", e);
                     }
-                    buf
-                } else {
-                    Vec::new()
-                };
-                return Ok(Status::Run(executable, actual_args));
-            } else if string_matches(cmd, &["bt"]) {
-                return Ok(Status::Backtrace);
-            } else if string_matches(cmd, &["so"]) {
-                return Ok(Status::StepOut);
-            } else if string_matches(cmd, &["su", "sov"]) {
-                return Ok(Status::StepOver);
-            } else if string_matches(cmd, &["si"]) {
-                return Ok(Status::StepInto);
-            } else if string_matches(cmd, &["s", "step"]) {
-                return Ok(Status::StepSingle);
-            } else if string_matches(cmd, &["info"]) {
-                return Ok(Status::Infos);
-            } else if string_matches(cmd, &["stack"]) {
-                return Ok(Status::GetStack);
-            } else if string_matches(cmd, &["pm"]) {
-                return Ok(Status::ProcMap);
-            } else if string_matches(cmd, &["rmem"]) {
-                if !self.ensure_args("rmem", 1) {
-                    continue;
                 }
-
-                if let Some(addr_raw) = self.get_number(1) {
-                    let addr: Addr = Addr::from(addr_raw as usize);
-                    return Ok(Status::ReadMem(addr));
-                } else {
-                    error!("Invalid address for rmem");
-                    continue;
+            } else if self.buf_preparsed[1] == "env" {
+                match self.buf_preparsed[2].split_once('=') {
+                    Some((name, value)) => self.launch_options.set_env(name, value),
+                    None => error!("Invalid environment variable, use NAME=VALUE"),
                 }
-            } else if string_matches(cmd, &["wmem"]) {
-                if !self.ensure_args("wmem", 2) {
-                    continue;
+            } else if self.buf_preparsed[1] == "cwd" {
+                self.launch_options.cwd = Some(PathBuf::from(&self.buf_preparsed[2]));
+            } else if self.buf_preparsed[1] == "stdin" {
+                self.launch_options.stdin = Some(PathBuf::from(&self.buf_preparsed[2]));
+            } else if self.buf_preparsed[1] == "stdout" {
+                self.launch_options.stdout = Some(PathBuf::from(&self.buf_preparsed[2]));
+            } else if self.buf_preparsed[1] == "stderr" {
+                self.launch_options.stderr = Some(PathBuf::from(&self.buf_preparsed[2]));
+            } else if self.buf_preparsed[1] == "aslr" {
+                match self.get_bool(2) {
+                    Some(aslr) => self.launch_options.disable_aslr = !aslr,
+                    None => error!("Invalid status for aslr"),
                 }
+            } else {
+                error!("Unknown subcommand for set");
+            }
+            return None;
+        } else if string_matches(cmd, &["unset"]) {
+            if !self.ensure_args("unset", 1) {
+                return None;
+            }
 
-                let addr_raw = if let Some(val) = self.get_number(1) {
-                    val as usize
-                } else {
-                    error!("Invalid address for wmem");
-                    continue;
-                };
+            match self.buf_preparsed[1].as_str() {
+                "env" => match self.buf_preparsed.get(2) {
+                    Some(name) => self.launch_options.unset_env(name.as_str()),
+                    None => self.launch_options.clear_env(),
+                },
+                "cwd" => self.launch_options.cwd = None,
+                "stdin" => self.launch_options.stdin = None,
+                "stdout" => self.launch_options.stdout = None,
+                "stderr" => self.launch_options.stderr = None,
+                _ => error!("Unknown subcommand for unset"),
+            }
+            return None;
+        } else if string_matches(cmd, &["sym", "gsym"]) {
+            if !self.ensure_args("symbol", 1) {
+                return None;
+            }
 
-                let value = if let Some(val) = self.get_number(2) {
-                    val as Word
-                } else {
-                    error!("Invalid value for wmem");
-                    continue;
-                };
+            let symbol_name: String = self.buf_preparsed[1].to_string();
+            return Some(Status::GetSymbolsByName(symbol_name));
+        } else if string_matches(cmd, &["var"]) {
+            if !self.ensure_args("var", 1) {
+                return None;
+            }
 
-                let addr: Addr = Addr::from(addr_raw);
-                return Ok(Status::WriteMem(addr, value));
-            } else if string_matches(cmd, &["regs"]) {
-                if !self.ensure_args("regs", 1) {
-                    continue;
-                }
+            let symbol_name: String = self.buf_preparsed[1].to_string();
+            return Some(Status::ReadVariable(symbol_name));
+        } else if string_matches(cmd, &["vars"]) {
+            if !self.ensure_args("vars", 2) {
+                return None;
+            }
 
-                if self.buf_preparsed[1] == "get" {
-                    return Ok(Status::DumpRegisters);
-                } else if self.buf_preparsed[1] == "set" {
-                    if !self.ensure_args("regs set", 3) {
-                        continue;
-                    }
+            let symbol_name: String = self.buf_preparsed[1].to_string();
 
-                    match Register::from_str(&self.buf_preparsed[2]) {
-                        Ok(register) => {
-                            if let Some(value) = self.get_number(3) {
-                                return Ok(Status::SetRegister(register, value));
-                            } else {
-                                error!("Invalid value for register");
-                                continue;
-                            }
-                        }
+            if let Some(value) = self.get_number(2) {
+                return Some(Status::WriteVariable(symbol_name, value as usize));
+            } else {
+                error!("Invalid value for variable");
+                return None;
+            }
+        } else if string_matches(cmd, &["run"]) {
+            if let (1, Some(default_executable)) =
+                (self.buf_preparsed.len(), self.default_executable.as_ref())
+            {
+                return Some(Status::Run(
+                    default_executable.into(),
+                    vec![path_to_cstring_or_empty(default_executable)],
+                    self.launch_options.clone(),
+                ));
+            }
+            if !self.ensure_args("run", 1) {
+                info!("For the run command, you can set a default executable when you launch the coreminer");
+                return None;
+            }
+
+            let executable: PathBuf = PathBuf::from(self.buf_preparsed[1].clone());
+            let actual_args: Vec<CString> = if self.buf_preparsed.len() > 2 {
+                let mut buf = Vec::new();
+
+                for s in &self.buf_preparsed[2..] {
+                    buf.push(match CString::new(s.as_str()) {
+                        Ok(s) => s,
                         Err(e) => {
-                            error!("Invalid register: {}", e);
+                            error!("could not make '{s}' into CString: {e}");
                             continue;
                         }
-                    }
-                } else {
-                    error!("Only 'set' and 'get' are valid subcommands for 'regs'");
+                    })
                 }
-                continue;
-            } else if string_matches(cmd, &["plugin"]) {
-                #[cfg(not(feature = "plugins"))]
-                {
-                    error!("this version of the coreminer has not been built with plugin support");
-                    continue;
+                buf
+            } else {
+                Vec::new()
+            };
+            return Some(Status::Run(
+                executable,
+                actual_args,
+                self.launch_options.clone(),
+            ));
+        } else if string_matches(cmd, &["restart"]) {
+            return Some(Status::Restart);
+        } else if string_matches(cmd, &["attach"]) {
+            if !self.ensure_args("attach", 1) {
+                return None;
+            }
+
+            if let Some(pid) = self.get_decimal(1) {
+                return Some(Status::Attach(pid as i32));
+            } else {
+                error!("Invalid process id for attach");
+                return None;
+            }
+        } else if string_matches(cmd, &["detach"]) {
+            return Some(Status::Detach);
+        } else if string_matches(cmd, &["core"]) {
+            if !self.ensure_args("core", 1) {
+                return None;
+            }
+
+            let core = PathBuf::from(&self.buf_preparsed[1]);
+            let executable = self.buf_preparsed.get(2).map(PathBuf::from);
+            return Some(Status::LoadCore(core, executable));
+        } else if string_matches(cmd, &["gcore"]) {
+            if !self.ensure_args("gcore", 1) {
+                return None;
+            }
+
+            return Some(Status::GenerateCore(PathBuf::from(&self.buf_preparsed[1])));
+        } else if string_matches(cmd, &["session"]) {
+            if !self.ensure_args("session", 2) {
+                return None;
+            }
+
+            let path = PathBuf::from(&self.buf_preparsed[2]);
+            match self.buf_preparsed[1].as_str() {
+                "save" => return Some(Status::SaveSession(path)),
+                "load" => return Some(Status::LoadSession(path)),
+                _ => error!("Only 'save' and 'load' are valid subcommands for 'session'"),
+            }
+            return None;
+        } else if string_matches(cmd, &["threads"]) {
+            return Some(Status::ListThreads);
+        } else if string_matches(cmd, &["thread"]) {
+            if !self.ensure_args("thread", 1) {
+                return None;
+            }
+
+            if let Some(tid) = self.get_decimal(1) {
+                return Some(Status::SelectThread(tid as i32));
+            } else {
+                error!("Invalid thread id for thread");
+                return None;
+            }
+        } else if string_matches(cmd, &["processes"]) {
+            return Some(Status::ListProcesses);
+        } else if string_matches(cmd, &["process"]) {
+            if !self.ensure_args("process", 1) {
+                return None;
+            }
+
+            if let Some(pid) = self.get_decimal(1) {
+                return Some(Status::SelectProcess(pid as i32));
+            } else {
+                error!("Invalid process id for process");
+                return None;
+            }
+        } else if string_matches(cmd, &["strace"]) {
+            if !self.ensure_args("strace", 1) {
+                return None;
+            }
+
+            if let Some(enable) = self.get_bool(1) {
+                return Some(Status::TraceSyscalls(enable));
+            } else {
+                error!("Invalid status for strace");
+                return None;
+            }
+        } else if string_matches(cmd, &["catch"]) {
+            if !self.ensure_args("catch", 1) {
+                return None;
+            }
+
+            return Some(Status::CatchSyscall(self.buf_preparsed[1].to_string()));
+        } else if string_matches(cmd, &["dcatch", "delcatch"]) {
+            if !self.ensure_args("dcatch", 1) {
+                return None;
+            }
+
+            return Some(Status::DelCatchSyscall(self.buf_preparsed[1].to_string()));
+        } else if string_matches(cmd, &["handle"]) {
+            if !self.ensure_args("handle", 4) {
+                return None;
+            }
+
+            let sig = match parse_signal(&self.buf_preparsed[1]) {
+                Ok(sig) => sig,
+                Err(e) => {
+                    error!("Invalid signal for handle: {}", e);
+                    return None;
                 }
-                if self.buf_preparsed.len() < 2 {
-                    unimplemented!()
-                    // return Ok(Status::PluginGetAll);
+            };
+            if let (Some(stop), Some(print), Some(pass)) =
+                (self.get_bool(2), self.get_bool(3), self.get_bool(4))
+            {
+                return Some(Status::SetSignalPolicy(
+                    sig as i32,
+                    SignalPolicy::new(stop, print, pass),
+                ));
+            } else {
+                error!("Invalid policy for handle");
+                return None;
+            }
+        } else if string_matches(cmd, &["signals"]) {
+            return Some(Status::ListSignalPolicies);
+        } else if string_matches(cmd, &["skip", "dskip", "delskip"]) {
+            if !self.ensure_args(cmd, 2) {
+                return None;
+            }
+
+            let Some(rule) = skip_rule(&self.buf_preparsed[1], &self.buf_preparsed[2]) else {
+                error!("Invalid kind for {cmd}, use function, cu, file or lib");
+                return None;
+            };
+            if cmd == "skip" {
+                return Some(Status::AddSkip(rule));
+            } else {
+                return Some(Status::DelSkip(rule));
+            }
+        } else if string_matches(cmd, &["skips"]) {
+            return Some(Status::ListSkips);
+        } else if string_matches(cmd, &["bt"]) {
+            return Some(Status::Backtrace);
+        } else if string_matches(cmd, &["so"]) {
+            return Some(Status::StepOut);
+        } else if string_matches(cmd, &["fin", "finish"]) {
+            return Some(Status::Finish);
+        } else if string_matches(cmd, &["adv", "advance"]) {
+            let Some(arg) = self.buf_preparsed.get(1) else {
+                return Some(Status::RunUntil(Location::Caller));
+            };
+
+            if let Some((file, line)) = arg.rsplit_once(':') {
+                if let Ok(line) = line.parse() {
+                    return Some(Status::RunUntil(Location::Line(PathBuf::from(file), line)));
                 }
-                let plugin_id: steckrs::PluginID = self.buf_preparsed[1].clone().leak();
-
-                if self.buf_preparsed.len() == 3 {
-                    if let Some(status) = self.get_bool(2) {
-                        return Ok(Status::PluginSetEnable(plugin_id.into(), status));
-                    } else {
-                        error!("Invalid address for delbreak");
-                        continue;
+            }
+            // anything else that is not a number is the name of a function
+            if !is_number(arg) {
+                return Some(Status::RunUntil(Location::Function(arg.clone())));
+            }
+            if let Some(addr_raw) = self.get_number(1) {
+                let addr: Addr = Addr::from(addr_raw as usize);
+                return Some(Status::RunUntil(Location::Addr(addr)));
+            } else {
+                error!("Invalid address for advance");
+                return None;
+            }
+        } else if string_matches(cmd, &["su", "sov"]) {
+            return Some(Status::StepOver);
+        } else if string_matches(cmd, &["si"]) {
+            return Some(Status::StepInto);
+        } else if string_matches(cmd, &["n", "next"]) {
+            return Some(Status::NextLine);
+        } else if string_matches(cmd, &["sl", "stepline"]) {
+            return Some(Status::StepLine);
+        } else if string_matches(cmd, &["s", "step"]) {
+            return Some(Status::StepSingle);
+        } else if string_matches(cmd, &["info", "bps"]) {
+            return Some(Status::ListBreakpoints);
+        } else if string_matches(cmd, &["watches"]) {
+            return Some(Status::ListWatchpoints);
+        } else if string_matches(cmd, &["libs"]) {
+            return Some(Status::ListLibraries);
+        } else if string_matches(cmd, &["stack"]) {
+            return Some(Status::GetStack);
+        } else if string_matches(cmd, &["pm"]) {
+            return Some(Status::ProcMap);
+        } else if string_matches(cmd, &["rmem"]) {
+            if !self.ensure_args("rmem", 1) {
+                return None;
+            }
+
+            if let Some(addr_raw) = self.get_number(1) {
+                let addr: Addr = Addr::from(addr_raw as usize);
+                return Some(Status::ReadMem(addr));
+            } else {
+                error!("Invalid address for rmem");
+                return None;
+            }
+        } else if string_matches(cmd, &["wmem"]) {
+            if !self.ensure_args("wmem", 2) {
+                return None;
+            }
+
+            let addr_raw = if let Some(val) = self.get_number(1) {
+                val as usize
+            } else {
+                error!("Invalid address for wmem");
+                return None;
+            };
+
+            let value = if let Some(val) = self.get_number(2) {
+                val as Word
+            } else {
+                error!("Invalid value for wmem");
+                return None;
+            };
+
+            let addr: Addr = Addr::from(addr_raw);
+            return Some(Status::WriteMem(addr, value));
+        } else if string_matches(cmd, &["regs"]) {
+            if !self.ensure_args("regs", 1) {
+                return None;
+            }
+
+            if self.buf_preparsed[1] == "get" {
+                return Some(Status::DumpRegisters);
+            } else if self.buf_preparsed[1] == "set" {
+                if !self.ensure_args("regs set", 3) {
+                    return None;
+                }
+
+                match Register::from_str(&self.buf_preparsed[2]) {
+                    Ok(register) => {
+                        if let Some(value) = self.get_number(3) {
+                            return Some(Status::SetRegister(register, value));
+                        } else {
+                            error!("Invalid value for register");
+                            return None;
+                        }
+                    }
+                    Err(e) => {
+                        error!("Invalid register: {}", e);
+                        return None;
                     }
-                } else {
-                    return Ok(Status::PluginGetStatus(plugin_id.into()));
                 }
-            } else if string_matches(cmd, &["plugins"]) {
-                #[cfg(not(feature = "plugins"))]
-                {
-                    error!("this version of the coreminer has not been built with plugin support");
-                    continue;
+            } else {
+                error!("Only 'set' and 'get' are valid subcommands for 'regs'");
+            }
+            return None;
+        } else if string_matches(cmd, &["plugin"]) {
+            #[cfg(not(feature = "plugins"))]
+            {
+                error!("this version of the coreminer has not been built with plugin support");
+                return None;
+            }
+            if self.buf_preparsed.len() < 2 {
+                unimplemented!()
+                // return Some(Status::PluginGetAll);
+            }
+            let plugin_id: steckrs::PluginID = self.buf_preparsed[1].clone().leak();
+
+            if self.buf_preparsed.len() == 3 {
+                if let Some(status) = self.get_bool(2) {
+                    return Some(Status::PluginSetEnable(plugin_id.into(), status));
+                } else {
+                    error!("Invalid address for delbreak");
+                    return None;
                 }
-                return Ok(Status::PluginGetList);
-            } else if string_matches(cmd, &["help", "h", "?"]) {
-                show_help();
-                continue;
-            } else if string_matches(cmd, &["q", "quit", "exit"]) {
-                return Ok(Status::DebuggerQuit);
             } else {
-                error!("Unknown command: {}", cmd);
-                info!("Type 'help' for available commands");
+                return Some(Status::PluginGetStatus(plugin_id.into()));
             }
+        } else if string_matches(cmd, &["plugins"]) {
+            #[cfg(not(feature = "plugins"))]
+            {
+                error!("this version of the coreminer has not been built with plugin support");
+                return None;
+            }
+            return Some(Status::PluginGetList);
+        } else if string_matches(cmd, &["help", "h", "?"]) {
+            show_help();
+            return None;
+        } else if string_matches(cmd, &["q", "quit", "exit"]) {
+            return Some(Status::DebuggerQuit);
+        } else {
+            error!("Unknown command: {}", cmd);
+            info!("Type 'help' for available commands");
         }
+        None
     }
 }
 
+impl DebuggerUI for CliUi {
+    fn process(&mut self, feedback: Feedback) -> crate::errors::Result<Status> {
+        if let Feedback::Error(e) = feedback {
+            error!("{e}");
+        } else if let Feedback::Disassembly(d) = feedback {
+            info!("\n{d}");
+        } else {
+            info!("{feedback}");
+        }
+
+        if self.stepper > 0 {
+            self.stepper -= 1;
+            return Ok(Status::StepSingle);
+        }
+
+        loop {
+            if let Err(e) = self.get_input() {
+                error!("Error getting input: {}", e);
+                continue;
+            }
+
+            if self.buf_preparsed.is_empty() {
+                continue;
+            }
+
+            if let Some(status) = self.parse_command() {
+                return Ok(status);
+            }
+        }
+    }
+}
+
+/// Handler for `SIGINT`
+///
+/// It does nothing, receiving the signal is enough to interrupt waiting for the debuggee.
+extern "C" fn on_sigint(_: nix::libc::c_int) {}
+
 /// Checks if a command matches any of the provided prefixes
 ///
 /// # Parameters
@@ -513,7 +936,35 @@ impl DebuggerUI for CliUi {
 /// * `true` - If the command matches any prefix
 /// * `false` - If the command does not match any prefix
 fn string_matches(cmd: &str, prefixes: &[&str]) -> bool {
-    prefixes.iter().any(|a| cmd == *a)
+    prefixes.contains(&cmd)
+}
+
+/// Checks if an argument is a hexadecimal number, with or without the `0x` prefix
+///
+/// Without the prefix, it must start with a decimal digit. Otherwise names made of hex letters,
+/// like `add` or `face`, would be taken for addresses. No C or Rust identifier starts with a
+/// digit.
+fn is_number(arg: &str) -> bool {
+    let digits = match arg.strip_prefix("0x") {
+        Some(digits) => digits,
+        None if arg.starts_with(|c: char| c.is_ascii_digit()) => arg,
+        None => return false,
+    };
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Makes a rule for the skip list from its kind and its pattern
+///
+/// The kinds are `function` and `cu` for globs, `file` for a source path prefix and `lib` for
+/// a shared library.
+fn skip_rule(kind: &str, pattern: &str) -> Option<SkipRule> {
+    match kind {
+        "function" => Some(SkipRule::Function(pattern.to_string())),
+        "cu" => Some(SkipRule::CompileUnit(pattern.to_string())),
+        "file" => Some(SkipRule::SourcePrefix(PathBuf::from(pattern))),
+        "lib" => Some(SkipRule::Library(PathBuf::from(pattern))),
+        _ => None,
+    }
 }
 
 /// Shows help information for the debugger commands
@@ -524,17 +975,55 @@ fn show_help() {
     concat!(
     "\nCoreminer Debugger Help:\n",
     "\n  run PATH:str [ARGS:str ...]             - Run program at PATH with optional arguments",
+    "\n  restart                                 - Run the last program again, keeping breakpoints",
+    "\n  attach PID:int                          - Attach to the running process with PID",
+    "\n  detach                                  - Detach from the debuggee and let it continue",
+    "\n  core PATH:str [EXE:str]                 - Inspect the core file at PATH of a crashed EXE",
+    "\n  gcore PATH:str                          - Write a core file of the debuggee to PATH",
+    "\n  session save PATH:str                   - Save program, breakpoints and settings to PATH",
+    "\n  session load PATH:str                   - Restore the session at PATH and run its program",
     "\n  c, cont                                 - Continue execution",
     "\n  s, step                                 - Step one instruction",
     "\n  si                                      - Step into function call",
     "\n  su, sov                                 - Step over function call",
     "\n  so                                      - Step out of current function",
+    "\n  fin, finish                             - Step out of current function and show its return value",
+    "\n  adv, advance                            - Run until the current function returns to its caller",
+    "\n  adv, advance ADDR|FUNCTION|FILE:LINE    - Run to an address, function or source line, without a breakpoint",
+    "\n  n, next                                 - Step to the next source line, over calls",
+    "\n  sl, stepline                            - Step to the next source line, into calls",
     "\n  bp, break ADDR:num                      - Set breakpoint at address (hex)",
+    "\n  bp, break FUNCTION:str                  - Set breakpoint at FUNCTION, pending until its library is loaded",
+    "\n  bp, break FILE:str:LINE:int             - Set breakpoints at LINE in the source FILE",
     "\n  dbp, delbreak ADDR:num                  - Delete breakpoint at address (hex)",
+    "\n  tbp, tbreak ADDR:num                    - Set breakpoint at address (hex) that is removed when hit",
+    "\n  hbp, hbreak ADDR:num                    - Set breakpoint at address (hex) in a debug register",
+    "\n  cond ADDR:num [LHS OP RHS]              - Only stop at the breakpoint if the condition is true",
+    "\n  ignore ADDR:num COUNT:int               - Ignore the next COUNT hits of the breakpoint",
+    "\n  commands ADDR:num                       - Read commands until 'end', run them and continue when hit",
+    "\n  bps, info                               - List all breakpoints with their hit counts",
+    "\n  watch ADDR:num LEN:int                  - Stop when LEN bytes at ADDR are written",
+    "\n  watch VAR:str                           - Stop when the variable VAR is written",
+    "\n  awatch ADDR:num LEN:int                 - Stop when LEN bytes at ADDR are read or written",
+    "\n  awatch VAR:str                          - Stop when the variable VAR is read or written",
+    "\n  delwatch ADDR:num                       - Delete the watchpoint at address (hex)",
+    "\n  watches                                 - List all watchpoints with their last values",
+    "\n  libs                                    - List the shared libraries loaded by the debuggee",
     "\n  d, dis ADDR:num LEN:num [--literal]     - Disassemble LEN bytes at ADDR",
     "\n  bt                                      - Show backtrace",
+    "\n  threads                                 - List all threads of the debuggee",
+    "\n  thread TID:int                          - Select the thread with TID for regs, bt and stepping",
+    "\n  processes                               - List all processes under the control of the debugger",
+    "\n  process PID:int                         - Select the held process with PID as the debuggee",
+    "\n  strace STATUS:bool                      - Stop at every syscall or stop doing so",
+    "\n  catch SYSCALL:str                       - Stop at the syscall with this name or number",
+    "\n  dcatch, delcatch SYSCALL:str            - Stop no longer at the syscall",
+    "\n  handle SIG:str STOP:bool PRINT:bool PASS:bool - Set if a signal stops, is logged and is passed",
+    "\n  signals                                 - Show the policies of all signals",
+    "\n  skip KIND:str PATTERN:str               - Do not step into code of a function, cu, file or lib",
+    "\n  dskip, delskip KIND:str PATTERN:str     - Remove a rule of the skip list",
+    "\n  skips                                   - List the rules of the skip list",
     "\n  stack                                   - Show stack",
-    "\n  info                                    - Show debugger info",
     "\n  pm                                      - Show process memory map",
     "\n  regs get                                - Show register values",
     "\n  regs set REG:str VAL:num                - Set register REG to value VAL (hex)",
@@ -544,6 +1033,13 @@ fn show_help() {
     "\n  var NAME:str                            - Read variable value",
     "\n  vars NAME:str VAL:num                   - Write value to variable",
     "\n  set stepper N                           - Set stepper to auto-step N times",
+    "\n  set follow-fork MODE:str                - Follow the 'parent', 'child' or 'both' on fork",
+    "\n  set env NAME=VALUE:str                  - Set an environment variable for the next run",
+    "\n  unset env [NAME:str]                    - Remove a variable, or the whole environment, for the next run",
+    "\n  set cwd DIR:str                         - Set the working directory for the next run",
+    "\n  set stdin|stdout|stderr FILE:str        - Redirect a standard stream to a file for the next run",
+    "\n  unset cwd|stdin|stdout|stderr           - Inherit this from the debugger again",
+    "\n  set aslr STATUS:bool                    - Enable or disable address space randomization for the next run",
     "\n  q, quit, exit                           - Exit the debugger",
     "\n  plugin ID:str [STATUS:bool]             - Show the status of a plugin or enable/disable it",
     "\n  plugins                                 - Get a list of all loaded plugins",
@@ -551,6 +1047,7 @@ fn show_help() {
     "\n\nAddresses and values should be in hexadecimal (with or without 0x prefix)",
     "\n\nInput Types:",
     "\n  FOO:num is a positive whole number in hexadecimal (optional 0x prefix)",
+    "\n  FOO:int is a positive whole number in decimal",
     "\n  FOO:str is a string",
     "\n  FOO:bool either of 'true', 'false', '1', or '0'",
     ));
@@ -581,6 +1078,18 @@ mod test {
         assert!(!string_matches("hello", &["help", "h", "?"]));
     }
 
+    #[test]
+    fn test_is_number() {
+        assert!(is_number("0x55555555513c"));
+        assert!(is_number("1f"));
+        assert!(is_number("0x00abc"));
+        for name in [
+            "add", "face", "dead", "cafe", "a", "f", "ab", "0x", "main", "1g",
+        ] {
+            assert!(!is_number(name), "{name} is not a number");
+        }
+    }
+
     #[test]
     fn test_get_number() {
         let mut ui = CliUi {
@@ -594,9 +1103,12 @@ mod test {
             history: BasicHistory::new(),
             stepper: 0,
             default_executable: None,
+            launch_options: LaunchOptions::default(),
         };
 
         assert_eq!(ui.get_number(1), Some(0x19));
+        assert_eq!(ui.get_decimal(1), Some(19));
+        assert_eq!(ui.get_decimal(2), None);
         assert_eq!(ui.get_number(2), Some(0x19));
         assert_eq!(ui.get_number(3), Some(0x19));
         assert_eq!(ui.get_number(4), None); // Out of bounds
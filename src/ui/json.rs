@@ -10,10 +10,14 @@
 //! - Writing JSON-formatted feedback to stdout
 //! - Supporting the same debugging operations as the CLI interface
 //!
+//! Stdin is read by a separate thread, so that commands like [`Status::Interrupt`] can be
+//! received while the debuggee runs in the background.
+//!
 //! This interface enables automation and integration with external tools
 //! that can communicate via JSON.
 
 use std::io::{BufRead, BufReader};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -72,11 +76,15 @@ pub struct Input {
 /// // Process feedback from the debugger with user input
 /// let status = ui.process(Feedback::Ok).unwrap();
 /// ```
-pub struct JsonUI;
+pub struct JsonUI {
+    lines: Receiver<Vec<u8>>,
+}
 
 impl JsonUI {
     /// Creates a new JSON UI instance
     ///
+    /// This starts a thread that reads the lines from stdin.
+    ///
     /// # Returns
     ///
     /// * `Ok(JsonUI)` - A new JSON UI instance
@@ -94,7 +102,27 @@ impl JsonUI {
     ///
     /// Never fails.
     pub fn build() -> Result<Self> {
-        Ok(JsonUI)
+        let (sender, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut reader = BufReader::new(std::io::stdin());
+            loop {
+                let mut buf = Vec::new();
+                match reader.read_until(b'\n', &mut buf) {
+                    Ok(0) => break,
+                    Ok(_) => {
+                        if sender.send(buf).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        error!("could not read from stdin: {e}");
+                        break;
+                    }
+                }
+            }
+            trace!("stopped reading from stdin");
+        });
+        Ok(JsonUI { lines })
     }
 
     /// Formats feedback as a JSON value
@@ -125,26 +153,57 @@ impl JsonUI {
     pub fn format_feedback(feedback: &Feedback) -> Result<serde_json::Value> {
         Ok(json!({ "feedback": feedback }))
     }
+
+    /// Parses a line from stdin as [`Input`]
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the line is not a valid [`Input`].
+    fn parse_input(buf: &[u8]) -> Result<Status> {
+        let input: Input = serde_json::from_slice(buf)?;
+        trace!("got input: {input:?}");
+        Ok(input.status)
+    }
+}
+
+/// The error for when stdin was closed
+fn stdin_closed() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "stdin was closed")
 }
 
 impl DebuggerUI for JsonUI {
     fn process(&mut self, mut feedback: crate::feedback::Feedback) -> Result<super::Status> {
-        let mut reader = BufReader::new(std::io::stdin());
-        let mut buf = Vec::new();
         loop {
             println!("{}", Self::format_feedback(&feedback)?);
-            buf.clear();
-            reader.read_until(b'\n', &mut buf)?;
-            let input: Input = match serde_json::from_slice(&buf) {
-                Ok(a) => a,
+            let buf = self.lines.recv().map_err(|_| stdin_closed())?;
+            match Self::parse_input(&buf) {
+                Ok(status) => return Ok(status),
                 Err(e) => {
                     error!("{e}");
-                    feedback = Feedback::Error(e.into());
-                    continue;
+                    feedback = Feedback::Error(e);
                 }
-            };
-            trace!("got input: {input:?}");
-            return Ok(input.status);
+            }
+        }
+    }
+
+    fn notify(&mut self, feedback: Feedback) -> Result<()> {
+        println!("{}", Self::format_feedback(&feedback)?);
+        Ok(())
+    }
+
+    fn poll(&mut self) -> Result<Option<Status>> {
+        let buf = match self.lines.try_recv() {
+            Ok(buf) => buf,
+            Err(TryRecvError::Empty) => return Ok(None),
+            Err(TryRecvError::Disconnected) => return Err(stdin_closed().into()),
+        };
+        match Self::parse_input(&buf) {
+            Ok(status) => Ok(Some(status)),
+            Err(e) => {
+                error!("{e}");
+                self.notify(Feedback::Error(e))?;
+                Ok(None)
+            }
         }
     }
 }
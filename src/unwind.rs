@@ -7,11 +7,15 @@
 //! calls, addresses, and names. Stack unwinding is essential for understanding
 //! the execution context of a program at a particular point in time.
 //!
+//! Core files cannot be unwound with [mod@unwind], as it needs a live process.
+//! [`unwind_frame_pointers`] follows the chain of saved frame pointers instead, which works for
+//! code that was compiled with frame pointers.
+//!
 //! The implementation is inspired by the `BugStalker` debugger project:
 //! <https://github.com/godzie44/BugStalker> (MIT Licensed)
 
 use crate::errors::Result;
-use crate::Addr;
+use crate::{Addr, Word, WORD_BYTES};
 
 use nix::unistd::Pid;
 use serde::Serialize;
@@ -164,3 +168,81 @@ pub fn unwind(pid: Pid) -> Result<Backtrace> {
 
     Ok(Backtrace::new(&frames))
 }
+
+/// Generates a [Backtrace] by following the chain of saved frame pointers
+///
+/// Every function compiled with frame pointers saves the frame pointer of its caller at
+/// `rbp` and has its return address right above it, at `rbp + 8`. The chain ends at a frame
+/// pointer of zero, or when the memory cannot be read. Functions compiled without frame pointers
+/// are skipped or end the chain.
+///
+/// # Parameters
+///
+/// * `rip` - Instruction pointer of the innermost frame
+/// * `rbp` - Frame pointer of the innermost frame
+/// * `read_word` - Reads a [Word] of memory
+/// * `function` - Finds the start address and name of the function containing an address
+///
+/// # Returns
+///
+/// The generated backtrace, which always contains at least the innermost frame
+///
+/// # Examples
+///
+/// ```
+/// use coreminer::unwind::unwind_frame_pointers;
+/// use coreminer::addr::Addr;
+/// use coreminer::errors::DebuggerError;
+///
+/// // the caller of the innermost frame returns to 0x1234, and has no caller itself
+/// let backtrace = unwind_frame_pointers(
+///     Addr::from(0x1000usize),
+///     Addr::from(0x7ff0usize),
+///     |addr| match addr.usize() {
+///         0x7ff0 => Ok(0),
+///         0x7ff8 => Ok(0x1234),
+///         _ => Err(DebuggerError::NotInCoreDump(addr)),
+///     },
+///     |_| None,
+/// );
+/// assert_eq!(backtrace.frames.len(), 2);
+/// assert_eq!(backtrace.frames[1].addr, Addr::from(0x1234usize));
+/// ```
+pub fn unwind_frame_pointers(
+    rip: Addr,
+    rbp: Addr,
+    read_word: impl Fn(Addr) -> Result<Word>,
+    function: impl Fn(Addr) -> Option<(Addr, String)>,
+) -> Backtrace {
+    /// Stops the walk in corrupted stacks that loop
+    const MAX_FRAMES: usize = 1024;
+
+    let frame = |addr: Addr| {
+        let (start_addr, name) = function(addr).unzip();
+        BacktraceFrame {
+            addr,
+            start_addr,
+            name,
+        }
+    };
+
+    let mut frames = vec![frame(rip)];
+    let mut rbp = rbp;
+    while rbp.usize() != 0 && frames.len() < MAX_FRAMES {
+        let (Ok(caller_rbp), Ok(return_addr)) = (read_word(rbp), read_word(rbp + WORD_BYTES))
+        else {
+            break;
+        };
+        if return_addr == 0 {
+            break;
+        }
+        frames.push(frame(return_addr.into()));
+        // the stack grows down, so the frames of callers are at higher addresses
+        if caller_rbp <= rbp.usize() {
+            break;
+        }
+        rbp = caller_rbp.into();
+    }
+
+    Backtrace::new(&frames)
+}
@@ -19,7 +19,7 @@ use crate::dbginfo::{search_through_symbols, OwnedSymbol, SymbolKind};
 use crate::debuggee::Debuggee;
 use crate::dwarf_parse::FrameInfo;
 use crate::errors::{DebuggerError, Result};
-use crate::{get_reg, mem_read, mem_write, set_reg, Addr, Word, WORD_BYTES};
+use crate::{mem_write, set_reg, Addr, Word, WORD_BYTES};
 
 /// A type alias for variable expressions (typically variable names)
 ///
@@ -339,7 +339,7 @@ impl Debuggee {
                 let _written = mem_write(&value_raw, self.pid, addr)?;
             }
             gimli::Location::Register { register } => {
-                set_reg(self.pid, register.try_into()?, value.to_u64())?;
+                set_reg(self.selected_thread, register.try_into()?, value.to_u64())?;
             }
             other => unimplemented!(
                 "writing to variable with gimli location of type {other:?} is not implemented"
@@ -413,18 +413,59 @@ impl Debuggee {
                     return Err(DebuggerError::SymbolHasNoByteSize);
                 };
                 let mut buf = vec![0; size];
-                let _len = mem_read(&mut buf, self.pid, addr)?;
+                let _len = self.read_memory(&mut buf, addr)?;
 
                 VariableValue::Bytes(buf)
             }
-            gimli::Location::Register { register } => {
-                VariableValue::Other(get_reg(self.pid, register.try_into()?)? as Word)
-            }
+            gimli::Location::Register { register } => VariableValue::Other(
+                self.get_reg(self.selected_thread, register.try_into()?)? as Word,
+            ),
             other => unimplemented!("gimli location of type {other:?} is not implemented"),
         };
 
         Ok(value)
     }
+
+    /// Finds the location of a variable in memory
+    ///
+    /// # Parameters
+    ///
+    /// * `sym` - The symbol representing the variable
+    /// * `frame_info` - Stack frame information
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((Addr, usize))` - The address of the variable and its size in bytes
+    /// * `Err(DebuggerError)` - If the variable is not in memory
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The symbol is not a valid variable
+    /// - The variable's location cannot be determined
+    /// - The variable is kept in a register or has no location in memory
+    /// - The data type of the variable cannot be determined
+    pub fn var_location(&self, sym: &OwnedSymbol, frame_info: &FrameInfo) -> Result<(Addr, usize)> {
+        Debuggee::check_sym_variable_ok(sym)?;
+        let Some(datatype) = self.get_type_for_symbol(sym)? else {
+            return Err(DebuggerError::NoDatatypeFound);
+        };
+        let Some(loc_attr) = sym.location() else {
+            return Err(DebuggerError::SymbolHasNoLocation);
+        };
+
+        match self.parse_location(loc_attr, frame_info, sym.encoding())? {
+            gimli::Location::Address { address } => {
+                let Some(size) = datatype.byte_size() else {
+                    return Err(DebuggerError::SymbolHasNoByteSize);
+                };
+                Ok((address.into(), size))
+            }
+            _ => Err(DebuggerError::VariableNotInMemory(
+                sym.name().unwrap_or_default().to_string(),
+            )),
+        }
+    }
 }
 
 fn serialize_gimli_value<S>(
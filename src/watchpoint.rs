@@ -0,0 +1,778 @@
+//! # Watchpoint Module
+//!
+//! Provides watchpoints, which stop the debuggee when it writes or reads a region of memory.
+//!
+//! A [`Watchpoint`] uses one of the debug registers if it can, like a
+//! [`HardwareBreakpoint`](crate::hwbreakpoint::HardwareBreakpoint). The watched location must be
+//! 1, 2, 4 or 8 bytes long and aligned to its length then. The CPU reports the access after the
+//! accessing instruction was executed, with a `SIGTRAP` with
+//! [`TRAP_HWBKPT`](crate::consts::TRAP_HWBKPT). The debug status register `DR6` tells which
+//! debug register was hit.
+//!
+//! Larger or misaligned regions, like buffers or structs, and regions watched when all debug
+//! registers are in use, are watched in software instead. The pages of the region are
+//! protected by making the debuggee call `mprotect`, which the debugger injects into a stopped
+//! thread. A write watchpoint takes away the write permission, a read/write watchpoint all
+//! permissions. Read/write watchpoints are refused on executable pages, the threads could
+//! neither run the code there nor the injected `mprotect`. Every access to the pages then
+//! raises a `SIGSEGV`, which is not delivered to the debuggee: the debugger lifts the
+//! protection, steps the accessing instruction and protects the pages again. The instruction
+//! is decoded to find out which memory it accessed, accesses to other parts of the pages
+//! continue silently. This is much slower than a debug register.
+//!
+//! The kernel does not raise a `SIGSEGV` when a syscall accesses a protected page, the syscall
+//! fails with `EFAULT` instead. Software watchpoints on memory that is passed to syscalls, like
+//! the buffer of a `read`, change the behavior of the debuggee. Watching the stack works, but
+//! every push and call on the same page is emulated.
+//!
+//! On a hit, the debugger reports a [`WatchpointHit`] with the content before and after the
+//! access and the address of the accessing instruction. For debug registers, it is found by
+//! disassembling the function that contains the instruction pointer.
+
+use std::fmt::Display;
+
+use iced_x86::{Decoder, DecoderOptions, InstructionInfoFactory, OpAccess};
+use nix::libc::{user_regs_struct, PROT_EXEC, PROT_NONE, PROT_WRITE};
+use nix::sys::ptrace;
+use nix::unistd::Pid;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, trace, warn};
+
+use crate::debuggee::Debuggee;
+use crate::errors::{DebuggerError, Result};
+use crate::hwbreakpoint::{take_debug_status, Trigger};
+use crate::{Addr, PAGE_SIZE};
+
+/// Number of `mprotect` on `x86_64` Linux
+const SYS_MPROTECT: u64 = 10;
+
+/// Maximum length of an `x86_64` instruction
+const MAX_INSTRUCTION_LEN: usize = 15;
+
+/// How many bytes of a large watched region are shown
+const SHOWN_BYTES: usize = 32;
+
+/// Which accesses a [`Watchpoint`] stops at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WatchKind {
+    /// Stop when the location is written
+    Write,
+    /// Stop when the location is read or written
+    ReadWrite,
+}
+
+/// A watched region in the memory of the debuggee
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Watchpoint {
+    addr: Addr,
+    len: usize,
+    kind: WatchKind,
+    hits: u64,
+    value: Vec<u8>,
+    /// The debug register, [`None`] if the region is watched by protecting its pages
+    #[serde(skip)]
+    slot: Option<usize>,
+}
+
+/// A hit of a [`Watchpoint`], as reported to the user
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct WatchpointHit {
+    /// Address of the watched region
+    pub addr: Addr,
+    /// Length of the watched region in bytes
+    pub len: usize,
+    /// Which accesses the watchpoint stops at
+    pub kind: WatchKind,
+    /// Address of the instruction that accessed the region, if it could be found
+    pub instruction: Option<Addr>,
+    /// Address of the accessed memory, if it is known
+    pub accessed: Option<Addr>,
+    /// Content of the region before the access
+    pub old: Vec<u8>,
+    /// Content of the region after the access
+    pub new: Vec<u8>,
+}
+
+/// An access of an instruction to memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MemoryAccess {
+    addr: Addr,
+    len: usize,
+    write: bool,
+}
+
+impl Watchpoint {
+    /// Returns the address of the watched region
+    #[must_use]
+    pub fn addr(&self) -> Addr {
+        self.addr
+    }
+
+    /// Returns the length of the watched region in bytes
+    #[must_use]
+    #[allow(clippy::len_without_is_empty)] // a watched region is never empty
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns which accesses the watchpoint stops at
+    #[must_use]
+    pub fn kind(&self) -> WatchKind {
+        self.kind
+    }
+
+    /// Returns how often the watchpoint was hit
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the content of the region at the last hit, or when the watchpoint was set
+    #[must_use]
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// Returns true if the watchpoint uses a debug register, false if it protects pages
+    #[must_use]
+    pub fn is_hardware(&self) -> bool {
+        self.slot.is_some()
+    }
+
+    /// Returns the addresses of the pages the watched region is on
+    fn pages(&self) -> impl Iterator<Item = Addr> {
+        let first = self.addr.usize() & !(PAGE_SIZE - 1);
+        (first..self.addr.usize() + self.len)
+            .step_by(PAGE_SIZE)
+            .map(Addr::from)
+    }
+
+    /// Checks if an access touches the watched region
+    fn overlaps(&self, access: &MemoryAccess) -> bool {
+        access.addr < self.addr + self.len && self.addr < access.addr + access.len
+    }
+}
+
+impl WatchKind {
+    /// Returns the [`Trigger`] of a debug register that watches `len` bytes for this kind
+    fn trigger(self, len: usize) -> Trigger {
+        match self {
+            Self::Write => Trigger::Write(len),
+            Self::ReadWrite => Trigger::ReadWrite(len),
+        }
+    }
+
+    /// Returns the protection of a page with this kind of watchpoint on it
+    ///
+    /// # Parameters
+    ///
+    /// * `original` - The protection of the page without watchpoints, as `PROT_*` flags
+    fn protection(self, original: i32) -> i32 {
+        match self {
+            Self::Write => original & !PROT_WRITE,
+            Self::ReadWrite => PROT_NONE,
+        }
+    }
+}
+
+impl Debuggee {
+    /// Sets a watchpoint on a region of memory
+    ///
+    /// A debug register is used if the region fits into one and one is free, else the pages of
+    /// the region are protected, see the [module documentation](self).
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - A stopped thread, which protects the pages of a software watchpoint
+    /// * `addr` - Address of the region
+    /// * `len` - Length of the region in bytes
+    /// * `kind` - Which accesses to stop at
+    ///
+    /// # Errors
+    ///
+    /// This function fails if:
+    /// - The region is empty or cannot be read
+    /// - There already is a watchpoint at the address
+    /// - The kernel refuses the address for a debug register
+    /// - The pages of the region cannot be protected, or a read/write watchpoint is on code
+    pub(crate) fn set_watchpoint(
+        &mut self,
+        tid: Pid,
+        addr: Addr,
+        len: usize,
+        kind: WatchKind,
+    ) -> Result<()> {
+        if len == 0 {
+            return Err(DebuggerError::InvalidWatchpoint(addr, len));
+        }
+        if self.watchpoints.contains_key(&addr) {
+            return Err(DebuggerError::WatchpointExists(addr));
+        }
+
+        let value = self.read_watched(addr, len)?;
+        let slot = if matches!(len, 1 | 2 | 4 | 8) && addr.usize() % len == 0 {
+            match self.take_debug_register(addr, kind.trigger(len)) {
+                Ok(slot) => Some(slot),
+                Err(DebuggerError::NoFreeDebugRegister) => None,
+                Err(e) => return Err(e),
+            }
+        } else {
+            None
+        };
+        let wp = Watchpoint {
+            addr,
+            len,
+            kind,
+            hits: 0,
+            value,
+            slot,
+        };
+        let pages: Vec<Addr> = if let Some(slot) = slot {
+            debug!("watchpoint at {addr} ({kind}, {len} bytes) uses DR{slot}");
+            Vec::new()
+        } else {
+            debug!("watchpoint at {addr} ({kind}, {len} bytes) protects its pages");
+            wp.pages().collect()
+        };
+        self.watchpoints.insert(addr, wp);
+        if let Err(e) = self.protect_pages(tid, &pages) {
+            self.watchpoints.remove(&addr);
+            self.protect_pages(tid, &pages)?;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Removes a watchpoint
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - A stopped thread, which restores the pages of a software watchpoint
+    /// * `addr` - Address of the watched region
+    ///
+    /// # Errors
+    ///
+    /// This function fails if there is no watchpoint at `addr`, or the debug registers or the
+    /// protection of the pages cannot be written.
+    pub(crate) fn del_watchpoint(&mut self, tid: Pid, addr: Addr) -> Result<()> {
+        let wp = self
+            .watchpoints
+            .remove(&addr)
+            .ok_or(DebuggerError::NoSuchWatchpoint(addr))?;
+        match wp.slot {
+            Some(slot) => self.release_debug_register(slot),
+            None => self.protect_pages(tid, &wp.pages().collect::<Vec<_>>()),
+        }
+    }
+
+    /// Returns all watchpoints
+    pub fn watchpoints(&self) -> impl Iterator<Item = &Watchpoint> {
+        self.watchpoints.values()
+    }
+
+    /// Checks if a thread stopped because it accessed a region watched by a debug register
+    ///
+    /// If it did, the hit is counted and the new content of the region is remembered for the
+    /// next hit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(WatchpointHit))` - If a watchpoint was hit
+    /// * `Ok(None)` - If the stop had nothing to do with a watchpoint
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the debug status of the thread or the watched region cannot be
+    /// read.
+    pub(crate) fn watchpoint_hit(&mut self, tid: Pid) -> Result<Option<WatchpointHit>> {
+        if !self.watchpoints.values().any(Watchpoint::is_hardware) {
+            return Ok(None);
+        }
+        let status = take_debug_status(tid)?;
+        trace!("debug status of thread {tid}: {status:#x}");
+        let Some(addr) = self
+            .watchpoints
+            .values()
+            .find(|wp| wp.slot.is_some_and(|slot| status & (1 << slot) != 0))
+            .map(Watchpoint::addr)
+        else {
+            return Ok(None);
+        };
+
+        let len = self.watchpoints[&addr].len;
+        let new = self.read_watched(addr, len)?;
+        let instruction =
+            self.instruction_before(Addr::from(crate::get_reg(tid, crate::Register::rip)?))?;
+        let wp = self
+            .watchpoints
+            .get_mut(&addr)
+            .expect("the watchpoint was just found");
+        wp.hits += 1;
+        let old = std::mem::replace(&mut wp.value, new.clone());
+        Ok(Some(WatchpointHit {
+            addr,
+            len,
+            kind: wp.kind,
+            instruction,
+            accessed: None,
+            old,
+            new,
+        }))
+    }
+
+    /// Checks if an address is on a page that is protected for a software watchpoint
+    pub(crate) fn is_guarded(&self, addr: Addr) -> bool {
+        self.guarded_pages
+            .contains_key(&Addr::from(addr.usize() & !(PAGE_SIZE - 1)))
+    }
+
+    /// Emulates an access of a thread to a page that is protected for a software watchpoint
+    ///
+    /// The thread stopped with a `SIGSEGV` at the accessing instruction. The protection is
+    /// lifted while the instruction is stepped, the `SIGSEGV` is not delivered. Watchpoints on
+    /// the accessed memory are hit if the access is of their kind, or if their content changed.
+    ///
+    /// # Parameters
+    ///
+    /// * `tid` - The thread that caused the `SIGSEGV`
+    /// * `fault` - The address that could not be accessed
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(WatchpointHit))` - If a watchpoint was hit
+    /// * `Ok(None)` - If the instruction accessed unwatched memory on a protected page
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the instruction cannot be stepped, or the protection of the pages
+    /// or the watched regions cannot be read or written.
+    pub(crate) fn emulate_guarded_access(
+        &mut self,
+        tid: Pid,
+        fault: Addr,
+    ) -> Result<Option<WatchpointHit>> {
+        let regs = ptrace::getregs(tid)?;
+        let instruction = Addr::from(regs.rip);
+        let mut accesses = self.decode_accesses(&regs)?;
+        if !accesses.iter().any(|access| {
+            self.is_guarded(access.addr) || self.is_guarded(access.addr + (access.len - 1))
+        }) {
+            // whether it wrote is found out by comparing the content
+            warn!("could not decode the access of the instruction at {instruction}");
+            accesses = vec![MemoryAccess {
+                addr: fault,
+                len: 1,
+                write: false,
+            }];
+        }
+        trace!("thread {tid} accessed {accesses:?} at {instruction}");
+        let watched: Vec<Addr> = self
+            .watchpoints
+            .values()
+            .filter(|wp| !wp.is_hardware() && accesses.iter().any(|a| wp.overlaps(a)))
+            .map(Watchpoint::addr)
+            .collect();
+
+        let guarded: Vec<Addr> = self.guarded_pages.keys().copied().collect();
+        self.lift_page_protection(tid)?;
+        let stepped = self.step_silently(tid);
+        self.protect_pages(tid, &guarded)?;
+        stepped?;
+        // a debug register may have seen the access too
+        let hardware_hit = self.watchpoint_hit(tid)?;
+
+        let mut hit = None;
+        for addr in watched {
+            let len = self.watchpoints[&addr].len;
+            let new = self.read_watched(addr, len)?;
+            let wp = self
+                .watchpoints
+                .get_mut(&addr)
+                .expect("the watchpoint was just found");
+            let access = accesses.iter().find(|a| wp.overlaps(a));
+            let written = access.is_some_and(|a| a.write) || new != wp.value;
+            if wp.kind == WatchKind::Write && !written {
+                continue;
+            }
+            wp.hits += 1;
+            let old = std::mem::replace(&mut wp.value, new.clone());
+            hit.get_or_insert(WatchpointHit {
+                addr,
+                len,
+                kind: wp.kind,
+                instruction: Some(instruction),
+                accessed: access.map(|a| a.addr),
+                old,
+                new,
+            });
+        }
+        Ok(hardware_hit.or(hit))
+    }
+
+    /// Gives the pages of all software watchpoints their original protection back and forgets
+    /// them, for example before detaching
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the protection of a page cannot be written.
+    pub(crate) fn unguard_pages(&mut self, tid: Pid) -> Result<()> {
+        self.lift_page_protection(tid)?;
+        self.guarded_pages.clear();
+        Ok(())
+    }
+
+    /// Gives the pages of all software watchpoints their original protection back for a while
+    ///
+    /// [`Self::guard_pages`] protects them again.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the protection of a page cannot be written.
+    pub(crate) fn lift_page_protection(&self, tid: Pid) -> Result<()> {
+        for (page, original) in &self.guarded_pages {
+            self.mprotect(tid, *page, *original)?;
+        }
+        Ok(())
+    }
+
+    /// Protects the pages of all software watchpoints again after
+    /// [`Self::lift_page_protection`]
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the protection of a page cannot be written.
+    pub(crate) fn guard_pages(&mut self, tid: Pid) -> Result<()> {
+        let pages: Vec<Addr> = self.guarded_pages.keys().copied().collect();
+        self.protect_pages(tid, &pages)
+    }
+
+    /// Sets the protection of pages to what the software watchpoints on them need
+    ///
+    /// Pages without software watchpoints get their original protection back.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if a page is not mapped, a read/write watchpoint would take away the
+    /// execute permission of a page of code, or the protection cannot be written.
+    fn protect_pages(&mut self, tid: Pid, pages: &[Addr]) -> Result<()> {
+        for page in pages {
+            let kind = self
+                .watchpoints
+                .values()
+                .filter(|wp| !wp.is_hardware() && wp.pages().any(|p| p == *page))
+                .map(Watchpoint::kind)
+                .max_by_key(|kind| *kind == WatchKind::ReadWrite);
+            let protection = match kind {
+                Some(kind) => {
+                    let original = match self.guarded_pages.get(page) {
+                        Some(original) => *original,
+                        None => self.original_protection(*page)?,
+                    };
+                    let protection = kind.protection(original);
+                    // mprotect is injected at the instruction pointer, which is on a page of
+                    // code: the thread could not execute it there anymore
+                    if original & PROT_EXEC != 0 && protection & PROT_EXEC == 0 {
+                        return Err(DebuggerError::WatchpointOnCode(*page));
+                    }
+                    self.guarded_pages.insert(*page, original);
+                    protection
+                }
+                None => match self.guarded_pages.remove(page) {
+                    Some(original) => original,
+                    None => continue,
+                },
+            };
+            self.mprotect(tid, *page, protection)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the protection of a page that the debugger did not change from the memory map
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the page is not mapped.
+    fn original_protection(&self, page: Addr) -> Result<i32> {
+        self.get_process_map()?
+            .regions
+            .iter()
+            .find(|region| region.start_address <= page && page < region.end_address)
+            .map(|region| region.permissions.protection())
+            .ok_or(DebuggerError::InvalidWatchpoint(page, PAGE_SIZE))
+    }
+
+    /// Changes the protection of a page by making a thread call `mprotect`
+    #[allow(clippy::cast_sign_loss)] // the PROT_* flags are not negative
+    fn mprotect(&self, tid: Pid, page: Addr, protection: i32) -> Result<()> {
+        trace!("mprotect({page}, {PAGE_SIZE:#x}, {protection:#x})");
+        self.inject_syscall(
+            tid,
+            SYS_MPROTECT,
+            &[page.u64(), PAGE_SIZE as u64, protection as u64],
+        )?;
+        Ok(())
+    }
+
+    /// Decodes the memory accesses of the instruction at the instruction pointer
+    ///
+    /// # Returns
+    ///
+    /// The accesses with their addresses, empty if the instruction could not be decoded.
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the code cannot be read.
+    fn decode_accesses(&self, regs: &user_regs_struct) -> Result<Vec<MemoryAccess>> {
+        let rip = Addr::from(regs.rip);
+        let mut code = [0; MAX_INSTRUCTION_LEN];
+        let len = self.read_memory(&mut code, rip)?;
+        self.hide_breakpoints(&mut code[..len], rip);
+
+        let instruction =
+            Decoder::with_ip(64, &code[..len], regs.rip, DecoderOptions::NONE).decode();
+        if instruction.is_invalid() {
+            return Ok(Vec::new());
+        }
+        let mut factory = InstructionInfoFactory::new();
+        Ok(factory
+            .info(&instruction)
+            .used_memory()
+            .iter()
+            .filter_map(|mem| {
+                let write = match mem.access() {
+                    OpAccess::Read | OpAccess::CondRead => false,
+                    OpAccess::Write
+                    | OpAccess::CondWrite
+                    | OpAccess::ReadWrite
+                    | OpAccess::ReadCondWrite => true,
+                    _ => return None,
+                };
+                let addr = mem.virtual_address(0, |reg, _, _| register_value(regs, reg))?;
+                Some(MemoryAccess {
+                    addr: Addr::from(addr),
+                    len: mem.memory_size().size().max(1),
+                    write,
+                })
+            })
+            .collect())
+    }
+
+    /// Reads the content of a watched region
+    ///
+    /// # Errors
+    ///
+    /// This function fails with [`DebuggerError::InvalidWatchpoint`] if the region is not
+    /// mapped.
+    fn read_watched(&self, addr: Addr, len: usize) -> Result<Vec<u8>> {
+        let mut buf = vec![0; len];
+        match self.read_memory(&mut buf, addr) {
+            Ok(read) if read == len => Ok(buf),
+            Ok(_) | Err(DebuggerError::Io(_)) => Err(DebuggerError::InvalidWatchpoint(addr, len)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Finds the instruction that ends right before an address
+    ///
+    /// Instructions on x86-64 have different lengths, so this disassembles the function that
+    /// contains `addr` from its start.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(Addr))` - The address of the instruction
+    /// * `Ok(None)` - If `addr` is not in a known function, or no instruction ends there
+    ///
+    /// # Errors
+    ///
+    /// This function fails if the debug information or the code cannot be read.
+    fn instruction_before(&self, addr: Addr) -> Result<Option<Addr>> {
+        let Some(start) = self
+            .get_function_by_addr(addr)?
+            .and_then(|f| f.low_addr())
+            .filter(|start| *start < addr)
+        else {
+            return Ok(None);
+        };
+        let disassembly = self.disassemble(start, (addr - start).usize(), false)?;
+        Ok(disassembly
+            .inner()
+            .iter()
+            .find(|(at, bytes, ..)| *at + bytes.len() == addr)
+            .map(|(at, ..)| *at))
+    }
+}
+
+/// Gets the value of a register for computing the address of a memory operand
+///
+/// Segment registers stand for their base address, which is only set for `fs` and `gs`.
+fn register_value(regs: &user_regs_struct, reg: iced_x86::Register) -> Option<u64> {
+    use iced_x86::Register as R;
+    let value = match reg.full_register() {
+        R::RAX => regs.rax,
+        R::RBX => regs.rbx,
+        R::RCX => regs.rcx,
+        R::RDX => regs.rdx,
+        R::RSI => regs.rsi,
+        R::RDI => regs.rdi,
+        R::RBP => regs.rbp,
+        R::RSP => regs.rsp,
+        R::R8 => regs.r8,
+        R::R9 => regs.r9,
+        R::R10 => regs.r10,
+        R::R11 => regs.r11,
+        R::R12 => regs.r12,
+        R::R13 => regs.r13,
+        R::R14 => regs.r14,
+        R::R15 => regs.r15,
+        R::ES | R::CS | R::SS | R::DS => 0,
+        R::FS => regs.fs_base,
+        R::GS => regs.gs_base,
+        _ => return None,
+    };
+    Some(match reg.size() {
+        size @ (1 | 2 | 4) if reg.is_gpr() => value & ((1 << (size * 8)) - 1),
+        _ => value,
+    })
+}
+
+/// Formats the content of a watched region: as a number if it fits into a [`Word`](crate::Word),
+/// else as [`Bytes`]
+struct Content<'a>(&'a [u8]);
+
+/// Formats memory as hexadecimal bytes, at most [`SHOWN_BYTES`] of them
+struct Bytes<'a>(&'a [u8]);
+
+impl Display for Content<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.len() > std::mem::size_of::<u64>() {
+            return Bytes(self.0).fmt(f);
+        }
+        let mut word = [0; 8];
+        word[..self.0.len()].copy_from_slice(self.0);
+        write!(f, "{:#x}", u64::from_le_bytes(word))
+    }
+}
+
+impl Display for Bytes<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, byte) in self.0.iter().take(SHOWN_BYTES).enumerate() {
+            if idx > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+        if self.0.len() > SHOWN_BYTES {
+            write!(f, " ...")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for WatchKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Write => write!(f, "write"),
+            Self::ReadWrite => write!(f, "read/write"),
+        }
+    }
+}
+
+impl Display for Watchpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:<10} {} bytes{} hits: {:<6} value: {}",
+            self.addr,
+            self.kind,
+            self.len,
+            if self.is_hardware() { "" } else { " software" },
+            self.hits,
+            Content(&self.value)
+        )
+    }
+}
+
+impl Display for WatchpointHit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Watchpoint at {} ({}, {} bytes) hit",
+            self.addr, self.kind, self.len
+        )?;
+        if let Some(instruction) = self.instruction {
+            write!(f, " by the instruction at {instruction}")?;
+        }
+        if let Some(accessed) = self.accessed {
+            write!(f, ", which accessed {accessed}")?;
+        }
+        if self.len <= std::mem::size_of::<u64>() {
+            return write!(
+                f,
+                "\n  old: {}\n  new: {}",
+                Content(&self.old),
+                Content(&self.new)
+            );
+        }
+        // only the changed bytes of a large region are interesting
+        let changed = |(idx, (old, new)): (usize, (&u8, &u8))| (old != new).then_some(idx);
+        let pairs = || self.old.iter().zip(&self.new).enumerate();
+        match (pairs().find_map(changed), pairs().rev().find_map(changed)) {
+            (Some(first), Some(last)) => write!(
+                f,
+                "\n  changed at {}:\n  old: {}\n  new: {}",
+                self.addr + first,
+                Bytes(&self.old[first..=last]),
+                Bytes(&self.new[first..=last])
+            ),
+            _ => write!(f, "\n  unchanged"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_watchpoint_pages() {
+        let wp = Watchpoint {
+            addr: Addr::from(0x4ff8usize),
+            len: 0x1010,
+            kind: WatchKind::Write,
+            hits: 0,
+            value: Vec::new(),
+            slot: None,
+        };
+        let pages: Vec<Addr> = wp.pages().collect();
+        assert_eq!(
+            pages,
+            [0x4000usize, 0x5000, 0x6000].map(Addr::from).to_vec()
+        );
+        assert_eq!(
+            WatchKind::ReadWrite.protection(nix::libc::PROT_READ),
+            PROT_NONE
+        );
+    }
+
+    #[test]
+    fn test_hit_shows_changed_bytes() {
+        let mut hit = WatchpointHit {
+            addr: Addr::from(0x1000usize),
+            len: 16,
+            kind: WatchKind::Write,
+            instruction: None,
+            accessed: Some(Addr::from(0x1004usize)),
+            old: vec![0; 16],
+            new: vec![0; 16],
+        };
+        hit.new[4] = 0x61;
+        hit.new[5] = 0x62;
+        assert!(hit
+            .to_string()
+            .ends_with("changed at 0x0000000000001004:\n  old: 00 00\n  new: 61 62"));
+        hit.len = 4;
+        hit.old = vec![1, 0, 0, 0];
+        hit.new = vec![2, 0, 0, 0];
+        assert!(hit.to_string().ends_with("old: 0x1\n  new: 0x2"));
+    }
+}